@@ -0,0 +1,135 @@
+use crate::{pda, ClientError, Result};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use micro_lending::{
+    LenderDeposit, LendingPool, Loan, LoanStatus, Platform, SocialAttestation, UserProfile,
+};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+
+// Byte offsets into Loan account data, after the 8-byte discriminator
+pub const LOAN_BORROWER_OFFSET: usize = 8;
+pub const LOAN_LENDER_POOL_OFFSET: usize = LOAN_BORROWER_OFFSET + 32;
+// borrower, lender_pool, amount, interest_rate, duration_days, disbursed_at, due_date,
+// amount_repaid, interest_accrued
+pub const LOAN_STATUS_OFFSET: usize = 8 + 32 + 32 + 8 + 2 + 4 + 8 + 8 + 8 + 8;
+
+// Byte offset of LendingPool.authority
+pub const LENDING_POOL_AUTHORITY_OFFSET: usize = 8;
+
+// Byte offset of SocialAttestation.user
+pub const SOCIAL_ATTESTATION_USER_OFFSET: usize = 8;
+
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    let mut data = data;
+    T::try_deserialize(&mut data).map_err(ClientError::from)
+}
+
+pub fn fetch<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T> {
+    fetch_optional(rpc, address)?.ok_or(ClientError::AccountNotFound(*address))
+}
+
+pub fn fetch_optional<T: AccountDeserialize>(
+    rpc: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<T>> {
+    let account = rpc
+        .get_account_with_commitment(address, rpc.commitment())?
+        .value;
+    match account {
+        Some(account) if account.owner == micro_lending::ID => decode(&account.data).map(Some),
+        Some(_) => Err(ClientError::InvalidOwner(*address)),
+        None => Ok(None),
+    }
+}
+
+// Every program account of type T matching the extra filters; the discriminator filter is added here
+pub fn fetch_all<T: AccountDeserialize + Discriminator>(
+    rpc: &RpcClient,
+    mut filters: Vec<RpcFilterType>,
+) -> Result<Vec<(Pubkey, T)>> {
+    filters.insert(
+        0,
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, T::DISCRIMINATOR)),
+    );
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    rpc.get_program_accounts_with_config(&micro_lending::ID, config)?
+        .into_iter()
+        .map(|(address, account)| Ok((address, decode(&account.data)?)))
+        .collect()
+}
+
+pub fn fetch_platform(rpc: &RpcClient) -> Result<Platform> {
+    fetch(rpc, &pda::platform().0)
+}
+
+pub fn fetch_user_profile(rpc: &RpcClient, user: &Pubkey) -> Result<UserProfile> {
+    fetch(rpc, &pda::user_profile(user).0)
+}
+
+pub fn fetch_lending_pool(rpc: &RpcClient, lending_pool: &Pubkey) -> Result<LendingPool> {
+    fetch(rpc, lending_pool)
+}
+
+pub fn fetch_loan(rpc: &RpcClient, borrower: &Pubkey, lending_pool: &Pubkey) -> Result<Loan> {
+    fetch(rpc, &pda::loan(borrower, lending_pool).0)
+}
+
+pub fn fetch_lender_deposit(
+    rpc: &RpcClient,
+    lender: &Pubkey,
+    lending_pool: &Pubkey,
+) -> Result<LenderDeposit> {
+    fetch(rpc, &pda::lender_deposit(lender, lending_pool).0)
+}
+
+pub fn fetch_lending_pools(rpc: &RpcClient) -> Result<Vec<(Pubkey, LendingPool)>> {
+    fetch_all(rpc, vec![])
+}
+
+pub fn fetch_loans_by_pool(rpc: &RpcClient, lending_pool: &Pubkey) -> Result<Vec<(Pubkey, Loan)>> {
+    fetch_all(
+        rpc,
+        vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            LOAN_LENDER_POOL_OFFSET,
+            lending_pool.as_ref(),
+        ))],
+    )
+}
+
+pub fn fetch_loans_by_status(rpc: &RpcClient, status: LoanStatus) -> Result<Vec<(Pubkey, Loan)>> {
+    fetch_all(
+        rpc,
+        vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            LOAN_STATUS_OFFSET,
+            &[status as u8],
+        ))],
+    )
+}
+
+pub fn fetch_user_profiles(rpc: &RpcClient) -> Result<Vec<(Pubkey, UserProfile)>> {
+    fetch_all(rpc, vec![])
+}
+
+pub fn fetch_attestations_for(
+    rpc: &RpcClient,
+    user: &Pubkey,
+) -> Result<Vec<(Pubkey, SocialAttestation)>> {
+    fetch_all(
+        rpc,
+        vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            SOCIAL_ATTESTATION_USER_OFFSET,
+            user.as_ref(),
+        ))],
+    )
+}
@@ -0,0 +1,1091 @@
+use crate::pda;
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token;
+use micro_lending::{
+    accounts, instruction, loan_consent_message, AutoApprovalRules, BorrowerPolicy, LoanConsent,
+    RepaymentIncentives, TransactionType, VerificationType,
+};
+use solana_ed25519_program::new_ed25519_instruction_with_signature;
+
+fn build(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining_accounts);
+    Instruction {
+        program_id: micro_lending::ID,
+        accounts: metas,
+        data: data.data(),
+    }
+}
+
+// A user's social attestations from `attesters`, followed by the attesters' registry PDAs
+// that the program checks verified attestations against
+fn attestation_accounts(user: &Pubkey, attesters: &[Pubkey]) -> Vec<AccountMeta> {
+    let attestations = attesters.iter().map(|attester| {
+        AccountMeta::new_readonly(pda::social_attestation(user, attester).0, false)
+    });
+    let registries = attesters
+        .iter()
+        .map(|attester| AccountMeta::new_readonly(pda::attester(attester).0, false));
+    attestations.chain(registries).collect()
+}
+
+// Registry PDAs of a loan's backing attesters, settled when the loan closes
+fn attester_registries(backing_attesters: &[Pubkey]) -> Vec<AccountMeta> {
+    backing_attesters
+        .iter()
+        .map(|attester| AccountMeta::new(pda::attester(attester).0, false))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Platform
+// ---------------------------------------------------------------------------
+
+pub fn initialize_platform(
+    payer: &Pubkey,
+    platform_authority: Pubkey,
+    platform_fee: u16,
+    max_loan_amount: u64,
+    min_loan_amount: u64,
+    score_half_life_days: u16,
+    inactivity_threshold_days: u16,
+) -> Instruction {
+    let (treasury, treasury_bump) = pda::treasury();
+    build(
+        accounts::InitializePlatform {
+            platform: pda::platform().0,
+            treasury,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::InitializePlatform {
+            platform_authority,
+            treasury_bump,
+            platform_fee,
+            max_loan_amount,
+            min_loan_amount,
+            score_half_life_days,
+            inactivity_threshold_days,
+        },
+        vec![],
+    )
+}
+
+pub fn add_verifier(authority: &Pubkey, verifier: Pubkey) -> Instruction {
+    build(
+        accounts::AddVerifier {
+            authority: *authority,
+            platform: pda::platform().0,
+        },
+        instruction::AddVerifier { verifier },
+        vec![],
+    )
+}
+
+pub fn remove_verifier(authority: &Pubkey, verifier: Pubkey) -> Instruction {
+    build(
+        accounts::RemoveVerifier {
+            authority: *authority,
+            platform: pda::platform().0,
+        },
+        instruction::RemoveVerifier { verifier },
+        vec![],
+    )
+}
+
+pub fn set_lending_ladder(
+    authority: &Pubkey,
+    first_loan_cap: u64,
+    step_up_bps: u16,
+    step_down_bps: u16,
+) -> Instruction {
+    build(
+        accounts::SetLendingLadder {
+            authority: *authority,
+            platform: pda::platform().0,
+        },
+        instruction::SetLendingLadder {
+            first_loan_cap,
+            step_up_bps,
+            step_down_bps,
+        },
+        vec![],
+    )
+}
+
+pub fn declare_disaster(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    forbearance_days: u16,
+) -> Instruction {
+    build(
+        accounts::DeclareDisaster {
+            platform: pda::platform().0,
+            lending_pool: *lending_pool,
+            authority: *authority,
+        },
+        instruction::DeclareDisaster { forbearance_days },
+        vec![],
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Users, verification and credit history
+// ---------------------------------------------------------------------------
+
+pub fn initialize_user(user: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeUser {
+            user_profile: pda::user_profile(user).0,
+            user: *user,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeUser {},
+        vec![],
+    )
+}
+
+pub fn set_verification(
+    verifier: &Pubkey,
+    user: &Pubkey,
+    verification_type: VerificationType,
+    verified: bool,
+    expires_at: Option<i64>,
+    evidence_hash: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::SetVerification {
+            verifier: *verifier,
+            platform: pda::platform().0,
+            user: *user,
+            user_profile: pda::user_profile(user).0,
+            verification_record: pda::verification_record(user, verification_type).0,
+            system_program: system_program::ID,
+        },
+        instruction::SetVerification {
+            verification_type,
+            verified,
+            expires_at,
+            evidence_hash,
+        },
+        vec![],
+    )
+}
+
+// `history_index` is the user's current transaction_history_count
+#[allow(clippy::too_many_arguments)]
+pub fn add_transaction_history(
+    authority: &Pubkey,
+    user: &Pubkey,
+    history_index: u16,
+    transaction_type: TransactionType,
+    amount: u64,
+    counterparty: Option<Pubkey>,
+    timestamp: i64,
+    frequency_score: u16,
+    consistency_score: u16,
+) -> Instruction {
+    build(
+        accounts::AddTransactionHistory {
+            authority: *authority,
+            platform: pda::platform().0,
+            user: *user,
+            user_profile: pda::user_profile(user).0,
+            transaction_history: pda::transaction_history(user, history_index).0,
+            system_program: system_program::ID,
+        },
+        instruction::AddTransactionHistory {
+            transaction_type,
+            amount,
+            counterparty,
+            timestamp,
+            frequency_score,
+            consistency_score,
+        },
+        vec![],
+    )
+}
+
+// `attesters` are those whose attestations of the user count towards the score
+pub fn update_credit_score(authority: &Pubkey, user: &Pubkey, attesters: &[Pubkey]) -> Instruction {
+    build(
+        accounts::UpdateCreditScore {
+            authority: *authority,
+            platform: pda::platform().0,
+            user_profile: pda::user_profile(user).0,
+            user: *user,
+        },
+        instruction::UpdateCreditScore {},
+        attestation_accounts(user, attesters),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Attesters and social attestations
+// ---------------------------------------------------------------------------
+
+pub fn register_attester(
+    authority: &Pubkey,
+    attester: &Pubkey,
+    name: String,
+    allowed_types: u8,
+) -> Instruction {
+    build(
+        accounts::RegisterAttester {
+            authority: *authority,
+            platform: pda::platform().0,
+            attester: *attester,
+            attester_registry: pda::attester(attester).0,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterAttester {
+            name,
+            allowed_types,
+        },
+        vec![],
+    )
+}
+
+pub fn update_attester(
+    authority: &Pubkey,
+    attester: &Pubkey,
+    allowed_types: u8,
+    is_active: bool,
+) -> Instruction {
+    build(
+        accounts::UpdateAttester {
+            authority: *authority,
+            platform: pda::platform().0,
+            attester_registry: pda::attester(attester).0,
+        },
+        instruction::UpdateAttester {
+            allowed_types,
+            is_active,
+        },
+        vec![],
+    )
+}
+
+// Registered attesters pass their registry so the attestation is marked verified
+pub fn add_attestation(
+    attester: &Pubkey,
+    user: &Pubkey,
+    registered_attester: bool,
+    attestation_type: u8,
+    score: u16,
+    metadata: String,
+    expires_at: Option<i64>,
+) -> Instruction {
+    build(
+        accounts::AddAttestation {
+            attester: *attester,
+            user: *user,
+            user_profile: pda::user_profile(user).0,
+            social_attestation: pda::social_attestation(user, attester).0,
+            attester_registry: registered_attester.then(|| pda::attester(attester).0),
+            system_program: system_program::ID,
+        },
+        instruction::AddAttestation {
+            attestation_type,
+            score,
+            metadata,
+            expires_at,
+        },
+        vec![],
+    )
+}
+
+// Registered attesters pass their registry so the attestation stays verified
+pub fn update_attestation(
+    attester: &Pubkey,
+    user: &Pubkey,
+    registered_attester: bool,
+    score: u16,
+    metadata: String,
+) -> Instruction {
+    build(
+        accounts::UpdateAttestation {
+            attester: *attester,
+            user: *user,
+            social_attestation: pda::social_attestation(user, attester).0,
+            attester_registry: registered_attester.then(|| pda::attester(attester).0),
+        },
+        instruction::UpdateAttestation { score, metadata },
+        vec![],
+    )
+}
+
+// Registered attesters pass their registry so the attestation stays verified
+pub fn renew_attestation(
+    attester: &Pubkey,
+    user: &Pubkey,
+    registered_attester: bool,
+    expires_at: Option<i64>,
+) -> Instruction {
+    build(
+        accounts::RenewAttestation {
+            attester: *attester,
+            user: *user,
+            user_profile: pda::user_profile(user).0,
+            social_attestation: pda::social_attestation(user, attester).0,
+            attester_registry: registered_attester.then(|| pda::attester(attester).0),
+        },
+        instruction::RenewAttestation { expires_at },
+        vec![],
+    )
+}
+
+pub fn revoke_attestation(attester: &Pubkey, user: &Pubkey) -> Instruction {
+    build(
+        accounts::RevokeAttestation {
+            attester: *attester,
+            user: *user,
+            user_profile: pda::user_profile(user).0,
+            social_attestation: pda::social_attestation(user, attester).0,
+        },
+        instruction::RevokeAttestation {},
+        vec![],
+    )
+}
+
+pub fn expire_attestation(user: &Pubkey, attester: &Pubkey) -> Instruction {
+    build(
+        accounts::ExpireAttestation {
+            user_profile: pda::user_profile(user).0,
+            social_attestation: pda::social_attestation(user, attester).0,
+        },
+        instruction::ExpireAttestation {},
+        vec![],
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Lending pools
+// ---------------------------------------------------------------------------
+
+pub fn create_lending_pool(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    pool_name: String,
+    base_interest_rate: u16,
+    max_loan_duration: i64,
+) -> Instruction {
+    let lending_pool = pda::lending_pool(authority, mint).0;
+    build(
+        accounts::CreateLendingPool {
+            lending_pool,
+            pool_token_account: pda::pool_token_account(&lending_pool).0,
+            mint: *mint,
+            authority: *authority,
+            token_program: *token_program,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::CreateLendingPool {
+            pool_name,
+            base_interest_rate,
+            max_loan_duration,
+        },
+        vec![],
+    )
+}
+
+pub fn set_borrower_policy(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    policy: BorrowerPolicy,
+) -> Instruction {
+    build(
+        accounts::SetBorrowerPolicy {
+            lending_pool: *lending_pool,
+            authority: *authority,
+        },
+        instruction::SetBorrowerPolicy { policy },
+        vec![],
+    )
+}
+
+pub fn set_pool_verification_requirements(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    required_verifications: u8,
+) -> Instruction {
+    build(
+        accounts::SetPoolVerificationRequirements {
+            lending_pool: *lending_pool,
+            authority: *authority,
+        },
+        instruction::SetPoolVerificationRequirements {
+            required_verifications,
+        },
+        vec![],
+    )
+}
+
+pub fn set_auto_approval_rules(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    rules: AutoApprovalRules,
+) -> Instruction {
+    build(
+        accounts::SetAutoApprovalRules {
+            lending_pool: *lending_pool,
+            authority: *authority,
+        },
+        instruction::SetAutoApprovalRules { rules },
+        vec![],
+    )
+}
+
+pub fn set_repayment_incentives(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    incentives: RepaymentIncentives,
+) -> Instruction {
+    build(
+        accounts::SetRepaymentIncentives {
+            lending_pool: *lending_pool,
+            authority: *authority,
+        },
+        instruction::SetRepaymentIncentives { incentives },
+        vec![],
+    )
+}
+
+pub fn set_loan_ttls(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    request_ttl: i64,
+    approval_ttl: i64,
+) -> Instruction {
+    build(
+        accounts::SetLoanTtls {
+            lending_pool: *lending_pool,
+            authority: *authority,
+        },
+        instruction::SetLoanTtls {
+            request_ttl,
+            approval_ttl,
+        },
+        vec![],
+    )
+}
+
+pub fn register_loan_officer(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    officer: &Pubkey,
+    commission_bps: u16,
+) -> Instruction {
+    build(
+        accounts::RegisterLoanOfficer {
+            loan_officer: pda::loan_officer(lending_pool, officer).0,
+            lending_pool: *lending_pool,
+            officer: *officer,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterLoanOfficer { commission_bps },
+        vec![],
+    )
+}
+
+pub fn update_loan_officer(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    officer: &Pubkey,
+    commission_bps: u16,
+    is_active: bool,
+) -> Instruction {
+    build(
+        accounts::UpdateLoanOfficer {
+            loan_officer: pda::loan_officer(lending_pool, officer).0,
+            lending_pool: *lending_pool,
+            authority: *authority,
+        },
+        instruction::UpdateLoanOfficer {
+            commission_bps,
+            is_active,
+        },
+        vec![],
+    )
+}
+
+pub fn create_loan_committee(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    members: Vec<Pubkey>,
+    quorum: u8,
+    voting_window: i64,
+) -> Instruction {
+    build(
+        accounts::CreateLoanCommittee {
+            loan_committee: pda::loan_committee(lending_pool).0,
+            lending_pool: *lending_pool,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::CreateLoanCommittee {
+            members,
+            quorum,
+            voting_window,
+        },
+        vec![],
+    )
+}
+
+pub fn update_loan_committee(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    members: Vec<Pubkey>,
+    quorum: u8,
+    voting_window: i64,
+) -> Instruction {
+    build(
+        accounts::UpdateLoanCommittee {
+            loan_committee: pda::loan_committee(lending_pool).0,
+            lending_pool: *lending_pool,
+            authority: *authority,
+        },
+        instruction::UpdateLoanCommittee {
+            members,
+            quorum,
+            voting_window,
+        },
+        vec![],
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Lenders
+// ---------------------------------------------------------------------------
+
+pub fn deposit_to_pool(
+    lender: &Pubkey,
+    lending_pool: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::DepositToPool {
+            lending_pool: *lending_pool,
+            mint: *mint,
+            lender_deposit: pda::lender_deposit(lender, lending_pool).0,
+            pool_token_account: pda::pool_token_account(lending_pool).0,
+            lender_token_account: pda::token_account(lender, mint, token_program),
+            associated_token_program: associated_token::ID,
+            lender: *lender,
+            token_program: *token_program,
+            system_program: system_program::ID,
+        },
+        instruction::DepositToPool { amount },
+        vec![],
+    )
+}
+
+pub fn claim_interest(
+    lender: &Pubkey,
+    lending_pool: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ClaimInterest {
+            lender: *lender,
+            mint: *mint,
+            lending_pool: *lending_pool,
+            lender_deposit: pda::lender_deposit(lender, lending_pool).0,
+            pool_token_account: pda::pool_token_account(lending_pool).0,
+            lender_token_account: pda::token_account(lender, mint, token_program),
+            token_program: *token_program,
+            system_program: system_program::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ClaimInterest {},
+        vec![],
+    )
+}
+
+pub fn withdraw_from_pool(
+    lender: &Pubkey,
+    lending_pool: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    shares_to_withdraw: u64,
+) -> Instruction {
+    build(
+        accounts::WithdrawFromPool {
+            lender: *lender,
+            lending_pool: *lending_pool,
+            lender_deposit: pda::lender_deposit(lender, lending_pool).0,
+            pool_token_account: pda::pool_token_account(lending_pool).0,
+            lender_token_account: pda::token_account(lender, mint, token_program),
+            associated_token_program: associated_token::ID,
+            mint: *mint,
+            token_program: *token_program,
+            system_program: system_program::ID,
+        },
+        instruction::WithdrawFromPool { shares_to_withdraw },
+        vec![],
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Loan lifecycle
+// ---------------------------------------------------------------------------
+
+// `attesters` are those whose attestations of the borrower back the request;
+// `officer` co-signs when the loan is originated by a field officer
+#[allow(clippy::too_many_arguments)]
+pub fn request_loan(
+    borrower: &Pubkey,
+    lending_pool: &Pubkey,
+    officer: Option<&Pubkey>,
+    attesters: &[Pubkey],
+    amount: u64,
+    duration_days: u32,
+    purpose: String,
+    collateral_type: u8,
+) -> Instruction {
+    let mut instruction = build(
+        accounts::RequestLoan {
+            platform: pda::platform().0,
+            user_profile: pda::user_profile(borrower).0,
+            lending_pool: *lending_pool,
+            loan: pda::loan(borrower, lending_pool).0,
+            borrower: *borrower,
+            payer: *borrower,
+            loan_officer: officer.map(|officer| pda::loan_officer(lending_pool, officer).0),
+            officer: officer.copied(),
+            instructions: None,
+            system_program: system_program::ID,
+        },
+        instruction::RequestLoan {
+            amount,
+            duration_days,
+            purpose,
+            collateral_type,
+        },
+        attestation_accounts(borrower, attesters),
+    );
+    // The borrower account is not a Signer in the program, as it need not sign with consent
+    for meta in &mut instruction.accounts {
+        if meta.pubkey == *borrower {
+            meta.is_signer = true;
+        }
+    }
+    instruction
+}
+
+// A request a loan officer submits and pays for on behalf of a borrower who signed
+// `loan_consent_message(&consent)` off-chain, giving `signature`. The ed25519 instruction
+// verifying it must come immediately before the request.
+pub fn request_loan_with_consent(
+    borrower: &Pubkey,
+    attesters: &[Pubkey],
+    consent: LoanConsent,
+    signature: &[u8; 64],
+) -> [Instruction; 2] {
+    let officer = consent.officer;
+    let lending_pool = consent.lending_pool;
+    let verify_consent = new_ed25519_instruction_with_signature(
+        &loan_consent_message(&consent),
+        signature,
+        &borrower.to_bytes(),
+    );
+    let request = build(
+        accounts::RequestLoan {
+            platform: pda::platform().0,
+            user_profile: pda::user_profile(borrower).0,
+            lending_pool,
+            loan: pda::loan(borrower, &lending_pool).0,
+            borrower: *borrower,
+            payer: officer,
+            loan_officer: Some(pda::loan_officer(&lending_pool, &officer).0),
+            officer: Some(officer),
+            instructions: Some(sysvar::instructions::ID),
+            system_program: system_program::ID,
+        },
+        instruction::RequestLoan {
+            amount: consent.amount,
+            duration_days: consent.duration_days,
+            purpose: consent.purpose,
+            collateral_type: consent.collateral_type,
+        },
+        attestation_accounts(borrower, attesters),
+    );
+    [verify_consent, request]
+}
+
+pub fn cancel_loan_request(borrower: &Pubkey, lending_pool: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelLoanRequest {
+            loan: pda::loan(borrower, lending_pool).0,
+            lending_pool: *lending_pool,
+            borrower: *borrower,
+        },
+        instruction::CancelLoanRequest {},
+        vec![],
+    )
+}
+
+pub fn vote_on_loan(
+    member: &Pubkey,
+    lending_pool: &Pubkey,
+    borrower: &Pubkey,
+    loan_created_at: i64,
+    approve: bool,
+) -> Instruction {
+    let loan = pda::loan(borrower, lending_pool).0;
+    build(
+        accounts::VoteOnLoan {
+            member: *member,
+            loan_committee: pda::loan_committee(lending_pool).0,
+            lending_pool: *lending_pool,
+            loan,
+            borrower: *borrower,
+            loan_vote: pda::loan_vote(&loan, loan_created_at, member).0,
+            system_program: system_program::ID,
+        },
+        instruction::VoteOnLoan { approve },
+        vec![],
+    )
+}
+
+// Pools with a committee pass it so its quorum is checked
+pub fn approve_loan(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    borrower: &Pubkey,
+    has_committee: bool,
+) -> Instruction {
+    build(
+        accounts::ApproveLoan {
+            loan: pda::loan(borrower, lending_pool).0,
+            lending_pool: *lending_pool,
+            loan_committee: has_committee.then(|| pda::loan_committee(lending_pool).0),
+            authority: *authority,
+        },
+        instruction::ApproveLoan {},
+        vec![],
+    )
+}
+
+pub fn reject_loan(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    borrower: &Pubkey,
+    reason_code: u8,
+) -> Instruction {
+    build(
+        accounts::RejectLoan {
+            loan: pda::loan(borrower, lending_pool).0,
+            lending_pool: *lending_pool,
+            borrower: *borrower,
+            authority: *authority,
+        },
+        instruction::RejectLoan { reason_code },
+        vec![],
+    )
+}
+
+pub fn expire_loan(keeper: &Pubkey, lending_pool: &Pubkey, borrower: &Pubkey) -> Instruction {
+    build(
+        accounts::ExpireLoan {
+            keeper: *keeper,
+            loan: pda::loan(borrower, lending_pool).0,
+            lending_pool: *lending_pool,
+            borrower: *borrower,
+        },
+        instruction::ExpireLoan {},
+        vec![],
+    )
+}
+
+// `officer` is the loan's originating officer, if any
+pub fn disburse_loan(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    borrower: &Pubkey,
+    officer: Option<&Pubkey>,
+) -> Instruction {
+    let lending_pool = pda::lending_pool(authority, mint).0;
+    build(
+        accounts::DisburseLoan {
+            authority: *authority,
+            platform: pda::platform().0,
+            loan: pda::loan(borrower, &lending_pool).0,
+            mint: *mint,
+            lending_pool,
+            user_profile: pda::user_profile(borrower).0,
+            pool_token_account: pda::pool_token_account(&lending_pool).0,
+            borrower: *borrower,
+            borrower_token_account: pda::token_account(borrower, mint, token_program),
+            loan_officer: officer.map(|officer| pda::loan_officer(&lending_pool, officer).0),
+            token_program: *token_program,
+            system_program: system_program::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::DisburseLoan {},
+        vec![],
+    )
+}
+
+// `backing_attesters` is Loan.backing_attesters; their registries are settled on full repayment
+pub fn repay_loan(
+    borrower: &Pubkey,
+    lending_pool: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    officer: Option<&Pubkey>,
+    backing_attesters: &[Pubkey],
+    payment_amount: u64,
+) -> Instruction {
+    build(
+        accounts::MakePayment {
+            platform: pda::platform().0,
+            mint: *mint,
+            loan: pda::loan(borrower, lending_pool).0,
+            lending_pool: *lending_pool,
+            user_profile: pda::user_profile(borrower).0,
+            pool_token_account: pda::pool_token_account(lending_pool).0,
+            borrower_token_account: pda::token_account(borrower, mint, token_program),
+            associated_token_program: associated_token::ID,
+            treasury_token_account: pda::treasury_token_account(mint, token_program),
+            borrower: *borrower,
+            loan_officer: officer.map(|officer| pda::loan_officer(lending_pool, officer).0),
+            officer_token_account: officer
+                .map(|officer| pda::token_account(officer, mint, token_program)),
+            system_program: system_program::ID,
+            token_program: *token_program,
+        },
+        instruction::RepayLoan { payment_amount },
+        attester_registries(backing_attesters),
+    )
+}
+
+pub fn restructure_loan(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    borrower: &Pubkey,
+    officer: Option<&Pubkey>,
+    new_duration_days: u32,
+    new_interest_rate: u16,
+    capitalize_arrears: bool,
+) -> Instruction {
+    build(
+        accounts::RestructureLoan {
+            platform: pda::platform().0,
+            loan: pda::loan(borrower, lending_pool).0,
+            lending_pool: *lending_pool,
+            user_profile: pda::user_profile(borrower).0,
+            loan_officer: officer.map(|officer| pda::loan_officer(lending_pool, officer).0),
+            authority: *authority,
+            borrower: *borrower,
+        },
+        instruction::RestructureLoan {
+            new_duration_days,
+            new_interest_rate,
+            capitalize_arrears,
+        },
+        vec![],
+    )
+}
+
+// `loan_created_at` is the current loan's created_at, which keys the archived copy.
+// `backing_attesters` are the current loan's, settled by the payoff; `attesters`' attestations
+// back the new loan; in committee pools, `committee_members` approve by co-signing.
+#[allow(clippy::too_many_arguments)]
+pub fn refinance_loan(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    borrower: &Pubkey,
+    officer: Option<&Pubkey>,
+    loan_created_at: i64,
+    backing_attesters: &[Pubkey],
+    attesters: &[Pubkey],
+    committee_members: &[Pubkey],
+    amount: u64,
+    duration_days: u32,
+) -> Instruction {
+    let lending_pool = pda::lending_pool(authority, mint).0;
+    let loan = pda::loan(borrower, &lending_pool).0;
+    let remaining = attestation_accounts(borrower, attesters)
+        .into_iter()
+        .chain(attester_registries(backing_attesters))
+        .chain(
+            committee_members
+                .iter()
+                .map(|member| AccountMeta::new_readonly(*member, true)),
+        )
+        .collect();
+    build(
+        accounts::RefinanceLoan {
+            platform: pda::platform().0,
+            mint: *mint,
+            loan,
+            refinanced_loan: pda::refinanced_loan(&loan, loan_created_at).0,
+            lending_pool,
+            loan_committee: (!committee_members.is_empty())
+                .then(|| pda::loan_committee(&lending_pool).0),
+            user_profile: pda::user_profile(borrower).0,
+            pool_token_account: pda::pool_token_account(&lending_pool).0,
+            borrower_token_account: pda::token_account(borrower, mint, token_program),
+            treasury_token_account: pda::treasury_token_account(mint, token_program),
+            loan_officer: officer.map(|officer| pda::loan_officer(&lending_pool, officer).0),
+            officer_token_account: officer
+                .map(|officer| pda::token_account(officer, mint, token_program)),
+            borrower: *borrower,
+            authority: *authority,
+            token_program: *token_program,
+            system_program: system_program::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::RefinanceLoan {
+            amount,
+            duration_days,
+        },
+        remaining,
+    )
+}
+
+pub fn grant_forbearance(
+    authority: &Pubkey,
+    lending_pool: &Pubkey,
+    borrower: &Pubkey,
+    days: u16,
+) -> Instruction {
+    build(
+        accounts::GrantForbearance {
+            loan: pda::loan(borrower, lending_pool).0,
+            lending_pool: *lending_pool,
+            authority: *authority,
+        },
+        instruction::GrantForbearance { days },
+        vec![],
+    )
+}
+
+pub fn liquidate_loan(
+    liquidator: &Pubkey,
+    lending_pool: &Pubkey,
+    borrower: &Pubkey,
+    officer: Option<&Pubkey>,
+    backing_attesters: &[Pubkey],
+) -> Instruction {
+    build(
+        accounts::LiquidateLoan {
+            liquidator: *liquidator,
+            platform: pda::platform().0,
+            loan: pda::loan(borrower, lending_pool).0,
+            lending_pool: *lending_pool,
+            user_profile: pda::user_profile(borrower).0,
+            loan_officer: officer.map(|officer| pda::loan_officer(lending_pool, officer).0),
+        },
+        instruction::LiquidateLoan {},
+        attester_registries(backing_attesters),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Quotes, read-only; run them with quote::simulate_quote
+// ---------------------------------------------------------------------------
+
+// `attesters` are those whose attestations of the borrower back the request, as for
+// request_loan
+pub fn quote_loan(
+    borrower: &Pubkey,
+    lending_pool: &Pubkey,
+    attesters: &[Pubkey],
+    amount: u64,
+    duration_days: u32,
+    purpose: String,
+    collateral_type: u8,
+) -> Instruction {
+    build(
+        accounts::QuoteLoan {
+            platform: pda::platform().0,
+            lending_pool: *lending_pool,
+            user_profile: pda::user_profile(borrower).0,
+        },
+        instruction::QuoteLoan {
+            amount,
+            duration_days,
+            purpose,
+            collateral_type,
+        },
+        attestation_accounts(borrower, attesters),
+    )
+}
+
+pub fn quote_payoff(borrower: &Pubkey, lending_pool: &Pubkey) -> Instruction {
+    build(
+        accounts::QuotePayoff {
+            platform: pda::platform().0,
+            lending_pool: *lending_pool,
+            loan: pda::loan(borrower, lending_pool).0,
+        },
+        instruction::QuotePayoff {},
+        vec![],
+    )
+}
+
+pub fn quote_withdrawal(
+    lender: &Pubkey,
+    lending_pool: &Pubkey,
+    shares_to_withdraw: u64,
+) -> Instruction {
+    build(
+        accounts::QuoteLenderPosition {
+            lending_pool: *lending_pool,
+            lender_deposit: pda::lender_deposit(lender, lending_pool).0,
+        },
+        instruction::QuoteWithdrawal { shares_to_withdraw },
+        vec![],
+    )
+}
+
+pub fn quote_pending_interest(lender: &Pubkey, lending_pool: &Pubkey) -> Instruction {
+    build(
+        accounts::QuoteLenderPosition {
+            lending_pool: *lending_pool,
+            lender_deposit: pda::lender_deposit(lender, lending_pool).0,
+        },
+        instruction::QuotePendingInterest {},
+        vec![],
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Account migration
+// ---------------------------------------------------------------------------
+
+// Rewrites an account still in its first layout in the current one; the payer covers the
+// extra rent. A pool is migrated by its authority, and `approved_loans` are all of its
+// Approved loans; pass none for other accounts.
+pub fn migrate_account(payer: &Pubkey, account: &Pubkey, approved_loans: &[Pubkey]) -> Instruction {
+    build(
+        accounts::MigrateAccount {
+            account: *account,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateAccount {},
+        approved_loans
+            .iter()
+            .map(|loan| AccountMeta::new_readonly(*loan, false))
+            .collect(),
+    )
+}
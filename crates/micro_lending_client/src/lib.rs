@@ -0,0 +1,40 @@
+//! Rust client for the micro_lending program: PDA derivation, instruction builders,
+//! account fetching and quotes.
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+pub mod quote;
+
+pub use micro_lending;
+pub use micro_lending::ID as PROGRAM_ID;
+
+use anchor_lang::prelude::Pubkey;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("rpc error: {0}")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    #[error("program error: {0}")]
+    Program(Box<anchor_lang::error::Error>),
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error("account {0} is not owned by the micro_lending program")]
+    InvalidOwner(Pubkey),
+    #[error("simulation failed: {0}")]
+    Simulation(String),
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(error: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(Box::new(error))
+    }
+}
+
+impl From<anchor_lang::error::Error> for ClientError {
+    fn from(error: anchor_lang::error::Error) -> Self {
+        ClientError::Program(Box::new(error))
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
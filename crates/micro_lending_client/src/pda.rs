@@ -0,0 +1,121 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use micro_lending::{
+    VerificationType, SEEDS_ATTESTER, SEEDS_PLATFORM, SEEDS_TREASURY, SEEDS_USER,
+    SEEDS_VERIFICATION,
+};
+
+pub fn platform() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEEDS_PLATFORM], &micro_lending::ID)
+}
+
+pub fn treasury() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEEDS_TREASURY], &micro_lending::ID)
+}
+
+pub fn user_profile(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEEDS_USER, user.as_ref()], &micro_lending::ID)
+}
+
+pub fn lending_pool(authority: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"lending_pool", authority.as_ref(), mint.as_ref()],
+        &micro_lending::ID,
+    )
+}
+
+pub fn pool_token_account(lending_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"pool_token_account", lending_pool.as_ref()],
+        &micro_lending::ID,
+    )
+}
+
+pub fn loan(borrower: &Pubkey, lending_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"loan", borrower.as_ref(), lending_pool.as_ref()],
+        &micro_lending::ID,
+    )
+}
+
+// Archived copy of a loan taken when it is refinanced, keyed by the old loan's created_at
+pub fn refinanced_loan(loan: &Pubkey, created_at: i64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"refinanced_loan", loan.as_ref(), &created_at.to_le_bytes()],
+        &micro_lending::ID,
+    )
+}
+
+pub fn lender_deposit(lender: &Pubkey, lending_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"lender_deposit", lender.as_ref(), lending_pool.as_ref()],
+        &micro_lending::ID,
+    )
+}
+
+pub fn social_attestation(user: &Pubkey, attester: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"social_attestation", user.as_ref(), attester.as_ref()],
+        &micro_lending::ID,
+    )
+}
+
+// `index` is the user's transaction_history_count at the time the entry was added
+pub fn transaction_history(user: &Pubkey, index: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"transaction_history", user.as_ref(), &index.to_le_bytes()],
+        &micro_lending::ID,
+    )
+}
+
+pub fn attester(attester: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEEDS_ATTESTER, attester.as_ref()], &micro_lending::ID)
+}
+
+pub fn verification_record(user: &Pubkey, verification_type: VerificationType) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SEEDS_VERIFICATION,
+            user.as_ref(),
+            &[verification_type as u8],
+        ],
+        &micro_lending::ID,
+    )
+}
+
+pub fn loan_officer(lending_pool: &Pubkey, officer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"loan_officer", lending_pool.as_ref(), officer.as_ref()],
+        &micro_lending::ID,
+    )
+}
+
+pub fn loan_committee(lending_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"loan_committee", lending_pool.as_ref()],
+        &micro_lending::ID,
+    )
+}
+
+// Votes are keyed by the loan's created_at so a re-requested loan starts a fresh ballot
+pub fn loan_vote(loan: &Pubkey, created_at: i64, member: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"loan_vote",
+            loan.as_ref(),
+            &created_at.to_le_bytes(),
+            member.as_ref(),
+        ],
+        &micro_lending::ID,
+    )
+}
+
+// Lender, borrower and treasury token accounts are associated token accounts
+pub fn token_account(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
+
+// The treasury token account is owned by the platform PDA
+pub fn treasury_token_account(mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    token_account(&platform().0, mint, token_program)
+}
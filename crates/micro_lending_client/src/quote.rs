@@ -0,0 +1,115 @@
+//! Quotes of the program's pricing and settlement math. The off-chain ones run the
+//! program's own quote functions against fetched account state; `simulate_quote` runs a
+//! quote instruction on a node and decodes its return data.
+
+use crate::{ClientError, Result};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+use anchor_lang::AnchorDeserialize;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use micro_lending::{
+    calculate_loan_quote, calculate_payoff_quote, calculate_withdrawal_quote,
+    get_unclaimed_interest, LenderDeposit, LendingPool, Loan, Platform, UserProfile,
+};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_sdk::message::Message;
+use solana_sdk::transaction::Transaction;
+
+pub use micro_lending::{LoanQuote, PayoffQuote, WithdrawalQuote};
+
+// Terms request_loan would assign, for a borrower with `verified_attestations` currently
+// valid, verified attestations
+#[allow(clippy::too_many_arguments)]
+pub fn quote_loan(
+    platform: &Platform,
+    lending_pool: &LendingPool,
+    user_profile: &UserProfile,
+    amount: u64,
+    duration_days: u32,
+    purpose: &str,
+    collateral_type: u8,
+    verified_attestations: usize,
+    current_time: i64,
+) -> Result<LoanQuote> {
+    Ok(calculate_loan_quote(
+        platform,
+        lending_pool,
+        user_profile,
+        amount,
+        duration_days,
+        purpose,
+        collateral_type,
+        verified_attestations,
+        current_time,
+    )?)
+}
+
+// Amount a single repay_loan call at `current_time` would need to clear the loan:
+// total_due plus platform_fee
+pub fn quote_payoff(
+    platform: &Platform,
+    lending_pool: &LendingPool,
+    loan: &Loan,
+    current_time: i64,
+) -> Result<PayoffQuote> {
+    Ok(calculate_payoff_quote(
+        platform,
+        lending_pool,
+        loan,
+        current_time,
+    )?)
+}
+
+// Interest a lender can claim right now
+pub fn pending_interest(lender_deposit: &LenderDeposit, lending_pool: &LendingPool) -> u64 {
+    get_unclaimed_interest(lender_deposit, lending_pool)
+}
+
+// Tokens withdraw_from_pool would pay out for `shares_to_withdraw`, including unclaimed interest
+pub fn quote_withdrawal(
+    lender_deposit: &LenderDeposit,
+    lending_pool: &LendingPool,
+    shares_to_withdraw: u64,
+) -> WithdrawalQuote {
+    calculate_withdrawal_quote(lender_deposit, lending_pool, shares_to_withdraw)
+}
+
+// Simulates one of the quote instructions and decodes its return data. `payer` only pays
+// the simulated fee; nothing is signed or sent.
+pub fn simulate_quote<T: AnchorDeserialize>(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    instruction: Instruction,
+) -> Result<T> {
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(payer)));
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(rpc.commitment()),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = rpc
+        .simulate_transaction_with_config(&transaction, config)?
+        .value;
+    if let Some(err) = result.err {
+        return Err(ClientError::Simulation(err.to_string()));
+    }
+    let return_data = result
+        .return_data
+        .filter(|return_data| return_data.program_id == micro_lending::ID.to_string())
+        .ok_or_else(|| ClientError::Simulation("no return data".to_string()))?;
+    decode_return_data(&return_data.data.0)
+}
+
+// Decodes base64 return data as reported by a simulation
+pub fn decode_return_data<T: AnchorDeserialize>(encoded: &str) -> Result<T> {
+    let mut data = STANDARD
+        .decode(encoded)
+        .map_err(|err| ClientError::Simulation(err.to_string()))?;
+    // Trailing zero bytes of return data are dropped by the runtime
+    data.resize(MAX_RETURN_DATA, 0);
+    T::deserialize(&mut &data[..]).map_err(|err| ClientError::Simulation(err.to_string()))
+}
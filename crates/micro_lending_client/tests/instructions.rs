@@ -0,0 +1,146 @@
+//! Account lists and signers of the instruction builders whose layout the program checks
+//! beyond the Anchor accounts struct.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::sysvar;
+use micro_lending::{loan_consent_message, CollateralType, LoanConsent};
+use micro_lending_client::{instructions, pda};
+
+fn meta<'a>(metas: &'a [AccountMeta], pubkey: &Pubkey) -> &'a AccountMeta {
+    metas
+        .iter()
+        .find(|meta| meta.pubkey == *pubkey)
+        .expect("account is passed")
+}
+
+#[test]
+fn borrower_signs_and_pays_a_direct_request() {
+    let borrower = Pubkey::new_unique();
+    let lending_pool = Pubkey::new_unique();
+    let attester = Pubkey::new_unique();
+    let instruction = instructions::request_loan(
+        &borrower,
+        &lending_pool,
+        None,
+        &[attester],
+        50_000,
+        30,
+        "stock".to_string(),
+        CollateralType::Social as u8,
+    );
+
+    // Passed as both the borrower and the payer
+    let borrower_metas: Vec<_> = instruction
+        .accounts
+        .iter()
+        .filter(|meta| meta.pubkey == borrower)
+        .collect();
+    assert_eq!(borrower_metas.len(), 2);
+    assert!(borrower_metas.iter().all(|meta| meta.is_signer));
+    assert!(borrower_metas.iter().any(|meta| meta.is_writable));
+    assert!(
+        instruction
+            .accounts
+            .iter()
+            .all(|meta| meta.pubkey != sysvar::instructions::ID),
+        "no consent to verify"
+    );
+    // Attestations follow the accounts struct, then their attesters' registries
+    let remaining: Vec<_> = instruction.accounts[instruction.accounts.len() - 2..]
+        .iter()
+        .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+        .collect();
+    assert_eq!(
+        remaining,
+        vec![
+            (
+                pda::social_attestation(&borrower, &attester).0,
+                false,
+                false
+            ),
+            (pda::attester(&attester).0, false, false),
+        ]
+    );
+}
+
+#[test]
+fn consent_request_is_verified_by_the_preceding_ed25519_instruction() {
+    let borrower = Pubkey::new_unique();
+    let officer = Pubkey::new_unique();
+    let lending_pool = Pubkey::new_unique();
+    let consent = LoanConsent {
+        officer,
+        lending_pool,
+        amount: 50_000,
+        duration_days: 30,
+        purpose: "stock".to_string(),
+        collateral_type: CollateralType::None as u8,
+        expires_at: 1_000,
+    };
+    let [verify, request] =
+        instructions::request_loan_with_consent(&borrower, &[], consent.clone(), &[7; 64]);
+
+    // One signature whose key, signature and message all sit in the instruction itself,
+    // which is the only layout request_loan accepts
+    let data = &verify.data;
+    let offset = |field: usize| u16::from_le_bytes([data[2 + 2 * field], data[3 + 2 * field]]);
+    assert_eq!(data[0], 1);
+    assert!([1, 3, 6].iter().all(|&field| offset(field) == u16::MAX));
+    let field = |start: u16, len: usize| &data[start as usize..start as usize + len];
+    assert_eq!(field(offset(2), 32), borrower.as_ref());
+    assert_eq!(field(offset(0), 64), &[7; 64]);
+    assert_eq!(
+        field(offset(4), offset(5) as usize),
+        &loan_consent_message(&consent)[..]
+    );
+
+    // The officer signs and pays; the borrower only consented
+    let metas = &request.accounts;
+    assert!(!meta(metas, &borrower).is_signer);
+    assert!(meta(metas, &officer).is_signer && meta(metas, &officer).is_writable);
+    assert!(meta(metas, &pda::loan_officer(&lending_pool, &officer).0).is_writable);
+    assert!(!meta(metas, &sysvar::instructions::ID).is_signer);
+}
+
+#[test]
+fn refinance_orders_attestations_registries_then_committee_signers() {
+    let authority = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let borrower = Pubkey::new_unique();
+    let backing_attester = Pubkey::new_unique();
+    let attester = Pubkey::new_unique();
+    let member = Pubkey::new_unique();
+    let instruction = instructions::refinance_loan(
+        &authority,
+        &mint,
+        &anchor_spl::token::ID,
+        &borrower,
+        None,
+        100,
+        &[backing_attester],
+        &[attester],
+        &[member],
+        50_000,
+        30,
+    );
+
+    let remaining: Vec<_> = instruction.accounts[instruction.accounts.len() - 4..]
+        .iter()
+        .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+        .collect();
+    assert_eq!(
+        remaining,
+        vec![
+            (
+                pda::social_attestation(&borrower, &attester).0,
+                false,
+                false
+            ),
+            (pda::attester(&attester).0, false, false),
+            (pda::attester(&backing_attester).0, false, true),
+            (member, true, false),
+        ]
+    );
+    let lending_pool = pda::lending_pool(&authority, &mint).0;
+    meta(&instruction.accounts, &pda::loan_committee(&lending_pool).0);
+}
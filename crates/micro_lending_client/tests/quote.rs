@@ -0,0 +1,292 @@
+//! The client's quotes against the settlement and origination rules they mirror.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AnchorSerialize;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use micro_lending::error::MicroLendingError;
+use micro_lending::{
+    settle_payment, AutoApprovalRules, BorrowerPolicy, CollateralType, LenderDeposit,
+    LendingLadder, LendingPool, Loan, LoanStatus, Platform, RepaymentIncentives, UserProfile,
+    ALL_COLLATERAL_TYPES,
+};
+use micro_lending_client::quote::{
+    decode_return_data, quote_loan, quote_payoff, quote_withdrawal, PayoffQuote,
+};
+
+const DAY: i64 = 86_400;
+
+fn platform(platform_fee: u16) -> Platform {
+    Platform {
+        authority: Pubkey::default(),
+        treasury: Pubkey::default(),
+        treasury_bump: 0,
+        platform_fee,
+        max_loan_amount: 1_000_000_000,
+        min_loan_amount: 1_000,
+        total_loans_issued: 0,
+        total_volume: 0,
+        total_defaults: 0,
+        is_active: true,
+        created_at: 0,
+        score_half_life_days: 0,
+        inactivity_threshold_days: 0,
+        verifiers: Vec::new(),
+        lending_ladder: LendingLadder {
+            first_loan_cap: 0,
+            step_up_bps: 0,
+            step_down_bps: 0,
+        },
+    }
+}
+
+fn pool(early_payoff_rebate_bps: u16) -> LendingPool {
+    LendingPool {
+        authority: Pubkey::default(),
+        mint: Pubkey::default(),
+        token_account: Pubkey::default(),
+        name: "client".to_string(),
+        base_interest_rate: 1000,
+        max_loan_duration: 365,
+        total_deposited: 10_000_000,
+        total_borrowed: 0,
+        total_shares: 10_000_000,
+        available_liquidity: 10_000_000,
+        active_loans: 0,
+        total_interest_earned: 0,
+        total_interest_distributed: 0,
+        interest_per_share: 0,
+        is_active: true,
+        created_at: 0,
+        borrower_policy: BorrowerPolicy {
+            min_credit_score: 0,
+            required_verifications: 0,
+            min_attestations: 0,
+            allowed_collateral_types: ALL_COLLATERAL_TYPES,
+            max_loan_per_borrower: 0,
+            allowed_purposes: Vec::new(),
+        },
+        total_reserved: 0,
+        auto_approval: AutoApprovalRules {
+            enabled: false,
+            max_utilization_bps: 0,
+            score_bands: Vec::new(),
+        },
+        request_ttl: 0,
+        approval_ttl: 0,
+        committee: None,
+        disaster_declared_at: 0,
+        disaster_forbearance_until: 0,
+        repayment_incentives: RepaymentIncentives {
+            streak_length: 0,
+            rate_step_down_bps: 0,
+            min_interest_rate: 0,
+            early_payoff_rebate_bps,
+        },
+    }
+}
+
+fn user_profile(credit_score: u16) -> UserProfile {
+    UserProfile {
+        owner: Pubkey::default(),
+        credit_score,
+        total_borrowed: 0,
+        total_repaid: 0,
+        active_loans: 0,
+        successful_loans: 0,
+        defaulted_loans: 0,
+        reputation_score: 0,
+        created_at: 0,
+        last_updated: 0,
+        kyc_verified: false,
+        phone_verified: false,
+        email_verified: false,
+        kyc_expires_at: None,
+        phone_expires_at: None,
+        email_expires_at: None,
+        transaction_history_count: 0,
+        social_attestations_count: 0,
+        last_activity_at: 0,
+        recent_outcomes: Vec::new(),
+        restructured_loans: 0,
+        borrowing_limit: 0,
+        on_time_streak: 0,
+        kyc_verifier: Pubkey::default(),
+        phone_verifier: Pubkey::default(),
+        email_verifier: Pubkey::default(),
+    }
+}
+
+// 100_000 at 10% for 30 days, disbursed at 0
+fn loan() -> Loan {
+    Loan {
+        borrower: Pubkey::default(),
+        lender_pool: Pubkey::default(),
+        amount: 100_000,
+        interest_rate: 1000,
+        duration_days: 30,
+        disbursed_at: 0,
+        due_date: 30 * DAY,
+        amount_repaid: 0,
+        interest_accrued: 0,
+        status: LoanStatus::Disbursed,
+        purpose: "stock".to_string(),
+        collateral_type: CollateralType::None,
+        collateral_value: 0,
+        payment_count: 0,
+        last_payment_date: 0,
+        grace_period_days: 3,
+        late_fee_rate: 100,
+        created_at: 0,
+        liquidated_at: None,
+        backing_attesters: Vec::new(),
+        approved_at: 0,
+        committee_votes: Vec::new(),
+        loan_officer: None,
+        accrual_start: 0,
+        scheduled_repayment: 100_821,
+        restructure_count: 0,
+        restructured_at: None,
+        forbearance_until: 0,
+        forbearance_count: 0,
+        refinanced: false,
+        refinanced_from: None,
+        on_time_streak: 0,
+        rate_step_downs: 0,
+        carried_interest: 0,
+    }
+}
+
+#[test]
+fn paying_the_payoff_quote_clears_the_loan() {
+    let platform = platform(150);
+    let loan = loan();
+    for current_time in [DAY, 20 * DAY, 40 * DAY] {
+        let quote = quote_payoff(&platform, &pool(2000), &loan, current_time).unwrap();
+        let settlement = settle_payment(
+            &quote,
+            platform.platform_fee,
+            quote.total_due + quote.platform_fee,
+        )
+        .unwrap();
+        assert_eq!(settlement.principal_paid, loan.amount);
+        assert_eq!(settlement.platform_fee, quote.platform_fee);
+        assert_eq!(settlement.early_payoff_rebate, quote.early_payoff_rebate);
+        assert_eq!(settlement.late_fee, quote.late_fee);
+    }
+}
+
+#[test]
+fn payoff_quote_rebates_early_and_charges_late() {
+    let platform = platform(0);
+    let loan = loan();
+
+    let early = quote_payoff(&platform, &pool(5000), &loan, 10 * DAY).unwrap();
+    assert_eq!(early.early_payoff_rebate, 136);
+    assert_eq!(early.interest_accrued, 137);
+    assert_eq!(early.late_fee, 0);
+
+    // Within the grace period there is no late fee, and after the due date no rebate
+    let grace = quote_payoff(&platform, &pool(5000), &loan, 33 * DAY).unwrap();
+    assert_eq!((grace.early_payoff_rebate, grace.late_fee), (0, 0));
+
+    let late = quote_payoff(&platform, &pool(5000), &loan, 40 * DAY).unwrap();
+    assert!(late.late_fee > 0);
+    assert_eq!(
+        late.total_due,
+        late.outstanding_principal + late.interest_accrued + late.late_fee
+    );
+}
+
+#[test]
+fn loan_quote_reports_the_first_failing_check() {
+    let platform = platform(0);
+    let pool = pool(0);
+    let quote = |profile: &UserProfile, collateral_type: u8, attestations: usize| {
+        quote_loan(
+            &platform,
+            &pool,
+            profile,
+            50_000,
+            30,
+            "stock",
+            collateral_type,
+            attestations,
+            0,
+        )
+        .unwrap()
+    };
+
+    let accepted = quote(&user_profile(650), CollateralType::None as u8, 0);
+    assert_eq!(accepted.rejection_code, None);
+    assert_eq!(accepted.interest_rate, 1100);
+    assert_eq!(accepted.required_credit_score, 300);
+
+    assert_eq!(
+        quote(&user_profile(650), CollateralType::Social as u8, 0).rejection_code,
+        Some(MicroLendingError::InsufficientAttestations.into())
+    );
+    assert_eq!(
+        quote(&user_profile(650), CollateralType::Social as u8, 1).rejection_code,
+        None
+    );
+    assert_eq!(
+        quote(&user_profile(650), 9, 0).rejection_code,
+        Some(MicroLendingError::InvalidCollateralType.into())
+    );
+
+    let busy = UserProfile {
+        active_loans: 1,
+        ..user_profile(650)
+    };
+    assert_eq!(
+        quote(&busy, CollateralType::None as u8, 0).rejection_code,
+        Some(MicroLendingError::BorrowerHasActiveLoan.into())
+    );
+}
+
+#[test]
+fn withdrawal_quote_flags_missing_liquidity() {
+    let mut pool = pool(0);
+    let deposit = LenderDeposit {
+        lender: Pubkey::default(),
+        pool: Pubkey::default(),
+        amount_deposited: 1_000_000,
+        shares: 1_000_000,
+        interest_debt: 0,
+        interest_earned: 0,
+        interest_claimed: 0,
+        deposited_at: 0,
+        last_claim: 0,
+    };
+
+    let quote = quote_withdrawal(&deposit, &pool, 1_000_000);
+    assert_eq!(quote.principal_amount, 1_000_000);
+    assert_eq!(quote.interest_amount, 0);
+    assert!(quote.sufficient_liquidity);
+
+    pool.total_borrowed = 9_500_000;
+    pool.available_liquidity = 500_000;
+    assert!(!quote_withdrawal(&deposit, &pool, 1_000_000).sufficient_liquidity);
+}
+
+#[test]
+fn return_data_decodes_with_trailing_zeros_dropped() {
+    let quote = PayoffQuote {
+        outstanding_principal: 100_000,
+        interest_accrued: 500,
+        early_payoff_rebate: 0,
+        late_fee: 0,
+        total_due: 100_500,
+        platform_fee: 0,
+        in_forbearance: false,
+    };
+    let mut data = quote.try_to_vec().unwrap();
+    while data.last() == Some(&0) {
+        data.pop();
+    }
+    let decoded: PayoffQuote = decode_return_data(&STANDARD.encode(&data)).unwrap();
+    assert_eq!(decoded, quote);
+
+    assert!(decode_return_data::<PayoffQuote>("not base64!").is_err());
+}
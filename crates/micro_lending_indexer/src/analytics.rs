@@ -0,0 +1,477 @@
+//! Standard microfinance portfolio metrics (PAR30/PAR90, write-offs, yield, repayment
+//! rate) over the indexed loans and payments, grouped by pool, loan officer or
+//! disbursement cohort.
+
+use crate::store::{Store, Value};
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(Clone, Copy, Debug)]
+pub enum GroupBy {
+    Pool,
+    Officer,
+    // Calendar month of disbursement, `YYYY-MM` in UTC
+    Cohort,
+}
+
+// Ratios are basis points of their denominator, 0 when it is empty
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct PortfolioMetrics {
+    pub group: String,
+    pub loans_disbursed: u64,
+    pub amount_disbursed: u64,
+    pub active_loans: u64,
+    pub gross_portfolio: u64,
+    pub par30_amount: u64,
+    pub par30_bps: u64,
+    pub par90_amount: u64,
+    pub par90_bps: u64,
+    pub loans_repaid: u64,
+    pub loans_repaid_on_time: u64,
+    pub loans_defaulted: u64,
+    pub written_off: u64,
+    pub write_off_bps: u64,
+    pub principal_collected: u64,
+    pub interest_collected: u64,
+    pub late_fees_collected: u64,
+    pub yield_bps: u64,
+    pub repayment_rate_bps: u64,
+    pub on_time_rate_bps: u64,
+}
+
+struct LoanRow {
+    loan: String,
+    requested_at: i64,
+    pool: String,
+    loan_officer: Option<String>,
+    status: String,
+    outcome: Option<String>,
+    amount: u64,
+    disbursed_at: i64,
+    due_date: Option<i64>,
+    forbearance_until: i64,
+    outstanding: u64,
+    principal_paid: u64,
+    interest_paid: u64,
+    late_fees: u64,
+}
+
+fn int(value: &Value) -> Result<i64> {
+    match value {
+        Value::Int(value) => Ok(*value),
+        other => bail!("expected an integer column, got {other:?}"),
+    }
+}
+
+fn opt_int(value: &Value) -> Result<Option<i64>> {
+    match value {
+        Value::Null => Ok(None),
+        value => int(value).map(Some),
+    }
+}
+
+fn text(value: &Value) -> Result<String> {
+    match value {
+        Value::Text(value) => Ok(value.clone()),
+        other => bail!("expected a text column, got {other:?}"),
+    }
+}
+
+fn opt_text(value: &Value) -> Result<Option<String>> {
+    match value {
+        Value::Null => Ok(None),
+        value => text(value).map(Some),
+    }
+}
+
+fn ratio_bps(numerator: u64, denominator: u64) -> u64 {
+    if denominator == 0 {
+        0
+    } else {
+        (numerator as u128 * 10000 / denominator as u128) as u64
+    }
+}
+
+// `YYYY-MM` of a unix timestamp, from the days-to-civil conversion of the proleptic
+// Gregorian calendar
+fn month_of(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(SECONDS_PER_DAY) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}")
+}
+
+fn load_loans(store: &mut Store) -> Result<Vec<LoanRow>> {
+    let mut loans = store
+        .query(
+            "SELECT loan, requested_at, pool, loan_officer, status, outcome, amount,
+                 disbursed_at, due_date, COALESCE(forbearance_until, 0), outstanding
+             FROM loans WHERE disbursed_at IS NOT NULL",
+            &[],
+        )?
+        .iter()
+        .map(|row| {
+            Ok(LoanRow {
+                loan: text(&row[0])?,
+                requested_at: int(&row[1])?,
+                pool: text(&row[2])?,
+                loan_officer: opt_text(&row[3])?,
+                status: text(&row[4])?,
+                outcome: opt_text(&row[5])?,
+                amount: int(&row[6])? as u64,
+                disbursed_at: int(&row[7])?,
+                due_date: opt_int(&row[8])?,
+                forbearance_until: int(&row[9])?,
+                outstanding: int(&row[10])? as u64,
+                principal_paid: 0,
+                interest_paid: 0,
+                late_fees: 0,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // Sorted here rather than in SQL, where Postgres would order text by its collation
+    loans.sort_by(|a, b| (&a.loan, a.requested_at).cmp(&(&b.loan, b.requested_at)));
+
+    // Like the indexer's updates, a payment belongs to the newest request of its loan
+    // address made before it
+    for row in store.query(
+        "SELECT loan, timestamp, principal_paid, interest_paid, late_fee FROM payments",
+        &[],
+    )? {
+        let loan = text(&row[0])?;
+        let timestamp = int(&row[1])?;
+        let end = loans.partition_point(|candidate| {
+            (candidate.loan.as_str(), candidate.requested_at) <= (loan.as_str(), timestamp)
+        });
+        let Some(target) = end.checked_sub(1).map(|index| &mut loans[index]) else {
+            continue;
+        };
+        if target.loan != loan {
+            continue;
+        }
+        target.principal_paid += int(&row[2])? as u64;
+        target.interest_paid += int(&row[3])? as u64;
+        target.late_fees += int(&row[4])? as u64;
+    }
+    Ok(loans)
+}
+
+// Disasters declared on each pool as (declared_at, forbearance_until), oldest first
+fn load_disasters(store: &mut Store) -> Result<BTreeMap<String, Vec<(i64, i64)>>> {
+    let mut disasters: BTreeMap<String, Vec<(i64, i64)>> = BTreeMap::new();
+    for row in store.query(
+        "SELECT pool, timestamp, forbearance_until FROM disasters",
+        &[],
+    )? {
+        disasters
+            .entry(text(&row[0])?)
+            .or_default()
+            .push((int(&row[1])?, int(&row[2])?));
+    }
+    for declared in disasters.values_mut() {
+        declared.sort_unstable();
+    }
+    Ok(disasters)
+}
+
+// Due date as of `as_of`, including the pool's latest disaster declared by then. The
+// program only moves a loan's due date when a transaction next touches it, so the
+// extension is worked out here the way `sync_disaster_forbearance` would apply it.
+fn adjusted_due_date(loan: &LoanRow, declared: &[(i64, i64)], as_of: i64) -> Option<i64> {
+    let due_date = loan.due_date?;
+    let latest = declared.partition_point(|&(declared_at, _)| declared_at <= as_of);
+    let Some(&(declared_at, forbearance_until)) = latest.checked_sub(1).map(|i| &declared[i])
+    else {
+        return Some(due_date);
+    };
+    if loan.disbursed_at >= declared_at || loan.forbearance_until >= forbearance_until {
+        return Some(due_date);
+    }
+    let start = loan.forbearance_until.max(declared_at);
+    Some(due_date + forbearance_until - start)
+}
+
+fn group_key(loan: &LoanRow, group_by: GroupBy) -> String {
+    match group_by {
+        GroupBy::Pool => loan.pool.clone(),
+        GroupBy::Officer => loan
+            .loan_officer
+            .clone()
+            .unwrap_or_else(|| "none".to_string()),
+        GroupBy::Cohort => month_of(loan.disbursed_at),
+    }
+}
+
+// Refinanced loans are closed into their follow-on loan and count as neither repaid
+// nor defaulted. `due_date` is the loan's due date after any pending disaster forbearance.
+fn add_loan(metrics: &mut PortfolioMetrics, loan: &LoanRow, due_date: Option<i64>, as_of: i64) {
+    metrics.loans_disbursed += 1;
+    metrics.amount_disbursed += loan.amount;
+    metrics.principal_collected += loan.principal_paid;
+    metrics.interest_collected += loan.interest_paid;
+    metrics.late_fees_collected += loan.late_fees;
+
+    match loan.status.as_str() {
+        "disbursed" | "active" => {
+            metrics.active_loans += 1;
+            metrics.gross_portfolio += loan.outstanding;
+            let days_overdue = due_date
+                .map(|due_date| (as_of - due_date).max(0) / SECONDS_PER_DAY)
+                .unwrap_or_default();
+            if days_overdue > 30 {
+                metrics.par30_amount += loan.outstanding;
+            }
+            if days_overdue > 90 {
+                metrics.par90_amount += loan.outstanding;
+            }
+        }
+        "repaid" => {
+            metrics.loans_repaid += 1;
+            if loan.outcome.as_deref() == Some("repaid_on_time") {
+                metrics.loans_repaid_on_time += 1;
+            }
+        }
+        "liquidated" => {
+            metrics.loans_defaulted += 1;
+            metrics.written_off += loan.outstanding;
+        }
+        _ => {}
+    }
+}
+
+fn finish(metrics: &mut PortfolioMetrics) {
+    metrics.par30_bps = ratio_bps(metrics.par30_amount, metrics.gross_portfolio);
+    metrics.par90_bps = ratio_bps(metrics.par90_amount, metrics.gross_portfolio);
+    metrics.write_off_bps = ratio_bps(metrics.written_off, metrics.amount_disbursed);
+    // Income over principal disbursed, not annualized
+    metrics.yield_bps = ratio_bps(
+        metrics.interest_collected + metrics.late_fees_collected,
+        metrics.amount_disbursed,
+    );
+    metrics.repayment_rate_bps = ratio_bps(
+        metrics.loans_repaid,
+        metrics.loans_repaid + metrics.loans_defaulted,
+    );
+    metrics.on_time_rate_bps = ratio_bps(metrics.loans_repaid_on_time, metrics.loans_repaid);
+}
+
+// Metrics of every disbursed loan as of `as_of` (unix seconds), one entry per group
+pub fn portfolio_report(
+    store: &mut Store,
+    group_by: GroupBy,
+    as_of: i64,
+) -> Result<Vec<PortfolioMetrics>> {
+    let disasters = load_disasters(store)?;
+    let mut groups: BTreeMap<String, PortfolioMetrics> = BTreeMap::new();
+    for loan in load_loans(store)? {
+        let declared = disasters.get(&loan.pool).map_or(&[][..], Vec::as_slice);
+        let due_date = adjusted_due_date(&loan, declared, as_of);
+        let group = group_key(&loan, group_by);
+        let metrics = groups
+            .entry(group.clone())
+            .or_insert_with(|| PortfolioMetrics {
+                group,
+                ..Default::default()
+            });
+        add_loan(metrics, &loan, due_date, as_of);
+    }
+    Ok(groups
+        .into_values()
+        .map(|mut metrics| {
+            finish(&mut metrics);
+            metrics
+        })
+        .collect())
+}
+
+pub fn write_json(writer: impl Write, report: &[PortfolioMetrics]) -> Result<()> {
+    serde_json::to_writer_pretty(writer, report)?;
+    Ok(())
+}
+
+pub fn write_csv(writer: impl Write, report: &[PortfolioMetrics]) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for metrics in report {
+        writer.serialize(metrics)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = SECONDS_PER_DAY;
+
+    fn open_loan(due_date: i64, outstanding: u64) -> LoanRow {
+        LoanRow {
+            loan: "loan".to_string(),
+            requested_at: 0,
+            pool: "pool".to_string(),
+            loan_officer: None,
+            status: "active".to_string(),
+            outcome: None,
+            amount: outstanding,
+            disbursed_at: 0,
+            due_date: Some(due_date),
+            forbearance_until: 0,
+            outstanding,
+            principal_paid: 0,
+            interest_paid: 0,
+            late_fees: 0,
+        }
+    }
+
+    fn metrics_as_of(loan: &LoanRow, declared: &[(i64, i64)], as_of: i64) -> PortfolioMetrics {
+        let mut metrics = PortfolioMetrics::default();
+        add_loan(
+            &mut metrics,
+            loan,
+            adjusted_due_date(loan, declared, as_of),
+            as_of,
+        );
+        finish(&mut metrics);
+        metrics
+    }
+
+    #[test]
+    fn month_of_follows_the_gregorian_calendar() {
+        assert_eq!(month_of(0), "1970-01");
+        assert_eq!(month_of(-1), "1969-12");
+        assert_eq!(month_of(951_782_400), "2000-02");
+        assert_eq!(month_of(1_709_251_199), "2024-02");
+        assert_eq!(month_of(1_709_251_200), "2024-03");
+    }
+
+    #[test]
+    fn par_counts_whole_days_past_due() {
+        let due_date = 100 * DAY;
+        let loan = open_loan(due_date, 1_000);
+
+        let metrics = metrics_as_of(&loan, &[], due_date + 30 * DAY);
+        assert_eq!((metrics.par30_amount, metrics.par90_amount), (0, 0));
+
+        let metrics = metrics_as_of(&loan, &[], due_date + 31 * DAY - 1);
+        assert_eq!(metrics.par30_amount, 0);
+
+        let metrics = metrics_as_of(&loan, &[], due_date + 31 * DAY);
+        assert_eq!((metrics.par30_amount, metrics.par90_amount), (1_000, 0));
+        assert_eq!(metrics.par30_bps, 10_000);
+
+        let metrics = metrics_as_of(&loan, &[], due_date + 91 * DAY);
+        assert_eq!((metrics.par30_amount, metrics.par90_amount), (1_000, 1_000));
+    }
+
+    #[test]
+    fn par_skips_loans_under_disaster_forbearance() {
+        let due_date = 100 * DAY;
+        let loan = open_loan(due_date, 1_000);
+        let declared = [(90 * DAY, 150 * DAY)];
+
+        let as_of = due_date + 45 * DAY;
+        assert_eq!(
+            adjusted_due_date(&loan, &declared, as_of),
+            Some(due_date + 60 * DAY)
+        );
+        assert_eq!(metrics_as_of(&loan, &declared, as_of).par30_amount, 0);
+
+        // Not yet declared as of the report date
+        assert_eq!(
+            adjusted_due_date(&loan, &declared, 80 * DAY),
+            Some(due_date)
+        );
+
+        // Disbursed after the declaration
+        let late = LoanRow {
+            disbursed_at: 95 * DAY,
+            ..open_loan(due_date, 1_000)
+        };
+        assert_eq!(adjusted_due_date(&late, &declared, as_of), Some(due_date));
+
+        // Already extended by DisasterForbearanceApplied
+        let applied = LoanRow {
+            forbearance_until: 150 * DAY,
+            ..open_loan(due_date + 60 * DAY, 1_000)
+        };
+        assert_eq!(
+            adjusted_due_date(&applied, &declared, as_of),
+            Some(due_date + 60 * DAY)
+        );
+
+        // Only the part past an earlier individual forbearance is added
+        let forborne = LoanRow {
+            forbearance_until: 120 * DAY,
+            ..open_loan(due_date, 1_000)
+        };
+        assert_eq!(
+            adjusted_due_date(&forborne, &declared, as_of),
+            Some(due_date + 30 * DAY)
+        );
+    }
+
+    fn insert_loan(store: &mut Store, requested_at: i64) {
+        store
+            .execute(
+                "INSERT INTO loans (loan, requested_at, borrower, pool, amount, interest_rate,
+                     duration_days, collateral_type, status, disbursed_at, due_date,
+                     outstanding, updated_slot)
+                 VALUES ('loan', $1, 'borrower', 'pool', 1000, 1000, 30, 'none', 'active',
+                     $1, $2, 1000, 0)",
+                &[requested_at.into(), (requested_at + 30 * DAY).into()],
+            )
+            .unwrap();
+    }
+
+    fn insert_payment(store: &mut Store, index: u32, loan: &str, timestamp: i64, principal: u64) {
+        store
+            .execute(
+                "INSERT INTO payments (signature, event_index, slot, loan, borrower, pool,
+                     payment_amount, platform_fee, interest_paid, late_fee, principal_paid,
+                     officer_commission, outstanding_after, timestamp)
+                 VALUES ('tx', $1, 0, $2, 'borrower', 'pool', $3, 0, 1, 2, $3, 0, 0, $4)",
+                &[
+                    index.into(),
+                    loan.into(),
+                    principal.into(),
+                    timestamp.into(),
+                ],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn payments_match_the_newest_request_before_them() {
+        let mut store = Store::open("sqlite://:memory:").unwrap();
+        insert_loan(&mut store, 1_000);
+        insert_loan(&mut store, 5_000);
+        insert_payment(&mut store, 0, "loan", 2_000, 10);
+        insert_payment(&mut store, 1, "loan", 5_000, 20);
+        insert_payment(&mut store, 2, "loan", 6_000, 40);
+        // Before any request of the address, and for another address
+        insert_payment(&mut store, 3, "loan", 500, 80);
+        insert_payment(&mut store, 4, "other", 2_000, 160);
+
+        let loans = load_loans(&mut store).unwrap();
+        let paid: Vec<_> = loans
+            .iter()
+            .map(|loan| (loan.requested_at, loan.principal_paid, loan.interest_paid))
+            .collect();
+        assert_eq!(paid, vec![(1_000, 10, 1), (5_000, 60, 2)]);
+        assert_eq!(loans[1].late_fees, 4);
+    }
+}
@@ -0,0 +1,440 @@
+//! Mapping of decoded events onto the normalized tables.
+
+use crate::events::ProgramEvent;
+use crate::logs::IndexedTransaction;
+use crate::store::{Store, Value};
+use anyhow::Result;
+use micro_lending_client::micro_lending::{
+    AttestationAction, AttestationType, CollateralType, LoanClosureReason, LoanOutcome,
+};
+
+fn collateral_name(collateral: CollateralType) -> &'static str {
+    match collateral {
+        CollateralType::None => "none",
+        CollateralType::Social => "social",
+        CollateralType::Asset => "asset",
+        CollateralType::Income => "income",
+        CollateralType::Group => "group",
+    }
+}
+
+fn closure_status(reason: LoanClosureReason) -> &'static str {
+    match reason {
+        LoanClosureReason::Rejected => "rejected",
+        LoanClosureReason::Cancelled => "cancelled",
+        LoanClosureReason::Expired => "expired",
+    }
+}
+
+fn outcome_name(outcome: LoanOutcome) -> &'static str {
+    match outcome {
+        LoanOutcome::RepaidOnTime => "repaid_on_time",
+        LoanOutcome::RepaidLate => "repaid_late",
+        LoanOutcome::Defaulted => "defaulted",
+    }
+}
+
+fn action_name(action: AttestationAction) -> &'static str {
+    match action {
+        AttestationAction::Added => "added",
+        AttestationAction::Updated => "updated",
+        AttestationAction::Renewed => "renewed",
+        AttestationAction::Revoked => "revoked",
+        AttestationAction::Expired => "expired",
+    }
+}
+
+fn attestation_type_name(attestation_type: AttestationType) -> &'static str {
+    match attestation_type {
+        AttestationType::Community => "community",
+        AttestationType::Employer => "employer",
+        AttestationType::Family => "family",
+        AttestationType::Business => "business",
+        AttestationType::Education => "education",
+        AttestationType::Reference => "reference",
+    }
+}
+
+// A loan address is reused once its account is closed or refinanced, so a row is keyed
+// by (loan, requested_at) and later events land on the newest request made before them
+fn current_loan(loan: usize, timestamp: usize) -> String {
+    format!(
+        "loan = ${loan} AND requested_at = (
+            SELECT MAX(requested_at) FROM loans WHERE loan = ${loan} AND requested_at <= ${timestamp})"
+    )
+}
+
+fn update_loan(
+    store: &mut Store,
+    loan: String,
+    timestamp: i64,
+    slot: u64,
+    assignments: &str,
+    mut params: Vec<Value>,
+) -> Result<()> {
+    let offset = params.len();
+    let sql = format!(
+        "UPDATE loans SET {assignments}, updated_slot = ${} WHERE {}",
+        offset + 3,
+        current_loan(offset + 1, offset + 2)
+    );
+    params.extend([loan.into(), timestamp.into(), slot.into()]);
+    store.execute(&sql, &params)?;
+    Ok(())
+}
+
+// Writes every event of one transaction and marks it processed, atomically; a transaction
+// that was already processed is skipped, which makes replays idempotent
+pub fn apply_transaction(store: &mut Store, tx: &IndexedTransaction) -> Result<bool> {
+    if store.is_processed(&tx.signature)? {
+        return Ok(false);
+    }
+    store.batch("BEGIN")?;
+    let result = apply_events(store, tx);
+    match result {
+        Ok(()) => {
+            store.batch("COMMIT")?;
+            Ok(true)
+        }
+        Err(err) => {
+            store.batch("ROLLBACK")?;
+            Err(err)
+        }
+    }
+}
+
+fn apply_events(store: &mut Store, tx: &IndexedTransaction) -> Result<()> {
+    for (index, event) in tx.events.iter().enumerate() {
+        apply_event(store, tx, index as u32, event)?;
+    }
+    store.execute(
+        "INSERT INTO processed_transactions (signature, slot, block_time) VALUES ($1, $2, $3)",
+        &[
+            tx.signature.as_str().into(),
+            tx.slot.into(),
+            tx.block_time.into(),
+        ],
+    )?;
+    store.set_last_slot(tx.slot)
+}
+
+fn apply_event(
+    store: &mut Store,
+    tx: &IndexedTransaction,
+    index: u32,
+    event: &ProgramEvent,
+) -> Result<()> {
+    let signature = || -> Value { tx.signature.as_str().into() };
+    let slot = tx.slot;
+    match event {
+        ProgramEvent::LoanRequested(e) => {
+            store.execute(
+                "INSERT INTO loans (loan, requested_at, borrower, pool, amount, interest_rate,
+                     duration_days, collateral_type, loan_officer, status, outstanding, updated_slot)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, 'requested', 0, $10)
+                 ON CONFLICT (loan, requested_at) DO NOTHING",
+                &[
+                    e.loan.to_string().into(),
+                    e.timestamp.into(),
+                    e.borrower.to_string().into(),
+                    e.pool.to_string().into(),
+                    e.amount.into(),
+                    e.interest_rate.into(),
+                    e.duration_days.into(),
+                    collateral_name(e.collateral_type).into(),
+                    e.loan_officer.map(|officer| officer.to_string()).into(),
+                    slot.into(),
+                ],
+            )?;
+        }
+        ProgramEvent::LoanApproved(e) => update_loan(
+            store,
+            e.loan.to_string(),
+            e.timestamp,
+            slot,
+            "status = 'approved', approved_at = $1",
+            vec![e.timestamp.into()],
+        )?,
+        ProgramEvent::LoanClosedBeforeDisbursement(e) => update_loan(
+            store,
+            e.loan.to_string(),
+            e.timestamp,
+            slot,
+            "status = $1, closed_at = $2, rejection_code = $3",
+            vec![
+                closure_status(e.reason).into(),
+                e.timestamp.into(),
+                e.rejection_code.into(),
+            ],
+        )?,
+        ProgramEvent::LoanDisbursed(e) => update_loan(
+            store,
+            e.loan.to_string(),
+            e.timestamp,
+            slot,
+            "status = 'disbursed', disbursed_at = $1, due_date = $2, interest_rate = $3, outstanding = $4",
+            vec![
+                e.timestamp.into(),
+                e.due_date.into(),
+                e.interest_rate.into(),
+                e.amount.into(),
+            ],
+        )?,
+        ProgramEvent::PaymentMade(e) => {
+            store.execute(
+                "INSERT INTO payments (signature, event_index, slot, loan, borrower, pool,
+                     payment_amount, platform_fee, interest_paid, late_fee, principal_paid,
+                     officer_commission, outstanding_after, timestamp)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                 ON CONFLICT (signature, event_index) DO NOTHING",
+                &[
+                    signature(),
+                    index.into(),
+                    slot.into(),
+                    e.loan.to_string().into(),
+                    e.borrower.to_string().into(),
+                    e.pool.to_string().into(),
+                    e.payment_amount.into(),
+                    e.platform_fee.into(),
+                    e.interest_paid.into(),
+                    e.late_fee.into(),
+                    e.principal_paid.into(),
+                    e.officer_commission.into(),
+                    e.outstanding_after.into(),
+                    e.timestamp.into(),
+                ],
+            )?;
+            update_loan(
+                store,
+                e.loan.to_string(),
+                e.timestamp,
+                slot,
+                // LoanRepaid is emitted before the payment that closes the loan
+                "status = CASE WHEN status = 'disbursed' THEN 'active' ELSE status END,
+                     outstanding = $1",
+                vec![e.outstanding_after.into()],
+            )?;
+        }
+        ProgramEvent::LoanRepaid(e) => update_loan(
+            store,
+            e.loan.to_string(),
+            e.timestamp,
+            slot,
+            "status = 'repaid', outcome = $1, outstanding = 0, closed_at = $2",
+            vec![outcome_name(e.outcome).into(), e.timestamp.into()],
+        )?,
+        ProgramEvent::LoanLiquidated(e) => {
+            store.execute(
+                "INSERT INTO defaults (signature, event_index, slot, loan, borrower, pool,
+                     liquidator, outstanding_amount, timestamp)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                 ON CONFLICT (signature, event_index) DO NOTHING",
+                &[
+                    signature(),
+                    index.into(),
+                    slot.into(),
+                    e.loan.to_string().into(),
+                    e.borrower.to_string().into(),
+                    e.pool.to_string().into(),
+                    e.liquidator.to_string().into(),
+                    e.outstanding_amount.into(),
+                    e.timestamp.into(),
+                ],
+            )?;
+            update_loan(
+                store,
+                e.loan.to_string(),
+                e.timestamp,
+                slot,
+                "status = 'liquidated', outcome = 'defaulted', outstanding = $1, closed_at = $2",
+                vec![e.outstanding_amount.into(), e.timestamp.into()],
+            )?;
+        }
+        ProgramEvent::LoanRestructured(e) => update_loan(
+            store,
+            e.loan.to_string(),
+            e.timestamp,
+            slot,
+            "outstanding = $1, interest_rate = $2, due_date = $3",
+            vec![
+                e.outstanding_after.into(),
+                e.new_interest_rate.into(),
+                e.new_due_date.into(),
+            ],
+        )?,
+        ProgramEvent::ForbearanceGranted(e) => update_loan(
+            store,
+            e.loan.to_string(),
+            e.timestamp,
+            slot,
+            "due_date = $1, forbearance_until = $2",
+            vec![e.new_due_date.into(), e.forbearance_until.into()],
+        )?,
+        ProgramEvent::DisasterDeclared(e) => {
+            store.execute(
+                "INSERT INTO disasters (signature, event_index, slot, pool, forbearance_days,
+                     forbearance_until, timestamp)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7)
+                 ON CONFLICT (signature, event_index) DO NOTHING",
+                &[
+                    signature(),
+                    index.into(),
+                    slot.into(),
+                    e.pool.to_string().into(),
+                    e.forbearance_days.into(),
+                    e.forbearance_until.into(),
+                    e.timestamp.into(),
+                ],
+            )?;
+        }
+        ProgramEvent::DisasterForbearanceApplied(e) => update_loan(
+            store,
+            e.loan.to_string(),
+            e.timestamp,
+            slot,
+            "due_date = $1, forbearance_until = $2",
+            vec![e.new_due_date.into(), e.forbearance_until.into()],
+        )?,
+        ProgramEvent::InterestRateSteppedDown(e) => update_loan(
+            store,
+            e.loan.to_string(),
+            e.timestamp,
+            slot,
+            "interest_rate = $1",
+            vec![e.new_interest_rate.into()],
+        )?,
+        ProgramEvent::LoanRefinanced(e) => {
+            // The follow-on loan takes over the address; the paid-off loan keeps its row
+            store.execute(
+                &format!(
+                    "INSERT INTO loans (loan, requested_at, borrower, pool, amount, interest_rate,
+                         duration_days, collateral_type, loan_officer, status, approved_at,
+                         disbursed_at, due_date, outstanding, refinanced_from, updated_slot)
+                     SELECT loan, $2, borrower, pool, $3, $4, duration_days, collateral_type,
+                         loan_officer, 'disbursed', $2, $2, $5, $3, $6, $7
+                     FROM loans WHERE {}
+                     ON CONFLICT (loan, requested_at) DO NOTHING",
+                    current_loan(1, 2)
+                ),
+                &[
+                    e.loan.to_string().into(),
+                    e.timestamp.into(),
+                    e.new_amount.into(),
+                    e.new_interest_rate.into(),
+                    e.due_date.into(),
+                    e.refinanced_loan.to_string().into(),
+                    slot.into(),
+                ],
+            )?;
+            update_loan(
+                store,
+                e.loan.to_string(),
+                e.timestamp - 1,
+                slot,
+                "status = 'refinanced', outstanding = 0, closed_at = $1",
+                vec![e.timestamp.into()],
+            )?;
+        }
+        ProgramEvent::Deposited(e) => {
+            store.execute(
+                "INSERT INTO deposits (signature, event_index, slot, pool, lender, amount,
+                     shares_minted, timestamp)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                 ON CONFLICT (signature, event_index) DO NOTHING",
+                &[
+                    signature(),
+                    index.into(),
+                    slot.into(),
+                    e.pool.to_string().into(),
+                    e.lender.to_string().into(),
+                    e.amount.into(),
+                    e.shares_minted.into(),
+                    e.timestamp.into(),
+                ],
+            )?;
+        }
+        ProgramEvent::Withdrawn(e) => {
+            store.execute(
+                "INSERT INTO withdrawals (signature, event_index, slot, pool, lender,
+                     shares_burned, principal_amount, interest_amount, total_amount, timestamp)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                 ON CONFLICT (signature, event_index) DO NOTHING",
+                &[
+                    signature(),
+                    index.into(),
+                    slot.into(),
+                    e.pool.to_string().into(),
+                    e.lender.to_string().into(),
+                    e.shares_burned.into(),
+                    e.principal_amount.into(),
+                    e.interest_amount.into(),
+                    e.total_amount.into(),
+                    e.timestamp.into(),
+                ],
+            )?;
+        }
+        ProgramEvent::AttestationChanged(e) => {
+            store.execute(
+                "INSERT INTO attestations (signature, event_index, slot, attestation, user_key,
+                     attester, action, attestation_type, score, verified, is_active, expires_at,
+                     timestamp)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                 ON CONFLICT (signature, event_index) DO NOTHING",
+                &[
+                    signature(),
+                    index.into(),
+                    slot.into(),
+                    e.attestation.to_string().into(),
+                    e.user.to_string().into(),
+                    e.attester.to_string().into(),
+                    action_name(e.action).into(),
+                    attestation_type_name(e.attestation_type).into(),
+                    e.score.into(),
+                    e.verified.into(),
+                    e.is_active.into(),
+                    e.expires_at.into(),
+                    e.timestamp.into(),
+                ],
+            )?;
+        }
+        ProgramEvent::CreditScoreUpdated(e) => {
+            store.execute(
+                "INSERT INTO score_changes (signature, event_index, slot, user_key, old_score,
+                     new_score, successful_loans, defaulted_loans, timestamp)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                 ON CONFLICT (signature, event_index) DO NOTHING",
+                &[
+                    signature(),
+                    index.into(),
+                    slot.into(),
+                    e.user.to_string().into(),
+                    e.old_score.into(),
+                    e.new_score.into(),
+                    e.successful_loans.into(),
+                    e.defaulted_loans.into(),
+                    e.timestamp.into(),
+                ],
+            )?;
+        }
+        // Pool, platform, governance and migration events carry no rows of their own
+        ProgramEvent::PlatformInitialized(_)
+        | ProgramEvent::LendingPoolCreated(_)
+        | ProgramEvent::UserInitialized(_)
+        | ProgramEvent::VerifierChanged(_)
+        | ProgramEvent::LendingLadderUpdated(_)
+        | ProgramEvent::BorrowerPolicyUpdated(_)
+        | ProgramEvent::AutoApprovalRulesUpdated(_)
+        | ProgramEvent::LoanTtlsUpdated(_)
+        | ProgramEvent::RepaymentIncentivesUpdated(_)
+        | ProgramEvent::LoanCommitteeChanged(_)
+        | ProgramEvent::LoanOfficerChanged(_)
+        | ProgramEvent::AttesterChanged(_)
+        | ProgramEvent::InterestClaimed(_)
+        | ProgramEvent::LoanVoteCast(_)
+        | ProgramEvent::EarlyPayoffRebated(_)
+        | ProgramEvent::TransactionHistoryAdded(_)
+        | ProgramEvent::VerificationUpdated(_)
+        | ProgramEvent::AccountMigrated(_) => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! Decoding of the program's Anchor events from `Program data:` log payloads.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use micro_lending_client::micro_lending::*;
+
+pub enum ProgramEvent {
+    PlatformInitialized(PlatformInitialized),
+    LendingPoolCreated(LendingPoolCreated),
+    UserInitialized(UserInitialized),
+    VerifierChanged(VerifierChanged),
+    LendingLadderUpdated(LendingLadderUpdated),
+    BorrowerPolicyUpdated(BorrowerPolicyUpdated),
+    AutoApprovalRulesUpdated(AutoApprovalRulesUpdated),
+    LoanTtlsUpdated(LoanTtlsUpdated),
+    RepaymentIncentivesUpdated(RepaymentIncentivesUpdated),
+    LoanCommitteeChanged(LoanCommitteeChanged),
+    LoanOfficerChanged(LoanOfficerChanged),
+    AttesterChanged(AttesterChanged),
+    Deposited(Deposited),
+    Withdrawn(Withdrawn),
+    InterestClaimed(InterestClaimed),
+    LoanRequested(LoanRequested),
+    LoanApproved(LoanApproved),
+    LoanVoteCast(LoanVoteCast),
+    LoanClosedBeforeDisbursement(LoanClosedBeforeDisbursement),
+    LoanDisbursed(LoanDisbursed),
+    PaymentMade(PaymentMade),
+    LoanRepaid(LoanRepaid),
+    LoanLiquidated(LoanLiquidated),
+    LoanRestructured(LoanRestructured),
+    ForbearanceGranted(ForbearanceGranted),
+    DisasterDeclared(DisasterDeclared),
+    DisasterForbearanceApplied(DisasterForbearanceApplied),
+    LoanRefinanced(LoanRefinanced),
+    InterestRateSteppedDown(InterestRateSteppedDown),
+    EarlyPayoffRebated(EarlyPayoffRebated),
+    AttestationChanged(AttestationChanged),
+    TransactionHistoryAdded(TransactionHistoryAdded),
+    VerificationUpdated(VerificationUpdated),
+    CreditScoreUpdated(CreditScoreUpdated),
+    AccountMigrated(AccountMigrated),
+}
+
+fn decode_as<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    let body = data.strip_prefix(T::DISCRIMINATOR)?;
+    T::deserialize(&mut &body[..]).ok()
+}
+
+macro_rules! decode_any {
+    ($data:expr, $($event:ident),+ $(,)?) => {
+        $(
+            if let Some(event) = decode_as::<$event>($data) {
+                return Some(ProgramEvent::$event(event));
+            }
+        )+
+    };
+}
+
+impl ProgramEvent {
+    // None for payloads that are not events of this program version
+    pub fn decode(data: &[u8]) -> Option<ProgramEvent> {
+        decode_any!(
+            data,
+            PlatformInitialized,
+            LendingPoolCreated,
+            UserInitialized,
+            VerifierChanged,
+            LendingLadderUpdated,
+            BorrowerPolicyUpdated,
+            AutoApprovalRulesUpdated,
+            LoanTtlsUpdated,
+            RepaymentIncentivesUpdated,
+            LoanCommitteeChanged,
+            LoanOfficerChanged,
+            AttesterChanged,
+            Deposited,
+            Withdrawn,
+            InterestClaimed,
+            LoanRequested,
+            LoanApproved,
+            LoanVoteCast,
+            LoanClosedBeforeDisbursement,
+            LoanDisbursed,
+            PaymentMade,
+            LoanRepaid,
+            LoanLiquidated,
+            LoanRestructured,
+            ForbearanceGranted,
+            DisasterDeclared,
+            DisasterForbearanceApplied,
+            LoanRefinanced,
+            InterestRateSteppedDown,
+            EarlyPayoffRebated,
+            AttestationChanged,
+            TransactionHistoryAdded,
+            VerificationUpdated,
+            CreditScoreUpdated,
+            AccountMigrated,
+        );
+        None
+    }
+}
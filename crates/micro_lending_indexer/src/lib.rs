@@ -0,0 +1,81 @@
+//! Indexer for the micro_lending program: decodes the events in the program's transaction
+//! logs and writes them into normalized SQL tables, and reports portfolio metrics over them.
+
+pub mod analytics;
+pub mod apply;
+pub mod events;
+pub mod logs;
+pub mod source;
+pub mod store;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use serde_json::Value;
+use solana_client::rpc_client::RpcClient;
+use std::path::Path;
+use store::Store;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IndexReport {
+    pub transactions: usize,
+    pub indexed: usize,
+    pub events: usize,
+    pub last_slot: Option<u64>,
+}
+
+pub struct Indexer {
+    pub store: Store,
+    pub program_id: Pubkey,
+}
+
+impl Indexer {
+    pub fn new(store: Store, program_id: Pubkey) -> Self {
+        Indexer { store, program_id }
+    }
+
+    // Slot to resume from: the explicit one, else the last indexed slot. Transactions
+    // from the resumed slot that were already indexed are skipped.
+    pub fn start_slot(&mut self, from_slot: Option<u64>) -> Result<u64> {
+        match from_slot {
+            Some(slot) => Ok(slot),
+            None => Ok(self.store.last_slot()?.unwrap_or_default()),
+        }
+    }
+
+    fn index_value(&mut self, value: &Value, report: &mut IndexReport) -> Result<()> {
+        report.transactions += 1;
+        let Some(tx) = logs::parse_transaction(value, &self.program_id)? else {
+            return Ok(());
+        };
+        if apply::apply_transaction(&mut self.store, &tx)? {
+            report.indexed += 1;
+            report.events += tx.events.len();
+        }
+        report.last_slot = report.last_slot.max(Some(tx.slot));
+        Ok(())
+    }
+
+    pub fn index_dump(&mut self, path: &Path, from_slot: u64) -> Result<IndexReport> {
+        let mut transactions = source::read_dump(path)?;
+        transactions.retain(|value| value["slot"].as_u64().unwrap_or_default() >= from_slot);
+        transactions.sort_by_key(|value| value["slot"].as_u64());
+
+        let mut report = IndexReport::default();
+        for value in &transactions {
+            self.index_value(value, &mut report)?;
+        }
+        Ok(report)
+    }
+
+    pub fn index_rpc(&mut self, rpc: &RpcClient, from_slot: u64) -> Result<IndexReport> {
+        let mut report = IndexReport::default();
+        for (signature, _) in source::signatures_since(rpc, &self.program_id, from_slot)? {
+            if self.store.is_processed(&signature)? {
+                continue;
+            }
+            let value = source::fetch_transaction(rpc, &signature)?;
+            self.index_value(&value, &mut report)?;
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,85 @@
+//! Extraction of program events from transaction logs. Transactions arrive as JSON in
+//! the `getTransaction` shape, whether fetched over RPC or read from a dump file.
+
+use crate::events::ProgramEvent;
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::Value;
+
+pub struct IndexedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub events: Vec<ProgramEvent>,
+}
+
+// Accepts a `getTransaction` result, or the flattened form
+// `{"slot", "signature", "blockTime", "err", "logs"}`.
+// Returns None for failed transactions, whose events were rolled back.
+pub fn parse_transaction(value: &Value, program_id: &Pubkey) -> Result<Option<IndexedTransaction>> {
+    let slot = value["slot"]
+        .as_u64()
+        .ok_or_else(|| anyhow!("transaction is missing slot"))?;
+    let signature = value["signature"]
+        .as_str()
+        .or_else(|| value["transaction"]["signatures"][0].as_str())
+        .ok_or_else(|| anyhow!("transaction at slot {slot} is missing its signature"))?
+        .to_string();
+    let meta = &value["meta"];
+    let err = if meta.is_object() {
+        &meta["err"]
+    } else {
+        &value["err"]
+    };
+    if !err.is_null() {
+        return Ok(None);
+    }
+    let logs = meta["logMessages"]
+        .as_array()
+        .or_else(|| value["logs"].as_array())
+        .ok_or_else(|| anyhow!("transaction {signature} has no log messages"))?;
+    let logs: Vec<&str> = logs.iter().filter_map(Value::as_str).collect();
+
+    Ok(Some(IndexedTransaction {
+        signature,
+        slot,
+        block_time: value["blockTime"].as_i64(),
+        events: program_events(&logs, program_id),
+    }))
+}
+
+// Events are `Program data:` lines logged while the program is at the top of the
+// invocation stack; data logged by other programs, including CPIs, is skipped
+pub fn program_events(logs: &[&str], program_id: &Pubkey) -> Vec<ProgramEvent> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for line in logs {
+        if let Some(data) = line.strip_prefix("Program data: ") {
+            if stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            let Ok(bytes) = STANDARD.decode(data.trim()) else {
+                continue;
+            };
+            if let Some(event) = ProgramEvent::decode(&bytes) {
+                events.push(event);
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            let (Some(program), Some(action)) = (words.next(), words.next()) else {
+                continue;
+            };
+            match action {
+                "invoke" => stack.push(program),
+                "success" | "failed:" => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
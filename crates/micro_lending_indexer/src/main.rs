@@ -0,0 +1,179 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand, ValueEnum};
+use micro_lending_client::PROGRAM_ID;
+use micro_lending_indexer::analytics::{self, GroupBy};
+use micro_lending_indexer::store::Store;
+use micro_lending_indexer::{IndexReport, Indexer};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Copy, ValueEnum)]
+enum GroupArg {
+    Pool,
+    Officer,
+    Cohort,
+}
+
+impl From<GroupArg> for GroupBy {
+    fn from(group: GroupArg) -> Self {
+        match group {
+            GroupArg::Pool => GroupBy::Pool,
+            GroupArg::Officer => GroupBy::Officer,
+            GroupArg::Cohort => GroupBy::Cohort,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FormatArg {
+    Json,
+    Csv,
+}
+
+#[derive(Parser)]
+#[command(
+    name = "microlend-indexer",
+    version,
+    about = "Index micro_lending events into SQLite or Postgres"
+)]
+struct Cli {
+    /// Database: a SQLite path (optionally sqlite://) or a postgres:// URL
+    #[arg(
+        long,
+        short = 'd',
+        env = "INDEXER_DATABASE_URL",
+        default_value = "sqlite://microlend.db"
+    )]
+    database: String,
+
+    /// Replay from this slot instead of the last indexed one; already indexed
+    /// transactions are skipped
+    #[arg(long, global = true)]
+    from_slot: Option<u64>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index the program's transactions from an RPC node
+    Rpc {
+        /// RPC URL
+        #[arg(
+            long,
+            short = 'u',
+            env = "INDEXER_RPC_URL",
+            default_value = "http://127.0.0.1:8899"
+        )]
+        url: String,
+
+        /// Keep polling for new transactions
+        #[arg(long)]
+        follow: bool,
+
+        /// Seconds between polls with --follow
+        #[arg(long, default_value_t = 10)]
+        interval: u64,
+    },
+    /// Index a file of getTransaction results, one JSON document per line
+    Dump { path: PathBuf },
+    /// Report PAR30/PAR90, write-offs, yield and repayment rates of the indexed loans
+    Report {
+        /// Group loans by pool, loan officer or disbursement month
+        #[arg(long, value_enum, default_value = "pool")]
+        by: GroupArg,
+
+        #[arg(long, value_enum, default_value = "json")]
+        format: FormatArg,
+
+        /// Measure arrears as of this unix timestamp instead of now
+        #[arg(long)]
+        as_of: Option<i64>,
+
+        /// Write to this file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+    },
+}
+
+fn report(
+    store: &mut Store,
+    by: GroupArg,
+    format: FormatArg,
+    as_of: Option<i64>,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let as_of = match as_of {
+        Some(as_of) => as_of,
+        None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64,
+    };
+    let metrics = analytics::portfolio_report(store, by.into(), as_of)?;
+    let writer: Box<dyn io::Write> = match output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    };
+    match format {
+        FormatArg::Json => analytics::write_json(writer, &metrics),
+        FormatArg::Csv => analytics::write_csv(writer, &metrics),
+    }
+}
+
+fn print_report(report: &IndexReport) {
+    println!(
+        "read {} transactions: {} indexed with {} events, last slot {}",
+        report.transactions,
+        report.indexed,
+        report.events,
+        report
+            .last_slot
+            .map(|slot| slot.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    );
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut indexer = Indexer::new(Store::open(&cli.database)?, PROGRAM_ID);
+    let from_slot = indexer.start_slot(cli.from_slot)?;
+
+    match cli.command {
+        Command::Dump { path } => print_report(&indexer.index_dump(&path, from_slot)?),
+        Command::Report {
+            by,
+            format,
+            as_of,
+            output,
+        } => report(&mut indexer.store, by, format, as_of, output)?,
+        Command::Rpc {
+            url,
+            follow,
+            interval,
+        } => {
+            let rpc = RpcClient::new_with_commitment(url, CommitmentConfig::confirmed());
+            let mut from_slot = from_slot;
+            loop {
+                match indexer.index_rpc(&rpc, from_slot) {
+                    Ok(report) => {
+                        if !follow || report.transactions > 0 {
+                            print_report(&report);
+                        }
+                        from_slot = from_slot.max(report.last_slot.unwrap_or_default());
+                    }
+                    Err(err) if follow => eprintln!("indexing failed: {err:#}"),
+                    Err(err) => return Err(err),
+                }
+                if !follow {
+                    break;
+                }
+                thread::sleep(Duration::from_secs(interval));
+            }
+        }
+    }
+    Ok(())
+}
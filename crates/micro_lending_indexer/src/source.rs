@@ -0,0 +1,86 @@
+//! Transaction sources: the program's signature history over RPC, or a dump file of
+//! `getTransaction` results, one JSON document per line.
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_client::rpc_request::RpcRequest;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+const SIGNATURE_PAGE: usize = 1_000;
+
+// Successful signatures of the program at or after `from_slot`, oldest first
+pub fn signatures_since(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    from_slot: u64,
+) -> Result<Vec<(String, u64)>> {
+    let mut signatures = Vec::new();
+    let mut before = None;
+    loop {
+        let page = rpc.get_signatures_for_address_with_config(
+            program_id,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until: None,
+                limit: Some(SIGNATURE_PAGE),
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        let exhausted = page.len() < SIGNATURE_PAGE || last.slot < from_slot;
+        before = Some(Signature::from_str(&last.signature)?);
+        signatures.extend(
+            page.into_iter()
+                .filter(|status| status.slot >= from_slot && status.err.is_none())
+                .map(|status| (status.signature, status.slot)),
+        );
+        if exhausted {
+            break;
+        }
+    }
+    signatures.reverse();
+    Ok(signatures)
+}
+
+pub fn fetch_transaction(rpc: &RpcClient, signature: &str) -> Result<Value> {
+    let transaction: Value = rpc.send(
+        RpcRequest::GetTransaction,
+        json!([
+            signature,
+            {
+                "encoding": "json",
+                "commitment": "confirmed",
+                "maxSupportedTransactionVersion": 0,
+            }
+        ]),
+    )?;
+    if transaction.is_null() {
+        return Err(anyhow!("transaction {signature} not found"));
+    }
+    Ok(transaction)
+}
+
+// A JSON array, or one transaction per line
+pub fn read_dump(path: &Path) -> Result<Vec<Value>> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    if contents.trim_start().starts_with('[') {
+        return Ok(serde_json::from_str(&contents)?);
+    }
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| {
+            serde_json::from_str(line).with_context(|| format!("{}:{}", path.display(), number + 1))
+        })
+        .collect()
+}
@@ -0,0 +1,387 @@
+//! SQL storage. Statements are written once with `$n` placeholders and run against
+//! either SQLite or Postgres.
+
+use anyhow::{bail, Result};
+use bytes::BytesMut;
+use postgres::types::{to_sql_checked, IsNull, ToSql, Type};
+use postgres::NoTls;
+use rusqlite::types::ValueRef;
+use std::error::Error;
+
+#[derive(Clone, Debug)]
+pub enum Value {
+    Int(i64),
+    Text(String),
+    Bool(bool),
+    Null,
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Int(value)
+    }
+}
+
+// Token amounts and slots are stored as BIGINT
+impl From<u64> for Value {
+    fn from(value: u64) -> Self {
+        Value::Int(value as i64)
+    }
+}
+
+impl From<u32> for Value {
+    fn from(value: u32) -> Self {
+        Value::Int(value as i64)
+    }
+}
+
+impl From<u16> for Value {
+    fn from(value: u16) -> Self {
+        Value::Int(value as i64)
+    }
+}
+
+impl From<u8> for Value {
+    fn from(value: u8) -> Self {
+        Value::Int(value as i64)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Value::Null)
+    }
+}
+
+// Postgres checks parameter types even for nulls, and a null from an Option has lost
+// its column type
+#[derive(Debug)]
+struct SqlNull;
+
+impl ToSql for SqlNull {
+    fn to_sql(&self, _: &Type, _: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        Ok(IsNull::Yes)
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+pub enum Store {
+    Sqlite(rusqlite::Connection),
+    Postgres(postgres::Client),
+}
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS indexer_state (
+    name TEXT PRIMARY KEY,
+    value BIGINT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS processed_transactions (
+    signature TEXT PRIMARY KEY,
+    slot BIGINT NOT NULL,
+    block_time BIGINT
+);
+CREATE TABLE IF NOT EXISTS loans (
+    loan TEXT NOT NULL,
+    requested_at BIGINT NOT NULL,
+    borrower TEXT NOT NULL,
+    pool TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    interest_rate BIGINT NOT NULL,
+    duration_days BIGINT NOT NULL,
+    collateral_type TEXT NOT NULL,
+    loan_officer TEXT,
+    status TEXT NOT NULL,
+    outcome TEXT,
+    approved_at BIGINT,
+    disbursed_at BIGINT,
+    due_date BIGINT,
+    forbearance_until BIGINT,
+    outstanding BIGINT NOT NULL,
+    closed_at BIGINT,
+    rejection_code BIGINT,
+    refinanced_from TEXT,
+    updated_slot BIGINT NOT NULL,
+    PRIMARY KEY (loan, requested_at)
+);
+CREATE TABLE IF NOT EXISTS payments (
+    signature TEXT NOT NULL,
+    event_index BIGINT NOT NULL,
+    slot BIGINT NOT NULL,
+    loan TEXT NOT NULL,
+    borrower TEXT NOT NULL,
+    pool TEXT NOT NULL,
+    payment_amount BIGINT NOT NULL,
+    platform_fee BIGINT NOT NULL,
+    interest_paid BIGINT NOT NULL,
+    late_fee BIGINT NOT NULL,
+    principal_paid BIGINT NOT NULL,
+    officer_commission BIGINT NOT NULL,
+    outstanding_after BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE TABLE IF NOT EXISTS deposits (
+    signature TEXT NOT NULL,
+    event_index BIGINT NOT NULL,
+    slot BIGINT NOT NULL,
+    pool TEXT NOT NULL,
+    lender TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    shares_minted BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE TABLE IF NOT EXISTS withdrawals (
+    signature TEXT NOT NULL,
+    event_index BIGINT NOT NULL,
+    slot BIGINT NOT NULL,
+    pool TEXT NOT NULL,
+    lender TEXT NOT NULL,
+    shares_burned BIGINT NOT NULL,
+    principal_amount BIGINT NOT NULL,
+    interest_amount BIGINT NOT NULL,
+    total_amount BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE TABLE IF NOT EXISTS defaults (
+    signature TEXT NOT NULL,
+    event_index BIGINT NOT NULL,
+    slot BIGINT NOT NULL,
+    loan TEXT NOT NULL,
+    borrower TEXT NOT NULL,
+    pool TEXT NOT NULL,
+    liquidator TEXT NOT NULL,
+    outstanding_amount BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE TABLE IF NOT EXISTS disasters (
+    signature TEXT NOT NULL,
+    event_index BIGINT NOT NULL,
+    slot BIGINT NOT NULL,
+    pool TEXT NOT NULL,
+    forbearance_days BIGINT NOT NULL,
+    forbearance_until BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE TABLE IF NOT EXISTS attestations (
+    signature TEXT NOT NULL,
+    event_index BIGINT NOT NULL,
+    slot BIGINT NOT NULL,
+    attestation TEXT NOT NULL,
+    user_key TEXT NOT NULL,
+    attester TEXT NOT NULL,
+    action TEXT NOT NULL,
+    attestation_type TEXT NOT NULL,
+    score BIGINT NOT NULL,
+    verified BOOLEAN NOT NULL,
+    is_active BOOLEAN NOT NULL,
+    expires_at BIGINT,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE TABLE IF NOT EXISTS score_changes (
+    signature TEXT NOT NULL,
+    event_index BIGINT NOT NULL,
+    slot BIGINT NOT NULL,
+    user_key TEXT NOT NULL,
+    old_score BIGINT NOT NULL,
+    new_score BIGINT NOT NULL,
+    successful_loans BIGINT NOT NULL,
+    defaulted_loans BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+";
+
+// SQLite spells numbered parameters `?n`
+fn sqlite_placeholders(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '$' && chars.peek().is_some_and(char::is_ascii_digit) {
+            out.push('?');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn sqlite_params(params: &[Value]) -> Vec<rusqlite::types::Value> {
+    params
+        .iter()
+        .map(|value| match value {
+            Value::Int(value) => rusqlite::types::Value::Integer(*value),
+            Value::Text(value) => rusqlite::types::Value::Text(value.clone()),
+            Value::Bool(value) => rusqlite::types::Value::Integer(*value as i64),
+            Value::Null => rusqlite::types::Value::Null,
+        })
+        .collect()
+}
+
+fn postgres_params(params: &[Value]) -> Vec<Box<dyn ToSql + Sync>> {
+    params
+        .iter()
+        .map(|value| -> Box<dyn ToSql + Sync> {
+            match value {
+                Value::Int(value) => Box::new(*value),
+                Value::Text(value) => Box::new(value.clone()),
+                Value::Bool(value) => Box::new(*value),
+                Value::Null => Box::new(SqlNull),
+            }
+        })
+        .collect()
+}
+
+impl Store {
+    // `sqlite://path`, a bare file path, or a `postgres://` connection string
+    pub fn open(url: &str) -> Result<Store> {
+        let mut store = if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+            Store::Postgres(postgres::Client::connect(url, NoTls)?)
+        } else {
+            let path = url.strip_prefix("sqlite://").unwrap_or(url);
+            Store::Sqlite(rusqlite::Connection::open(path)?)
+        };
+        store.batch(SCHEMA)?;
+        Ok(store)
+    }
+
+    pub fn batch(&mut self, sql: &str) -> Result<()> {
+        match self {
+            Store::Sqlite(connection) => connection.execute_batch(sql)?,
+            Store::Postgres(client) => client.batch_execute(sql)?,
+        }
+        Ok(())
+    }
+
+    pub fn execute(&mut self, sql: &str, params: &[Value]) -> Result<u64> {
+        match self {
+            Store::Sqlite(connection) => {
+                let changed = connection.execute(
+                    &sqlite_placeholders(sql),
+                    rusqlite::params_from_iter(sqlite_params(params)),
+                )?;
+                Ok(changed as u64)
+            }
+            Store::Postgres(client) => {
+                let params = postgres_params(params);
+                let params: Vec<&(dyn ToSql + Sync)> =
+                    params.iter().map(|param| param.as_ref()).collect();
+                Ok(client.execute(sql, &params)?)
+            }
+        }
+    }
+
+    // Rows of BIGINT, TEXT and BOOLEAN columns
+    pub fn query(&mut self, sql: &str, params: &[Value]) -> Result<Vec<Vec<Value>>> {
+        match self {
+            Store::Sqlite(connection) => {
+                let mut statement = connection.prepare(&sqlite_placeholders(sql))?;
+                let columns = statement.column_count();
+                let mut rows =
+                    statement.query(rusqlite::params_from_iter(sqlite_params(params)))?;
+                let mut out = Vec::new();
+                while let Some(row) = rows.next()? {
+                    let mut values = Vec::with_capacity(columns);
+                    for index in 0..columns {
+                        values.push(match row.get_ref(index)? {
+                            ValueRef::Null => Value::Null,
+                            ValueRef::Integer(value) => Value::Int(value),
+                            ValueRef::Text(value) => {
+                                Value::Text(String::from_utf8_lossy(value).into_owned())
+                            }
+                            other => bail!("unsupported SQLite value {other:?}"),
+                        });
+                    }
+                    out.push(values);
+                }
+                Ok(out)
+            }
+            Store::Postgres(client) => {
+                let params = postgres_params(params);
+                let params: Vec<&(dyn ToSql + Sync)> =
+                    params.iter().map(|param| param.as_ref()).collect();
+                let mut out = Vec::new();
+                for row in client.query(sql, &params)? {
+                    let mut values = Vec::with_capacity(row.len());
+                    for (index, column) in row.columns().iter().enumerate() {
+                        let value = match *column.type_() {
+                            Type::INT8 => row.get::<_, Option<i64>>(index).into(),
+                            Type::BOOL => row.get::<_, Option<bool>>(index).into(),
+                            Type::TEXT | Type::VARCHAR => {
+                                row.get::<_, Option<String>>(index).into()
+                            }
+                            ref other => bail!("unsupported Postgres column type {other}"),
+                        };
+                        values.push(value);
+                    }
+                    out.push(values);
+                }
+                Ok(out)
+            }
+        }
+    }
+
+    fn query_i64(&mut self, sql: &str, params: &[Value]) -> Result<Option<i64>> {
+        Ok(
+            match self.query(sql, params)?.first().and_then(|row| row.first()) {
+                Some(Value::Int(value)) => Some(*value),
+                _ => None,
+            },
+        )
+    }
+
+    pub fn last_slot(&mut self) -> Result<Option<u64>> {
+        Ok(self
+            .query_i64(
+                "SELECT value FROM indexer_state WHERE name = $1",
+                &["last_slot".into()],
+            )?
+            .map(|slot| slot as u64))
+    }
+
+    pub fn set_last_slot(&mut self, slot: u64) -> Result<()> {
+        self.execute(
+            "INSERT INTO indexer_state (name, value) VALUES ($1, $2)
+             ON CONFLICT (name) DO UPDATE SET value = excluded.value
+             WHERE indexer_state.value < excluded.value",
+            &["last_slot".into(), slot.into()],
+        )?;
+        Ok(())
+    }
+
+    pub fn is_processed(&mut self, signature: &str) -> Result<bool> {
+        Ok(self
+            .query_i64(
+                "SELECT slot FROM processed_transactions WHERE signature = $1",
+                &[signature.into()],
+            )?
+            .is_some())
+    }
+}
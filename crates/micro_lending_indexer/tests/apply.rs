@@ -0,0 +1,206 @@
+//! Writing decoded events into the tables, and replaying transactions already indexed.
+
+use anchor_lang::prelude::Pubkey;
+use micro_lending_client::micro_lending::{
+    CollateralType, Deposited, LoanDisbursed, LoanOutcome, LoanRepaid, LoanRequested, PaymentMade,
+};
+use micro_lending_indexer::apply::apply_transaction;
+use micro_lending_indexer::events::ProgramEvent;
+use micro_lending_indexer::logs::IndexedTransaction;
+use micro_lending_indexer::store::{Store, Value};
+
+const DAY: i64 = 86_400;
+
+struct Keys {
+    loan: Pubkey,
+    borrower: Pubkey,
+    pool: Pubkey,
+}
+
+fn keys() -> Keys {
+    Keys {
+        loan: Pubkey::new_unique(),
+        borrower: Pubkey::new_unique(),
+        pool: Pubkey::new_unique(),
+    }
+}
+
+fn tx(signature: &str, slot: u64, events: Vec<ProgramEvent>) -> IndexedTransaction {
+    IndexedTransaction {
+        signature: signature.to_string(),
+        slot,
+        block_time: Some(slot as i64),
+        events,
+    }
+}
+
+fn requested(keys: &Keys, timestamp: i64) -> ProgramEvent {
+    ProgramEvent::LoanRequested(LoanRequested {
+        loan: keys.loan,
+        borrower: keys.borrower,
+        pool: keys.pool,
+        amount: 100_000,
+        duration_days: 30,
+        interest_rate: 1000,
+        collateral_type: CollateralType::None,
+        loan_officer: None,
+        timestamp,
+    })
+}
+
+fn disbursed(keys: &Keys, timestamp: i64) -> ProgramEvent {
+    ProgramEvent::LoanDisbursed(LoanDisbursed {
+        loan: keys.loan,
+        borrower: keys.borrower,
+        pool: keys.pool,
+        amount: 100_000,
+        interest_rate: 1000,
+        due_date: timestamp + 30 * DAY,
+        pool_borrowed_before: 0,
+        pool_borrowed_after: 100_000,
+        timestamp,
+    })
+}
+
+fn paid(keys: &Keys, principal_paid: u64, outstanding_after: u64, timestamp: i64) -> ProgramEvent {
+    ProgramEvent::PaymentMade(PaymentMade {
+        loan: keys.loan,
+        borrower: keys.borrower,
+        pool: keys.pool,
+        payment_amount: principal_paid + 100,
+        platform_fee: 0,
+        interest_paid: 100,
+        late_fee: 0,
+        principal_paid,
+        officer_commission: 0,
+        outstanding_before: outstanding_after + principal_paid,
+        outstanding_after,
+        pool_liquidity_before: 0,
+        pool_liquidity_after: principal_paid + 100,
+        timestamp,
+    })
+}
+
+fn repaid(keys: &Keys, timestamp: i64) -> ProgramEvent {
+    ProgramEvent::LoanRepaid(LoanRepaid {
+        loan: keys.loan,
+        borrower: keys.borrower,
+        pool: keys.pool,
+        amount: 100_000,
+        amount_repaid: 100_000,
+        outcome: LoanOutcome::RepaidOnTime,
+        payment_count: 1,
+        timestamp,
+    })
+}
+
+fn deposited(keys: &Keys, amount: u64, timestamp: i64) -> ProgramEvent {
+    ProgramEvent::Deposited(Deposited {
+        pool: keys.pool,
+        lender: Pubkey::new_unique(),
+        amount,
+        shares_minted: amount,
+        lender_shares_before: 0,
+        lender_shares_after: amount,
+        pool_liquidity_before: 0,
+        pool_liquidity_after: amount,
+        timestamp,
+    })
+}
+
+fn count(store: &mut Store, table: &str) -> i64 {
+    match &store
+        .query(&format!("SELECT COUNT(*) FROM {table}"), &[])
+        .unwrap()[0][0]
+    {
+        Value::Int(count) => *count,
+        other => panic!("unexpected count {other:?}"),
+    }
+}
+
+// (status, outstanding) of each request of the loan address, oldest first
+fn loan_rows(store: &mut Store, keys: &Keys) -> Vec<(String, i64)> {
+    store
+        .query(
+            "SELECT status, outstanding FROM loans WHERE loan = $1 ORDER BY requested_at",
+            &[keys.loan.to_string().into()],
+        )
+        .unwrap()
+        .into_iter()
+        .map(|row| match (&row[0], &row[1]) {
+            (Value::Text(status), Value::Int(outstanding)) => (status.clone(), *outstanding),
+            other => panic!("unexpected row {other:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn replaying_indexed_transactions_changes_nothing() {
+    let mut store = Store::open("sqlite://:memory:").unwrap();
+    let keys = keys();
+    let transactions = [
+        tx("request", 10, vec![requested(&keys, DAY)]),
+        tx("disburse", 11, vec![disbursed(&keys, 2 * DAY)]),
+        tx(
+            "pay",
+            12,
+            vec![
+                paid(&keys, 40_000, 60_000, 3 * DAY),
+                deposited(&keys, 5_000, 3 * DAY),
+            ],
+        ),
+    ];
+    for tx in &transactions {
+        assert!(apply_transaction(&mut store, tx).unwrap());
+    }
+    assert_eq!(
+        loan_rows(&mut store, &keys),
+        vec![("active".to_string(), 60_000)]
+    );
+
+    // A replay from an earlier slot skips every transaction and keeps the resume point
+    for tx in &transactions {
+        assert!(!apply_transaction(&mut store, tx).unwrap());
+    }
+    assert_eq!(count(&mut store, "loans"), 1);
+    assert_eq!(count(&mut store, "payments"), 1);
+    assert_eq!(count(&mut store, "deposits"), 1);
+    assert_eq!(count(&mut store, "processed_transactions"), 3);
+    assert_eq!(store.last_slot().unwrap(), Some(12));
+    assert_eq!(
+        loan_rows(&mut store, &keys),
+        vec![("active".to_string(), 60_000)]
+    );
+}
+
+#[test]
+fn events_land_on_the_newest_request_of_a_reused_address() {
+    let mut store = Store::open("sqlite://:memory:").unwrap();
+    let keys = keys();
+    let transactions = [
+        tx(
+            "first",
+            10,
+            vec![requested(&keys, DAY), disbursed(&keys, DAY)],
+        ),
+        tx(
+            "repay",
+            11,
+            vec![repaid(&keys, 5 * DAY), paid(&keys, 100_000, 0, 5 * DAY)],
+        ),
+        tx(
+            "second",
+            12,
+            vec![requested(&keys, 10 * DAY), disbursed(&keys, 10 * DAY)],
+        ),
+        tx("partial", 13, vec![paid(&keys, 30_000, 70_000, 12 * DAY)]),
+    ];
+    for tx in &transactions {
+        apply_transaction(&mut store, tx).unwrap();
+    }
+    assert_eq!(
+        loan_rows(&mut store, &keys),
+        vec![("repaid".to_string(), 0), ("active".to_string(), 70_000)]
+    );
+    assert_eq!(store.last_slot().unwrap(), Some(13));
+}
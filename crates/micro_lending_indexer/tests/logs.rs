@@ -0,0 +1,106 @@
+//! Event extraction from transaction logs, including logs of CPIs.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{Discriminator, Event};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use micro_lending_client::micro_lending::{Deposited, InterestClaimed};
+use micro_lending_indexer::events::ProgramEvent;
+use micro_lending_indexer::logs::{parse_transaction, program_events};
+use serde_json::json;
+
+fn deposited(amount: u64) -> String {
+    let event = Deposited {
+        pool: Pubkey::new_unique(),
+        lender: Pubkey::new_unique(),
+        amount,
+        shares_minted: amount,
+        lender_shares_before: 0,
+        lender_shares_after: amount,
+        pool_liquidity_before: 0,
+        pool_liquidity_after: amount,
+        timestamp: 1_700_000_000,
+    };
+    format!("Program data: {}", STANDARD.encode(event.data()))
+}
+
+fn deposited_amounts(events: &[ProgramEvent]) -> Vec<u64> {
+    events
+        .iter()
+        .map(|event| match event {
+            ProgramEvent::Deposited(event) => event.amount,
+            _ => panic!("unexpected event"),
+        })
+        .collect()
+}
+
+#[test]
+fn only_data_logged_by_the_program_itself_is_decoded() {
+    let program = micro_lending_client::PROGRAM_ID.to_string();
+    let token = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+    let other = Pubkey::new_unique().to_string();
+    let logs = [
+        format!("Program {other} invoke [1]"),
+        deposited(1),
+        format!("Program {other} success"),
+        format!("Program {program} invoke [1]"),
+        "Program log: Instruction: DepositToPool".to_string(),
+        deposited(2),
+        format!("Program {token} invoke [2]"),
+        deposited(3),
+        format!("Program {token} consumed 4645 of 180000 compute units"),
+        format!("Program {token} success"),
+        deposited(4),
+        format!("Program {program} consumed 20000 of 200000 compute units"),
+        format!("Program {program} success"),
+        // The program reached through a CPI from another program
+        format!("Program {other} invoke [1]"),
+        format!("Program {program} invoke [2]"),
+        deposited(5),
+        format!("Program {program} success"),
+        deposited(6),
+        format!("Program {other} success"),
+    ];
+    let logs: Vec<&str> = logs.iter().map(String::as_str).collect();
+    let events = program_events(&logs, &micro_lending_client::PROGRAM_ID);
+    assert_eq!(deposited_amounts(&events), vec![2, 4, 5]);
+}
+
+#[test]
+fn undecodable_data_is_skipped() {
+    let program = micro_lending_client::PROGRAM_ID.to_string();
+    let claimed_prefix = STANDARD.encode(InterestClaimed::DISCRIMINATOR);
+    let logs = [
+        format!("Program {program} invoke [1]"),
+        "Program data: not base64!".to_string(),
+        format!("Program data: {claimed_prefix}"),
+        format!("Program data: {}", STANDARD.encode([0u8; 16])),
+        deposited(7),
+        format!("Program {program} success"),
+    ];
+    let logs: Vec<&str> = logs.iter().map(String::as_str).collect();
+    let events = program_events(&logs, &micro_lending_client::PROGRAM_ID);
+    assert_eq!(deposited_amounts(&events), vec![7]);
+}
+
+#[test]
+fn failed_transactions_are_not_indexed() {
+    let program = micro_lending_client::PROGRAM_ID.to_string();
+    let logs = json!([format!("Program {program} invoke [1]"), deposited(1)]);
+    let failed = json!({
+        "slot": 10,
+        "blockTime": 1_700_000_000,
+        "transaction": { "signatures": ["sig"] },
+        "meta": { "err": { "InstructionError": [0, "Custom"] }, "logMessages": logs },
+    });
+    let program_id = micro_lending_client::PROGRAM_ID;
+    assert!(parse_transaction(&failed, &program_id).unwrap().is_none());
+
+    let flattened = json!({ "slot": 10, "signature": "sig", "err": null, "logs": logs });
+    let tx = parse_transaction(&flattened, &program_id).unwrap().unwrap();
+    assert_eq!((tx.signature.as_str(), tx.slot), ("sig", 10));
+    assert_eq!(tx.block_time, None);
+    assert_eq!(deposited_amounts(&tx.events), vec![1]);
+
+    assert!(parse_transaction(&json!({ "signature": "sig" }), &program_id).is_err());
+}
@@ -0,0 +1,75 @@
+//! Permissionless instructions the keeper runs, and the local checks that decide when a
+//! loan needs one. The checks mirror the program's own `require!`s so the keeper only
+//! submits transactions that should succeed.
+
+use anchor_lang::prelude::Pubkey;
+use micro_lending_client::instructions;
+use micro_lending_client::micro_lending::utils::{
+    is_in_forbearance, is_loan_overdue, is_loan_stale, sync_disaster_forbearance,
+};
+use micro_lending_client::micro_lending::{LendingPool, Loan, LoanStatus};
+use solana_sdk::instruction::Instruction;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Crank {
+    // Default an overdue disbursed loan
+    Liquidate,
+    // Close a request or approval that outlived the pool's TTL
+    Expire,
+}
+
+impl Crank {
+    pub const ALL: [Crank; 2] = [Crank::Liquidate, Crank::Expire];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Crank::Liquidate => "liquidate_loan",
+            Crank::Expire => "expire_loan",
+        }
+    }
+
+    // Loan statuses the crank applies to, used as getProgramAccounts filters
+    pub fn statuses(self) -> &'static [LoanStatus] {
+        match self {
+            Crank::Liquidate => &[LoanStatus::Disbursed, LoanStatus::Active],
+            Crank::Expire => &[LoanStatus::Requested, LoanStatus::Approved],
+        }
+    }
+
+    pub fn is_due(self, loan: &Loan, lending_pool: &LendingPool, current_time: i64) -> bool {
+        match self {
+            Crank::Liquidate => is_liquidatable(loan, lending_pool, current_time),
+            Crank::Expire => is_loan_stale(loan, lending_pool, current_time),
+        }
+    }
+
+    pub fn instruction(self, keeper: &Pubkey, loan: &Loan) -> Instruction {
+        match self {
+            Crank::Liquidate => instructions::liquidate_loan(
+                keeper,
+                &loan.lender_pool,
+                &loan.borrower,
+                loan.loan_officer.as_ref(),
+                &loan.backing_attesters,
+            ),
+            Crank::Expire => instructions::expire_loan(keeper, &loan.lender_pool, &loan.borrower),
+        }
+    }
+}
+
+// Same checks as liquidate_loan, including forbearance from a disaster declared on the pool
+pub fn is_liquidatable(loan: &Loan, lending_pool: &LendingPool, current_time: i64) -> bool {
+    if loan.status != LoanStatus::Disbursed && loan.status != LoanStatus::Active {
+        return false;
+    }
+    let mut loan = loan.clone();
+    sync_disaster_forbearance(&mut loan, lending_pool);
+    !is_in_forbearance(&loan, current_time)
+        && is_loan_overdue(loan.due_date, current_time, loan.grace_period_days)
+}
+
+pub struct Job {
+    pub crank: Crank,
+    pub loan_address: Pubkey,
+    pub loan: Loan,
+}
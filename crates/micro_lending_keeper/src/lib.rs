@@ -0,0 +1,232 @@
+//! Keeper for the micro_lending program: scans loans and submits the permissionless
+//! cranks (`liquidate_loan`, `expire_loan`) once they are due.
+
+pub mod cranks;
+pub mod metrics;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use cranks::{Crank, Job};
+use metrics::Metrics;
+use micro_lending_client::accounts;
+use micro_lending_client::micro_lending::LendingPool;
+use solana_client::client_error::ClientError;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Debug)]
+pub struct KeeperConfig {
+    pub cranks: Vec<Crank>,
+    // Compute unit price of the first attempt, in micro-lamports
+    pub priority_fee: u64,
+    // Multiplier applied to the price on each retry, capped at max_priority_fee
+    pub priority_fee_multiplier: u64,
+    pub max_priority_fee: u64,
+    pub compute_unit_limit: u32,
+    pub max_attempts: u32,
+    pub retry_delay: Duration,
+}
+
+impl Default for KeeperConfig {
+    fn default() -> Self {
+        KeeperConfig {
+            cranks: Crank::ALL.to_vec(),
+            priority_fee: 1_000,
+            priority_fee_multiplier: 2,
+            max_priority_fee: 1_000_000,
+            compute_unit_limit: 200_000,
+            max_attempts: 4,
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl KeeperConfig {
+    // Compute unit price of the attempt after one priced at `priority_fee`
+    pub fn next_priority_fee(&self, priority_fee: u64) -> u64 {
+        priority_fee
+            .saturating_mul(self.priority_fee_multiplier)
+            .min(self.max_priority_fee)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ScanReport {
+    pub loans_scanned: usize,
+    pub jobs_found: usize,
+    pub submitted: usize,
+    pub failed: usize,
+}
+
+pub struct Keeper {
+    pub rpc: RpcClient,
+    pub keypair: Keypair,
+    pub config: KeeperConfig,
+    pub metrics: Arc<Metrics>,
+}
+
+impl Keeper {
+    pub fn new(rpc: RpcClient, keypair: Keypair, config: KeeperConfig) -> Self {
+        Keeper {
+            rpc,
+            keypair,
+            config,
+            metrics: Arc::new(Metrics::default()),
+        }
+    }
+
+    // One pass over every loan in a crankable status
+    pub fn run_once(&self) -> Result<ScanReport> {
+        let result = self.scan();
+        if result.is_err() {
+            self.metrics.record_scan_error();
+        }
+        let (jobs, loans_scanned, now) = result?;
+        self.metrics.record_scan(loans_scanned, now);
+
+        let mut report = ScanReport {
+            loans_scanned,
+            jobs_found: jobs.len(),
+            ..ScanReport::default()
+        };
+        for job in jobs {
+            self.metrics.record_job_found(job.crank);
+            let instruction = job.crank.instruction(&self.keypair.pubkey(), &job.loan);
+            match self.submit(instruction) {
+                Ok(signature) => {
+                    report.submitted += 1;
+                    self.metrics.record_submitted(job.crank);
+                    println!(
+                        "{} {} (borrower {}): {}",
+                        job.crank.name(),
+                        job.loan_address,
+                        job.loan.borrower,
+                        signature
+                    );
+                }
+                Err(err) => {
+                    report.failed += 1;
+                    self.metrics.record_failed(job.crank);
+                    eprintln!("{} {}: {:#}", job.crank.name(), job.loan_address, err);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    // Run forever, scanning every `interval`
+    pub fn run(&self, interval: Duration) -> ! {
+        loop {
+            match self.run_once() {
+                Ok(report) if report.jobs_found > 0 => println!(
+                    "scanned {} loans: {} due, {} submitted, {} failed",
+                    report.loans_scanned, report.jobs_found, report.submitted, report.failed
+                ),
+                Ok(_) => {}
+                Err(err) => eprintln!("scan failed: {err:#}"),
+            }
+            thread::sleep(interval);
+        }
+    }
+
+    fn scan(&self) -> Result<(Vec<Job>, usize, i64)> {
+        let now = self.cluster_time();
+        let pools: HashMap<Pubkey, LendingPool> = accounts::fetch_lending_pools(&self.rpc)?
+            .into_iter()
+            .collect();
+
+        let mut jobs = Vec::new();
+        let mut loans_scanned = 0;
+        for crank in &self.config.cranks {
+            for status in crank.statuses() {
+                let loans = accounts::fetch_loans_by_status(&self.rpc, *status)?;
+                loans_scanned += loans.len();
+                for (loan_address, loan) in loans {
+                    let Some(lending_pool) = pools.get(&loan.lender_pool) else {
+                        continue;
+                    };
+                    if crank.is_due(&loan, lending_pool, now) {
+                        jobs.push(Job {
+                            crank: *crank,
+                            loan_address,
+                            loan,
+                        });
+                    }
+                }
+            }
+        }
+        Ok((jobs, loans_scanned, now))
+    }
+
+    // Send with a compute budget, retrying with a fresh blockhash and a higher price.
+    // Program errors are final: the loan changed under us and retrying cannot help.
+    pub fn submit(&self, instruction: Instruction) -> Result<Signature> {
+        let mut priority_fee = self.config.priority_fee;
+        let mut attempt = 1;
+        loop {
+            self.metrics.record_priority_fee(priority_fee);
+            match self.send(&instruction, priority_fee) {
+                Ok(signature) => return Ok(signature),
+                Err(err) if attempt >= self.config.max_attempts || is_program_error(&err) => {
+                    return Err(err.into());
+                }
+                Err(err) => {
+                    eprintln!("attempt {attempt} failed, retrying: {err}");
+                    attempt += 1;
+                    priority_fee = self.config.next_priority_fee(priority_fee);
+                    self.metrics.record_retry(priority_fee);
+                    thread::sleep(self.config.retry_delay);
+                }
+            }
+        }
+    }
+
+    fn send(
+        &self,
+        instruction: &Instruction,
+        priority_fee: u64,
+    ) -> Result<Signature, Box<ClientError>> {
+        let instructions = [
+            ComputeBudgetInstruction::set_compute_unit_limit(self.config.compute_unit_limit),
+            ComputeBudgetInstruction::set_compute_unit_price(priority_fee),
+            instruction.clone(),
+        ];
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&self.keypair.pubkey()),
+            &[&self.keypair],
+            blockhash,
+        );
+        Ok(self.rpc.send_and_confirm_transaction(&transaction)?)
+    }
+
+    // Loan deadlines are judged against cluster time, which a test validator may warp
+    fn cluster_time(&self) -> i64 {
+        if let Ok(slot) = self.rpc.get_slot() {
+            if let Ok(block_time) = self.rpc.get_block_time(slot) {
+                return block_time;
+            }
+        }
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or_default()
+    }
+}
+
+fn is_program_error(err: &ClientError) -> bool {
+    matches!(
+        err.get_transaction_error(),
+        Some(solana_sdk::transaction::TransactionError::InstructionError(
+            ..
+        ))
+    )
+}
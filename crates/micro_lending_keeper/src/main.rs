@@ -0,0 +1,116 @@
+use anyhow::{anyhow, Result};
+use clap::{Parser, ValueEnum};
+use micro_lending_keeper::cranks::Crank;
+use micro_lending_keeper::{metrics, Keeper, KeeperConfig};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::read_keypair_file;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CrankArg {
+    Liquidate,
+    Expire,
+}
+
+impl From<CrankArg> for Crank {
+    fn from(crank: CrankArg) -> Self {
+        match crank {
+            CrankArg::Liquidate => Crank::Liquidate,
+            CrankArg::Expire => Crank::Expire,
+        }
+    }
+}
+
+#[derive(Parser)]
+#[command(
+    name = "microlend-keeper",
+    version,
+    about = "Liquidate overdue loans and expire stale requests"
+)]
+struct Cli {
+    /// RPC URL
+    #[arg(
+        long,
+        short = 'u',
+        env = "KEEPER_RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+
+    /// Keeper keypair; pays fees and signs cranks
+    #[arg(long, short = 'k', env = "KEEPER_KEYPAIR")]
+    keypair: PathBuf,
+
+    /// Cranks to run; all by default
+    #[arg(long = "crank", value_enum)]
+    cranks: Vec<CrankArg>,
+
+    /// Seconds between scans
+    #[arg(long, default_value_t = 30)]
+    interval: u64,
+
+    /// Scan and submit once, then exit
+    #[arg(long)]
+    once: bool,
+
+    /// Serve Prometheus metrics on this address, e.g. 127.0.0.1:9464
+    #[arg(long, env = "KEEPER_METRICS_ADDR")]
+    metrics_addr: Option<SocketAddr>,
+
+    /// Compute unit price of the first attempt, in micro-lamports
+    #[arg(long, default_value_t = 1_000)]
+    priority_fee: u64,
+
+    /// Multiplier applied to the compute unit price on each retry
+    #[arg(long, default_value_t = 2)]
+    priority_fee_multiplier: u64,
+
+    /// Upper bound on the compute unit price, in micro-lamports
+    #[arg(long, default_value_t = 1_000_000)]
+    max_priority_fee: u64,
+
+    #[arg(long, default_value_t = 200_000)]
+    compute_unit_limit: u32,
+
+    /// Attempts per transaction before giving up until the next scan
+    #[arg(long, default_value_t = 4)]
+    max_attempts: u32,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let keypair = read_keypair_file(&cli.keypair)
+        .map_err(|err| anyhow!("reading keypair {}: {}", cli.keypair.display(), err))?;
+    let rpc = RpcClient::new_with_commitment(cli.url, CommitmentConfig::confirmed());
+
+    let mut config = KeeperConfig {
+        priority_fee: cli.priority_fee,
+        priority_fee_multiplier: cli.priority_fee_multiplier,
+        max_priority_fee: cli.max_priority_fee,
+        compute_unit_limit: cli.compute_unit_limit,
+        max_attempts: cli.max_attempts.max(1),
+        ..KeeperConfig::default()
+    };
+    if !cli.cranks.is_empty() {
+        config.cranks = cli.cranks.into_iter().map(Crank::from).collect();
+    }
+    let keeper = Keeper::new(rpc, keypair, config);
+
+    if let Some(address) = cli.metrics_addr {
+        metrics::serve(address, keeper.metrics.clone())?;
+        println!("metrics on http://{address}/metrics");
+    }
+
+    if cli.once {
+        let report = keeper.run_once()?;
+        println!(
+            "scanned {} loans: {} due, {} submitted, {} failed",
+            report.loans_scanned, report.jobs_found, report.submitted, report.failed
+        );
+        return Ok(());
+    }
+    keeper.run(Duration::from_secs(cli.interval))
+}
@@ -0,0 +1,170 @@
+//! Prometheus counters for the keeper, served as plain text over HTTP.
+
+use crate::cranks::Crank;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+#[derive(Default)]
+struct CrankMetrics {
+    jobs_found: AtomicU64,
+    submitted: AtomicU64,
+    failed: AtomicU64,
+}
+
+#[derive(Default)]
+pub struct Metrics {
+    scans: AtomicU64,
+    scan_errors: AtomicU64,
+    loans_scanned: AtomicU64,
+    retries: AtomicU64,
+    last_scan_timestamp: AtomicU64,
+    last_priority_fee: AtomicU64,
+    cranks: [CrankMetrics; Crank::ALL.len()],
+}
+
+impl Metrics {
+    fn crank(&self, crank: Crank) -> &CrankMetrics {
+        &self.cranks[crank as usize]
+    }
+
+    pub fn record_scan(&self, loans_scanned: usize, timestamp: i64) {
+        self.scans.fetch_add(1, Ordering::Relaxed);
+        self.loans_scanned
+            .store(loans_scanned as u64, Ordering::Relaxed);
+        self.last_scan_timestamp
+            .store(timestamp.max(0) as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_scan_error(&self) {
+        self.scan_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_job_found(&self, crank: Crank) {
+        self.crank(crank).jobs_found.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_submitted(&self, crank: Crank) {
+        self.crank(crank).submitted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_failed(&self, crank: Crank) {
+        self.crank(crank).failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_retry(&self, priority_fee: u64) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+        self.last_priority_fee
+            .store(priority_fee, Ordering::Relaxed);
+    }
+
+    pub fn record_priority_fee(&self, priority_fee: u64) {
+        self.last_priority_fee
+            .store(priority_fee, Ordering::Relaxed);
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let gauge = |out: &mut String, name: &str, kind: &str, help: &str, value: u64| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            let _ = writeln!(out, "{name} {value}");
+        };
+        gauge(
+            &mut out,
+            "keeper_scans_total",
+            "counter",
+            "Completed account scans",
+            self.scans.load(Ordering::Relaxed),
+        );
+        gauge(
+            &mut out,
+            "keeper_scan_errors_total",
+            "counter",
+            "Scans that failed to fetch accounts",
+            self.scan_errors.load(Ordering::Relaxed),
+        );
+        gauge(
+            &mut out,
+            "keeper_loans_scanned",
+            "gauge",
+            "Loans examined in the last scan",
+            self.loans_scanned.load(Ordering::Relaxed),
+        );
+        gauge(
+            &mut out,
+            "keeper_retries_total",
+            "counter",
+            "Transaction resubmissions",
+            self.retries.load(Ordering::Relaxed),
+        );
+        gauge(
+            &mut out,
+            "keeper_last_scan_timestamp_seconds",
+            "gauge",
+            "Cluster time of the last completed scan",
+            self.last_scan_timestamp.load(Ordering::Relaxed),
+        );
+        gauge(
+            &mut out,
+            "keeper_priority_fee_micro_lamports",
+            "gauge",
+            "Compute unit price of the last submission",
+            self.last_priority_fee.load(Ordering::Relaxed),
+        );
+
+        let labelled = [
+            ("keeper_jobs_found_total", "Loans found due for a crank"),
+            ("keeper_submitted_total", "Crank transactions confirmed"),
+            ("keeper_failed_total", "Crank transactions that gave up"),
+        ];
+        for (index, (name, help)) in labelled.iter().enumerate() {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} counter");
+            for crank in Crank::ALL {
+                let metrics = self.crank(crank);
+                let value = match index {
+                    0 => &metrics.jobs_found,
+                    1 => &metrics.submitted,
+                    _ => &metrics.failed,
+                };
+                let _ = writeln!(
+                    out,
+                    "{name}{{crank=\"{}\"}} {}",
+                    crank.name(),
+                    value.load(Ordering::Relaxed)
+                );
+            }
+        }
+        out
+    }
+}
+
+// Serve GET /metrics on a background thread
+pub fn serve(address: SocketAddr, metrics: Arc<Metrics>) -> std::io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let _ = respond(stream, &metrics);
+        }
+    });
+    Ok(())
+}
+
+fn respond(mut stream: TcpStream, metrics: &Metrics) -> std::io::Result<()> {
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let (status, body) = if request_line.starts_with("GET /metrics") {
+        ("200 OK", metrics.render())
+    } else {
+        ("404 Not Found", String::new())
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
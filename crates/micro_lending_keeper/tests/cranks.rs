@@ -0,0 +1,257 @@
+//! The keeper's local due checks, crank instructions and retry pricing, without a node.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountSerialize;
+use micro_lending_client::accounts::{LOAN_LENDER_POOL_OFFSET, LOAN_STATUS_OFFSET};
+use micro_lending_client::micro_lending::{
+    AutoApprovalRules, BorrowerPolicy, CollateralType, LendingPool, Loan, LoanStatus,
+    RepaymentIncentives, ALL_COLLATERAL_TYPES,
+};
+use micro_lending_client::pda;
+use micro_lending_keeper::cranks::{is_liquidatable, Crank};
+use micro_lending_keeper::metrics::Metrics;
+use micro_lending_keeper::KeeperConfig;
+
+const DAY: i64 = 86_400;
+
+fn pool() -> LendingPool {
+    LendingPool {
+        authority: Pubkey::default(),
+        mint: Pubkey::default(),
+        token_account: Pubkey::default(),
+        name: "keeper".to_string(),
+        base_interest_rate: 1000,
+        max_loan_duration: 365,
+        total_deposited: 0,
+        total_borrowed: 0,
+        total_shares: 0,
+        available_liquidity: 0,
+        active_loans: 0,
+        total_interest_earned: 0,
+        total_interest_distributed: 0,
+        interest_per_share: 0,
+        is_active: true,
+        created_at: 0,
+        borrower_policy: BorrowerPolicy {
+            min_credit_score: 0,
+            required_verifications: 0,
+            min_attestations: 0,
+            allowed_collateral_types: ALL_COLLATERAL_TYPES,
+            max_loan_per_borrower: 0,
+            allowed_purposes: Vec::new(),
+        },
+        total_reserved: 0,
+        auto_approval: AutoApprovalRules {
+            enabled: false,
+            max_utilization_bps: 0,
+            score_bands: Vec::new(),
+        },
+        request_ttl: 2 * DAY,
+        approval_ttl: DAY,
+        committee: None,
+        disaster_declared_at: 0,
+        disaster_forbearance_until: 0,
+        repayment_incentives: RepaymentIncentives {
+            streak_length: 0,
+            rate_step_down_bps: 0,
+            min_interest_rate: 0,
+            early_payoff_rebate_bps: 0,
+        },
+    }
+}
+
+// Requested on day 1, disbursed on day 1 and due on day 30 with 3 days' grace
+fn loan(status: LoanStatus) -> Loan {
+    Loan {
+        borrower: Pubkey::new_unique(),
+        lender_pool: Pubkey::new_unique(),
+        amount: 100_000,
+        interest_rate: 1000,
+        duration_days: 30,
+        disbursed_at: DAY,
+        due_date: 30 * DAY,
+        amount_repaid: 0,
+        interest_accrued: 0,
+        status,
+        purpose: String::new(),
+        collateral_type: CollateralType::None,
+        collateral_value: 0,
+        payment_count: 0,
+        last_payment_date: 0,
+        grace_period_days: 3,
+        late_fee_rate: 0,
+        created_at: DAY,
+        liquidated_at: None,
+        backing_attesters: Vec::new(),
+        approved_at: DAY,
+        committee_votes: Vec::new(),
+        loan_officer: None,
+        accrual_start: DAY,
+        scheduled_repayment: 100_000,
+        restructure_count: 0,
+        restructured_at: None,
+        forbearance_until: 0,
+        forbearance_count: 0,
+        refinanced: false,
+        refinanced_from: None,
+        on_time_streak: 0,
+        rate_step_downs: 0,
+        carried_interest: 0,
+    }
+}
+
+#[test]
+fn loans_are_liquidatable_once_the_grace_period_ends() {
+    let pool = pool();
+    let loan = loan(LoanStatus::Active);
+    assert!(!is_liquidatable(&loan, &pool, 33 * DAY));
+    assert!(is_liquidatable(&loan, &pool, 33 * DAY + 1));
+    assert!(is_liquidatable(
+        &Loan {
+            status: LoanStatus::Disbursed,
+            ..loan.clone()
+        },
+        &pool,
+        40 * DAY
+    ));
+
+    for status in [
+        LoanStatus::Requested,
+        LoanStatus::Approved,
+        LoanStatus::Repaid,
+        LoanStatus::Liquidated,
+    ] {
+        let closed = Loan {
+            status,
+            ..loan.clone()
+        };
+        assert!(!is_liquidatable(&closed, &pool, 40 * DAY));
+    }
+}
+
+#[test]
+fn forbearance_defers_liquidation() {
+    let loan = loan(LoanStatus::Active);
+    let forborne = Loan {
+        forbearance_until: 45 * DAY,
+        ..loan.clone()
+    };
+    assert!(!is_liquidatable(&forborne, &pool(), 40 * DAY));
+
+    // Declared on the pool but not yet picked up by the loan, which moves its due date
+    // from day 30 to day 60
+    let mut disaster = pool();
+    disaster.disaster_declared_at = 20 * DAY;
+    disaster.disaster_forbearance_until = 50 * DAY;
+    assert!(!is_liquidatable(&loan, &disaster, 55 * DAY));
+    assert!(!is_liquidatable(&loan, &disaster, 63 * DAY));
+    assert!(is_liquidatable(&loan, &disaster, 63 * DAY + 1));
+
+    // Loans disbursed after the declaration are not covered
+    let later = Loan {
+        disbursed_at: 25 * DAY,
+        ..loan
+    };
+    assert!(is_liquidatable(&later, &disaster, 40 * DAY));
+}
+
+#[test]
+fn expiry_follows_the_pool_ttls() {
+    let pool = pool();
+    let requested = loan(LoanStatus::Requested);
+    assert!(!Crank::Expire.is_due(&requested, &pool, 3 * DAY));
+    assert!(Crank::Expire.is_due(&requested, &pool, 3 * DAY + 1));
+
+    let approved = loan(LoanStatus::Approved);
+    assert!(Crank::Expire.is_due(&approved, &pool, 2 * DAY + 1));
+    assert!(!Crank::Expire.is_due(&loan(LoanStatus::Active), &pool, 40 * DAY));
+
+    let no_ttl = LendingPool {
+        request_ttl: 0,
+        ..pool
+    };
+    assert!(!Crank::Expire.is_due(&requested, &no_ttl, 400 * DAY));
+}
+
+#[test]
+fn liquidation_settles_backing_attesters_and_the_officer() {
+    let keeper = Pubkey::new_unique();
+    let officer = Pubkey::new_unique();
+    let attester = Pubkey::new_unique();
+    let loan = Loan {
+        loan_officer: Some(officer),
+        backing_attesters: vec![attester],
+        ..loan(LoanStatus::Active)
+    };
+    let instruction = Crank::Liquidate.instruction(&keeper, &loan);
+    assert_eq!(instruction.program_id, micro_lending_client::PROGRAM_ID);
+
+    let accounts: Vec<_> = instruction
+        .accounts
+        .iter()
+        .map(|meta| meta.pubkey)
+        .collect();
+    assert_eq!(accounts[0], keeper);
+    assert!(accounts.contains(&pda::loan(&loan.borrower, &loan.lender_pool).0));
+    assert!(accounts.contains(&pda::loan_officer(&loan.lender_pool, &officer).0));
+    let registry = instruction.accounts.last().unwrap();
+    assert_eq!(registry.pubkey, pda::attester(&attester).0);
+    assert!(registry.is_writable);
+}
+
+// The scan filters loans with memcmp filters at these offsets
+#[test]
+fn scan_filters_match_the_loan_layout() {
+    for status in Crank::ALL.iter().flat_map(|crank| crank.statuses()) {
+        let loan = loan(*status);
+        let mut data = Vec::new();
+        loan.try_serialize(&mut data).unwrap();
+        assert_eq!(data[LOAN_STATUS_OFFSET], *status as u8);
+        assert_eq!(
+            &data[LOAN_LENDER_POOL_OFFSET..LOAN_LENDER_POOL_OFFSET + 32],
+            loan.lender_pool.as_ref()
+        );
+    }
+}
+
+#[test]
+fn retries_raise_the_priority_fee_up_to_the_cap() {
+    let config = KeeperConfig {
+        priority_fee: 1_000,
+        priority_fee_multiplier: 3,
+        max_priority_fee: 20_000,
+        ..KeeperConfig::default()
+    };
+    let fees: Vec<_> = std::iter::successors(Some(config.priority_fee), |fee| {
+        Some(config.next_priority_fee(*fee))
+    })
+    .take(5)
+    .collect();
+    assert_eq!(fees, vec![1_000, 3_000, 9_000, 20_000, 20_000]);
+    assert_eq!(config.next_priority_fee(u64::MAX), 20_000);
+}
+
+#[test]
+fn metrics_are_labelled_per_crank() {
+    let metrics = Metrics::default();
+    metrics.record_scan(7, 1_700_000_000);
+    metrics.record_job_found(Crank::Liquidate);
+    metrics.record_submitted(Crank::Liquidate);
+    metrics.record_failed(Crank::Expire);
+    metrics.record_retry(2_000);
+
+    let rendered = metrics.render();
+    for line in [
+        "keeper_scans_total 1",
+        "keeper_loans_scanned 7",
+        "keeper_retries_total 1",
+        "keeper_priority_fee_micro_lamports 2000",
+        "keeper_last_scan_timestamp_seconds 1700000000",
+        "keeper_jobs_found_total{crank=\"liquidate_loan\"} 1",
+        "keeper_submitted_total{crank=\"liquidate_loan\"} 1",
+        "keeper_failed_total{crank=\"liquidate_loan\"} 0",
+        "keeper_failed_total{crank=\"expire_loan\"} 1",
+    ] {
+        assert!(rendered.lines().any(|rendered| rendered == line), "{line}");
+    }
+}
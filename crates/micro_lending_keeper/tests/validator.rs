@@ -0,0 +1,31 @@
+//! A scan against a running node, e.g. `solana-test-validator --bpf-program
+//! <micro_lending id> target/deploy/micro_lending.so`, at `KEEPER_RPC_URL`.
+
+use micro_lending_keeper::{Keeper, KeeperConfig};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use std::time::Duration;
+
+#[test]
+#[ignore = "needs a running solana-test-validator; run with --ignored"]
+fn scan_runs_against_a_validator() {
+    let url =
+        std::env::var("KEEPER_RPC_URL").unwrap_or_else(|_| "http://127.0.0.1:8899".to_string());
+    let rpc = RpcClient::new_with_commitment(url, CommitmentConfig::confirmed());
+    let keypair = Keypair::new();
+    let airdrop = rpc
+        .request_airdrop(&keypair.pubkey(), LAMPORTS_PER_SOL)
+        .expect("airdrop to the keeper");
+    while !rpc.confirm_transaction(&airdrop).unwrap() {
+        std::thread::sleep(Duration::from_millis(200));
+    }
+    let keeper = Keeper::new(rpc, keypair, KeeperConfig::default());
+
+    // Every loan found due must be cranked
+    let report = keeper.run_once().expect("scan the program's loans");
+    assert_eq!(report.failed, 0);
+    assert_eq!(report.submitted, report.jobs_found);
+    assert!(keeper.metrics.render().contains("keeper_scans_total 1"));
+}
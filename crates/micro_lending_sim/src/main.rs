@@ -0,0 +1,110 @@
+use anyhow::{ensure, Result};
+use clap::{Parser, ValueEnum};
+use micro_lending_sim::report;
+use micro_lending_sim::scenario::{ParamGrid, Scenario};
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FormatArg {
+    Json,
+    Csv,
+}
+
+#[derive(Parser)]
+#[command(
+    name = "microlend-sim",
+    version,
+    about = "Simulate lender returns, losses and liquidity of a micro_lending pool"
+)]
+struct Cli {
+    /// Scenario JSON; the built-in scenario when omitted (see --print-scenario)
+    #[arg(long, short = 's')]
+    scenario: Option<PathBuf>,
+
+    /// Print the scenario as JSON, as a starting point for a region's own, and exit
+    #[arg(long)]
+    print_scenario: bool,
+
+    /// Monte-Carlo runs per parameter combination
+    #[arg(long, short = 'n', default_value_t = 200)]
+    runs: usize,
+
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+
+    /// Pool base interest rates to sweep, in basis points
+    #[arg(long, value_delimiter = ',', default_value = "1000")]
+    base_rate_bps: Vec<u16>,
+
+    /// Annual late fee rates to sweep, in basis points
+    #[arg(long, value_delimiter = ',', default_value = "500")]
+    late_fee_rate_bps: Vec<u16>,
+
+    /// Grace periods to sweep, in days
+    #[arg(long, value_delimiter = ',', default_value = "7")]
+    grace_days: Vec<u8>,
+
+    /// Platform fees to sweep, in basis points of each payment
+    #[arg(long, value_delimiter = ',', default_value = "0")]
+    platform_fee_bps: Vec<u16>,
+
+    /// Interest rate step-downs to sweep, in basis points per on-time streak
+    #[arg(long, value_delimiter = ',', default_value = "0")]
+    step_down_bps: Vec<u16>,
+
+    /// Early payoff rebates to sweep, in basis points of the accrued interest
+    #[arg(long, value_delimiter = ',', default_value = "0")]
+    rebate_bps: Vec<u16>,
+
+    /// On-time weekly installments per rate step-down
+    #[arg(long, default_value_t = 4)]
+    streak_length: u8,
+
+    /// Floor of the rate step-downs, in basis points
+    #[arg(long, default_value_t = 0)]
+    min_rate_bps: u16,
+
+    #[arg(long, value_enum, default_value = "json")]
+    format: FormatArg,
+
+    /// Write to this file instead of stdout
+    #[arg(long, short = 'o')]
+    output: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let scenario = match &cli.scenario {
+        Some(path) => Scenario::load(path)?,
+        None => Scenario::default(),
+    };
+    if cli.print_scenario {
+        serde_json::to_writer_pretty(io::stdout().lock(), &scenario)?;
+        println!();
+        return Ok(());
+    }
+    ensure!(cli.runs > 0, "--runs must be positive");
+    let grid = ParamGrid {
+        base_interest_rates: cli.base_rate_bps,
+        late_fee_rates: cli.late_fee_rate_bps,
+        grace_periods_days: cli.grace_days,
+        platform_fees: cli.platform_fee_bps,
+        rate_step_downs_bps: cli.step_down_bps,
+        early_payoff_rebates_bps: cli.rebate_bps,
+        streak_length: cli.streak_length,
+        min_interest_rate: cli.min_rate_bps,
+    };
+    grid.validate()?;
+
+    let summaries = micro_lending_sim::run_sweep(&scenario, &grid, cli.runs, cli.seed);
+    let writer: Box<dyn io::Write> = match cli.output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    };
+    match cli.format {
+        FormatArg::Json => report::write_json(writer, &summaries),
+        FormatArg::Csv => report::write_csv(writer, &summaries),
+    }
+}
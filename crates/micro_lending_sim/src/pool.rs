@@ -0,0 +1,484 @@
+//! Off-chain mirror of the pool instructions. Share pricing, interest, late fees and quotes
+//! come from the program's own functions, and each instruction's bookkeeping is applied to
+//! the same state structs the program stores. Token accounts are not modelled: the pool's
+//! `available_liquidity` stands in for its token balance.
+
+use crate::scenario::{PoolParams, Scenario};
+use anchor_lang::prelude::Pubkey;
+use micro_lending::{
+    calculate_interest_rate, calculate_loan_payment, calculate_payoff_quote,
+    calculate_shares_to_mint, calculate_withdrawal_quote, days_between, expected_installment,
+    get_unclaimed_interest, is_in_forbearance, is_loan_overdue, reserve_loan_liquidity,
+    settle_payment, step_down_interest_rate, AutoApprovalRules, BorrowerPolicy, CollateralType,
+    LenderDeposit, LendingLadder, LendingPool, Loan, LoanStatus, Platform, RepaymentIncentives,
+    ALL_COLLATERAL_TYPES,
+};
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+// interest_per_share is scaled by 1e9
+const INTEREST_SCALE: u128 = 1_000_000_000;
+
+// What a borrower's payments went to
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Payment {
+    pub amount: u64,
+    pub platform_fee: u64,
+    pub interest: u64,
+    pub late_fee: u64,
+    pub principal: u64,
+    pub early_payoff_rebate: u64,
+    pub rate_stepped_down: bool,
+}
+
+pub struct SimPool {
+    pub platform: Platform,
+    pub pool: LendingPool,
+    pub lenders: Vec<LenderDeposit>,
+    pub treasury: u64, // platform fees collected
+    grace_period_days: u8,
+    late_fee_rate: u16,
+}
+
+fn platform(params: &PoolParams, scenario: &Scenario, created_at: i64) -> Platform {
+    Platform {
+        authority: Pubkey::default(),
+        treasury: Pubkey::default(),
+        treasury_bump: 0,
+        platform_fee: params.platform_fee,
+        max_loan_amount: scenario.max_loan_amount,
+        min_loan_amount: scenario.min_loan_amount,
+        total_loans_issued: 0,
+        total_volume: 0,
+        total_defaults: 0,
+        is_active: true,
+        created_at,
+        score_half_life_days: 0,
+        inactivity_threshold_days: 0,
+        verifiers: Vec::new(),
+        lending_ladder: LendingLadder {
+            first_loan_cap: 0,
+            step_up_bps: 10000,
+            step_down_bps: 10000,
+        },
+    }
+}
+
+fn lending_pool(params: &PoolParams, scenario: &Scenario, created_at: i64) -> LendingPool {
+    LendingPool {
+        authority: Pubkey::default(),
+        mint: Pubkey::default(),
+        token_account: Pubkey::default(),
+        name: "simulation".to_string(),
+        base_interest_rate: params.base_interest_rate,
+        max_loan_duration: scenario.durations_days.iter().copied().max().unwrap_or(0) as i64,
+        total_deposited: 0,
+        total_borrowed: 0,
+        total_shares: 0,
+        available_liquidity: 0,
+        active_loans: 0,
+        total_interest_earned: 0,
+        total_interest_distributed: 0,
+        interest_per_share: 0,
+        is_active: true,
+        created_at,
+        borrower_policy: BorrowerPolicy {
+            min_credit_score: 0,
+            required_verifications: 0,
+            min_attestations: 0,
+            allowed_collateral_types: ALL_COLLATERAL_TYPES,
+            max_loan_per_borrower: 0,
+            allowed_purposes: Vec::new(),
+        },
+        total_reserved: 0,
+        auto_approval: AutoApprovalRules {
+            enabled: false,
+            max_utilization_bps: 0,
+            score_bands: Vec::new(),
+        },
+        request_ttl: 0,
+        approval_ttl: 0,
+        committee: None,
+        disaster_declared_at: 0,
+        disaster_forbearance_until: 0,
+        repayment_incentives: RepaymentIncentives {
+            streak_length: params.streak_length,
+            rate_step_down_bps: params.rate_step_down_bps,
+            min_interest_rate: params.min_interest_rate,
+            early_payoff_rebate_bps: params.early_payoff_rebate_bps,
+        },
+    }
+}
+
+fn lender_deposit() -> LenderDeposit {
+    LenderDeposit {
+        lender: Pubkey::default(),
+        pool: Pubkey::default(),
+        amount_deposited: 0,
+        shares: 0,
+        interest_debt: 0,
+        interest_earned: 0,
+        interest_claimed: 0,
+        deposited_at: 0,
+        last_claim: 0,
+    }
+}
+
+impl SimPool {
+    pub fn new(params: &PoolParams, scenario: &Scenario, created_at: i64) -> Self {
+        SimPool {
+            platform: platform(params, scenario, created_at),
+            pool: lending_pool(params, scenario, created_at),
+            lenders: (0..scenario.lenders).map(|_| lender_deposit()).collect(),
+            treasury: 0,
+            grace_period_days: params.grace_period_days,
+            late_fee_rate: params.late_fee_rate,
+        }
+    }
+
+    // deposit_to_pool
+    pub fn deposit(&mut self, lender: usize, amount: u64, now: i64) -> anchor_lang::Result<u64> {
+        let shares_to_mint = calculate_shares_to_mint(&self.pool, amount)?;
+        let pool = &mut self.pool;
+        let lender_deposit = &mut self.lenders[lender];
+        if lender_deposit.amount_deposited == 0 {
+            lender_deposit.deposited_at = now;
+        }
+        lender_deposit.amount_deposited += amount;
+        lender_deposit.shares += shares_to_mint;
+        lender_deposit.interest_debt +=
+            (shares_to_mint as u128 * pool.interest_per_share as u128 / INTEREST_SCALE) as u64;
+        pool.total_deposited += amount;
+        pool.total_shares += shares_to_mint;
+        pool.available_liquidity += amount;
+        Ok(shares_to_mint)
+    }
+
+    // Tokens withdrawing `shares` would pay out, with the lender's unclaimed interest
+    pub fn withdrawal_amount(&self, lender: usize, shares: u64) -> u64 {
+        calculate_withdrawal_quote(&self.lenders[lender], &self.pool, shares).total_amount
+    }
+
+    // Everything a lender could take out if the pool had the liquidity
+    pub fn lender_value(&self, lender: usize) -> u64 {
+        self.withdrawal_amount(lender, self.lenders[lender].shares)
+    }
+
+    // withdraw_from_pool; None when the pool lacks the liquidity to pay out
+    pub fn withdraw(&mut self, lender: usize, shares: u64) -> Option<u64> {
+        let lender_deposit = &self.lenders[lender];
+        if shares == 0 || shares > lender_deposit.shares {
+            return None;
+        }
+        let quote = calculate_withdrawal_quote(lender_deposit, &self.pool, shares);
+        if !quote.sufficient_liquidity {
+            return None;
+        }
+        let unclaimed_interest = get_unclaimed_interest(lender_deposit, &self.pool);
+
+        let pool = &mut self.pool;
+        let lender_deposit = &mut self.lenders[lender];
+        lender_deposit.interest_claimed += unclaimed_interest;
+        lender_deposit.interest_earned += unclaimed_interest;
+        pool.total_interest_distributed += unclaimed_interest;
+
+        lender_deposit.shares -= shares;
+        lender_deposit.interest_debt = (lender_deposit.shares as u128
+            * pool.interest_per_share as u128
+            / INTEREST_SCALE) as u64;
+        lender_deposit.amount_deposited = lender_deposit
+            .amount_deposited
+            .saturating_sub(quote.principal_amount);
+        pool.total_shares -= shares;
+        pool.total_deposited = pool.total_deposited.saturating_sub(quote.principal_amount);
+        pool.available_liquidity -= quote.total_amount;
+        Some(quote.total_amount)
+    }
+
+    // A loan as request_loan records it, with the grace period and late fee under test
+    pub fn new_loan(
+        &self,
+        credit_score: u16,
+        amount: u64,
+        duration_days: u32,
+        now: i64,
+    ) -> anchor_lang::Result<Loan> {
+        let interest_rate =
+            calculate_interest_rate(credit_score, self.pool.base_interest_rate, duration_days)?;
+        Ok(Loan {
+            borrower: Pubkey::default(),
+            lender_pool: Pubkey::default(),
+            amount,
+            interest_rate,
+            duration_days,
+            disbursed_at: 0,
+            due_date: 0,
+            amount_repaid: 0,
+            interest_accrued: 0,
+            status: LoanStatus::Requested,
+            purpose: String::new(),
+            collateral_type: CollateralType::None,
+            collateral_value: 0,
+            payment_count: 0,
+            last_payment_date: 0,
+            grace_period_days: self.grace_period_days,
+            late_fee_rate: self.late_fee_rate,
+            created_at: now,
+            liquidated_at: None,
+            backing_attesters: Vec::new(),
+            approved_at: 0,
+            committee_votes: Vec::new(),
+            loan_officer: None,
+            accrual_start: 0,
+            scheduled_repayment: 0,
+            restructure_count: 0,
+            restructured_at: None,
+            forbearance_until: 0,
+            forbearance_count: 0,
+            refinanced: false,
+            refinanced_from: None,
+            on_time_streak: 0,
+            rate_step_downs: 0,
+            carried_interest: 0,
+        })
+    }
+
+    // approve_loan's reservation followed by disburse_loan
+    pub fn disburse(&mut self, loan: &mut Loan, now: i64) -> anchor_lang::Result<()> {
+        reserve_loan_liquidity(&mut self.pool, loan.amount)?;
+        loan.approved_at = now;
+        loan.status = LoanStatus::Disbursed;
+        loan.disbursed_at = now;
+        loan.due_date = now + loan.duration_days as i64 * SECONDS_PER_DAY;
+        loan.accrual_start = now;
+        loan.scheduled_repayment =
+            calculate_loan_payment(loan.amount, loan.interest_rate, loan.duration_days)?;
+
+        self.pool.total_reserved -= loan.amount;
+        self.pool.total_borrowed += loan.amount;
+        self.platform.total_loans_issued += 1;
+        self.platform.total_volume += loan.amount;
+        Ok(())
+    }
+
+    // make_payment without a loan officer; None where the program would reject the payment
+    pub fn make_payment(
+        &mut self,
+        loan: &mut Loan,
+        payment_amount: u64,
+        now: i64,
+    ) -> Option<Payment> {
+        let days_elapsed = days_between(loan.accrual_start, now);
+        let quote = calculate_payoff_quote(&self.platform, &self.pool, loan, now).ok()?;
+        let settlement = settle_payment(&quote, self.platform.platform_fee, payment_amount).ok()?;
+        let lender_charges = settlement.interest_paid + settlement.late_fee;
+        let principal = settlement.principal_paid;
+
+        loan.interest_accrued = settlement.interest_paid;
+        loan.amount_repaid += principal;
+        loan.payment_count += 1;
+        loan.last_payment_date = now;
+        loan.accrual_start += days_elapsed as i64 * 86400;
+
+        // On-time streaks and the rate step-downs they earn
+        let mut rate_stepped_down = false;
+        if is_loan_overdue(loan.due_date, now, loan.grace_period_days) {
+            loan.on_time_streak = 0;
+        } else if payment_amount >= expected_installment(loan) {
+            loan.on_time_streak = loan.on_time_streak.saturating_add(1);
+            rate_stepped_down =
+                step_down_interest_rate(loan, &self.pool.repayment_incentives).is_some();
+        }
+        if loan.amount_repaid >= loan.amount {
+            loan.status = LoanStatus::Repaid;
+            self.pool.active_loans = self.pool.active_loans.saturating_sub(1);
+        } else {
+            loan.status = LoanStatus::Active;
+        }
+
+        let pool = &mut self.pool;
+        pool.available_liquidity += payment_amount - settlement.platform_fee;
+        pool.total_borrowed -= principal;
+        if lender_charges > 0 && pool.total_shares > 0 {
+            pool.interest_per_share +=
+                (lender_charges as u128 * INTEREST_SCALE / pool.total_shares as u128) as u64;
+        }
+        pool.total_interest_earned += lender_charges;
+        self.treasury += settlement.platform_fee;
+
+        Some(Payment {
+            amount: payment_amount,
+            platform_fee: settlement.platform_fee,
+            interest: settlement.interest_paid,
+            late_fee: settlement.late_fee,
+            principal,
+            early_payoff_rebate: settlement.early_payoff_rebate,
+            rate_stepped_down,
+        })
+    }
+
+    // Pays the weekly installment make_payment counts toward the on-time streak, or the
+    // payoff when that is less
+    pub fn pay_installment(&mut self, loan: &mut Loan, now: i64) -> Payment {
+        calculate_payoff_quote(&self.platform, &self.pool, loan, now)
+            .ok()
+            .and_then(|quote| {
+                let payoff = quote.total_due + quote.platform_fee;
+                self.make_payment(loan, expected_installment(loan).min(payoff), now)
+            })
+            .unwrap_or_default()
+    }
+
+    // Pays off the loan in one payment of its payoff quote
+    pub fn pay_off(&mut self, loan: &mut Loan, now: i64) -> Payment {
+        calculate_payoff_quote(&self.platform, &self.pool, loan, now)
+            .ok()
+            .and_then(|quote| self.make_payment(loan, quote.total_due + quote.platform_fee, now))
+            .unwrap_or_default()
+    }
+
+    // Whether a keeper that waits `delay_days` past the grace period would liquidate
+    pub fn is_liquidatable(loan: &Loan, now: i64, delay_days: u32) -> bool {
+        !is_in_forbearance(loan, now)
+            && is_loan_overdue(
+                loan.due_date,
+                now - delay_days as i64 * SECONDS_PER_DAY,
+                loan.grace_period_days,
+            )
+    }
+
+    // liquidate_loan; returns the principal written off
+    pub fn liquidate(&mut self, loan: &mut Loan, now: i64) -> u64 {
+        let outstanding = loan.amount - loan.amount_repaid;
+        loan.status = LoanStatus::Liquidated;
+        loan.liquidated_at = Some(now);
+        self.pool.active_loans = self.pool.active_loans.saturating_sub(1);
+        self.pool.total_borrowed = self.pool.total_borrowed.saturating_sub(outstanding);
+        self.platform.total_defaults += 1;
+        outstanding
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOAN: u64 = 100_000_000;
+
+    fn params(rate_step_down_bps: u16, early_payoff_rebate_bps: u16) -> PoolParams {
+        PoolParams {
+            base_interest_rate: 1000,
+            late_fee_rate: 500,
+            grace_period_days: 3,
+            platform_fee: 100,
+            streak_length: 2,
+            rate_step_down_bps,
+            min_interest_rate: 0,
+            early_payoff_rebate_bps,
+        }
+    }
+
+    // A pool of two lenders holding 1_000_000_000 and a 60-day loan disbursed at 0
+    fn funded(params: &PoolParams) -> (SimPool, Loan) {
+        let mut sim = SimPool::new(params, &Scenario::default(), 0);
+        sim.lenders.truncate(2);
+        sim.deposit(0, 600_000_000, 0).unwrap();
+        sim.deposit(1, 400_000_000, 0).unwrap();
+        let mut loan = sim.new_loan(700, LOAN, 60, 0).unwrap();
+        sim.disburse(&mut loan, 0).unwrap();
+        (sim, loan)
+    }
+
+    fn week(n: i64) -> i64 {
+        n * 7 * SECONDS_PER_DAY
+    }
+
+    #[test]
+    fn withdrawal_returns_the_deposit_and_waits_for_liquidity() {
+        let mut sim = SimPool::new(&params(0, 0), &Scenario::default(), 0);
+        let shares = sim.deposit(0, 1_000_000, 0).unwrap();
+        assert_eq!(sim.withdraw(0, shares), Some(1_000_000));
+        assert_eq!(sim.pool.available_liquidity, 0);
+
+        // The whole deposit is lent out
+        let shares = sim.deposit(0, LOAN, 0).unwrap();
+        let mut loan = sim.new_loan(700, LOAN, 60, 0).unwrap();
+        sim.disburse(&mut loan, 0).unwrap();
+        assert_eq!(sim.withdraw(0, shares), None);
+        assert_eq!(sim.lenders[0].shares, shares);
+        assert_eq!(sim.withdraw(0, shares + 1), None);
+    }
+
+    #[test]
+    fn payoff_clears_the_loan_and_credits_lenders_by_share() {
+        let (mut sim, mut loan) = funded(&params(0, 0));
+        let paid = sim.pay_off(&mut loan, 70 * SECONDS_PER_DAY);
+
+        assert!(loan.status == LoanStatus::Repaid);
+        assert_eq!(paid.principal, LOAN);
+        assert!(paid.interest > 0 && paid.late_fee > 0);
+        assert_eq!(sim.treasury, paid.platform_fee);
+        assert_eq!(sim.pool.total_borrowed, 0);
+        assert_eq!(
+            sim.pool.available_liquidity,
+            1_000_000_000 + paid.interest + paid.late_fee
+        );
+        // Lenders earn in proportion to their shares, less rounding
+        let earned = |lender| sim.lender_value(lender) - sim.lenders[lender].amount_deposited;
+        assert!(earned(0) * 2 / 3 >= earned(1) - 1);
+        assert!(earned(0) + earned(1) <= paid.interest + paid.late_fee);
+    }
+
+    #[test]
+    fn on_time_installments_step_the_rate_down_and_a_late_one_resets_the_streak() {
+        let (mut sim, mut loan) = funded(&params(100, 0));
+        let rate = loan.interest_rate;
+
+        assert!(!sim.pay_installment(&mut loan, week(1)).rate_stepped_down);
+        assert!(sim.pay_installment(&mut loan, week(2)).rate_stepped_down);
+        assert_eq!(loan.interest_rate, rate - 100);
+        assert_eq!((loan.on_time_streak, loan.rate_step_downs), (2, 1));
+
+        // An installment below the schedule neither counts nor breaks the streak
+        let short = expected_installment(&loan) / 2;
+        sim.make_payment(&mut loan, short, week(3)).unwrap();
+        assert_eq!(loan.on_time_streak, 2);
+
+        let late = loan.due_date + 4 * SECONDS_PER_DAY;
+        assert!(!sim.pay_installment(&mut loan, late).rate_stepped_down);
+        assert_eq!(loan.on_time_streak, 0);
+        assert_eq!(loan.interest_rate, rate - 100);
+    }
+
+    #[test]
+    fn only_an_early_payoff_earns_the_rebate() {
+        let (mut sim, mut loan) = funded(&params(0, 5000));
+        let installment = sim.pay_installment(&mut loan, week(1));
+        assert_eq!(installment.early_payoff_rebate, 0);
+
+        let (mut plain, mut plain_loan) = funded(&params(0, 0));
+        plain.pay_installment(&mut plain_loan, week(1));
+        let full = plain.pay_off(&mut plain_loan, week(3));
+        let rebated = sim.pay_off(&mut loan, week(3));
+        assert!(loan.status == LoanStatus::Repaid);
+        assert!(rebated.early_payoff_rebate > 0);
+        assert_eq!(
+            rebated.interest + rebated.early_payoff_rebate,
+            full.interest
+        );
+    }
+
+    #[test]
+    fn liquidation_writes_off_the_unpaid_principal() {
+        let (mut sim, mut loan) = funded(&params(0, 0));
+        let paid = sim.pay_installment(&mut loan, week(1));
+        let past_grace = loan.due_date + 4 * SECONDS_PER_DAY;
+
+        assert!(!SimPool::is_liquidatable(&loan, past_grace, 30));
+        assert!(SimPool::is_liquidatable(&loan, past_grace, 0));
+        assert_eq!(sim.liquidate(&mut loan, past_grace), LOAN - paid.principal);
+        assert!(loan.status == LoanStatus::Liquidated);
+        assert_eq!(sim.pool.total_borrowed, 0);
+        assert_eq!(sim.platform.total_defaults, 1);
+    }
+}
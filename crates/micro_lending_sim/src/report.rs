@@ -0,0 +1,128 @@
+//! Summaries of a parameter set's runs: lender APY and loss-rate percentiles, and how often
+//! and how badly lenders were kept waiting for liquidity.
+
+use crate::scenario::PoolParams;
+use crate::simulation::RunOutcome;
+use anyhow::Result;
+use serde::Serialize;
+use std::io::Write;
+
+// Rates are basis points; `_mean` fields average over the runs
+#[derive(Debug, Clone, Serialize)]
+pub struct Summary {
+    pub base_interest_rate: u16,
+    pub late_fee_rate: u16,
+    pub grace_period_days: u8,
+    pub platform_fee: u16,
+    pub streak_length: u8,
+    pub rate_step_down_bps: u16,
+    pub min_interest_rate: u16,
+    pub early_payoff_rebate_bps: u16,
+    pub runs: usize,
+    pub lender_apy_mean_bps: i64,
+    pub lender_apy_p5_bps: i64,
+    pub lender_apy_p50_bps: i64,
+    pub lender_apy_p95_bps: i64,
+    pub loss_rate_mean_bps: u64,
+    pub loss_rate_p95_bps: u64,
+    // Share of runs in which a withdrawal had to wait for liquidity
+    pub shortfall_probability_bps: u64,
+    pub shortfall_days_mean: f64,
+    pub peak_shortfall_mean: u64,
+    pub longest_wait_days: u32,
+    pub loans_funded_mean: f64,
+    pub loans_unfunded_mean: f64,
+    pub loans_declined_mean: f64,
+    pub loans_repaid_late_mean: f64,
+    pub loans_liquidated_mean: f64,
+    pub utilization_mean_bps: u64,
+    pub interest_paid_mean: u64,
+    pub late_fees_paid_mean: u64,
+    pub platform_fees_mean: u64,
+    pub early_payoff_rebates_mean: u64,
+    pub rate_step_downs_mean: f64,
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (count, total) = values.fold((0usize, 0.0), |(count, total), value| {
+        (count + 1, total + value)
+    });
+    if count == 0 {
+        0.0
+    } else {
+        total / count as f64
+    }
+}
+
+// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], percent: usize) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn sorted(values: impl Iterator<Item = f64>) -> Vec<f64> {
+    let mut values: Vec<f64> = values.collect();
+    values.sort_by(f64::total_cmp);
+    values
+}
+
+pub fn summarize(params: &PoolParams, runs: &[RunOutcome]) -> Summary {
+    let apy = sorted(runs.iter().map(|run| run.lender_apy_bps));
+    let loss = sorted(runs.iter().map(RunOutcome::loss_rate_bps));
+    let average = |field: fn(&RunOutcome) -> f64| mean(runs.iter().map(field));
+    let runs_short = runs.iter().filter(|run| run.shortfall_days > 0).count();
+
+    Summary {
+        base_interest_rate: params.base_interest_rate,
+        late_fee_rate: params.late_fee_rate,
+        grace_period_days: params.grace_period_days,
+        platform_fee: params.platform_fee,
+        streak_length: params.streak_length,
+        rate_step_down_bps: params.rate_step_down_bps,
+        min_interest_rate: params.min_interest_rate,
+        early_payoff_rebate_bps: params.early_payoff_rebate_bps,
+        runs: runs.len(),
+        lender_apy_mean_bps: mean(apy.iter().copied()).round() as i64,
+        lender_apy_p5_bps: percentile(&apy, 5).round() as i64,
+        lender_apy_p50_bps: percentile(&apy, 50).round() as i64,
+        lender_apy_p95_bps: percentile(&apy, 95).round() as i64,
+        loss_rate_mean_bps: mean(loss.iter().copied()).round() as u64,
+        loss_rate_p95_bps: percentile(&loss, 95).round() as u64,
+        shortfall_probability_bps: (runs_short * 10000).checked_div(runs.len()).unwrap_or(0) as u64,
+        shortfall_days_mean: average(|run| run.shortfall_days as f64),
+        peak_shortfall_mean: average(|run| run.peak_shortfall as f64).round() as u64,
+        longest_wait_days: runs
+            .iter()
+            .map(|run| run.longest_wait_days)
+            .max()
+            .unwrap_or(0),
+        loans_funded_mean: average(|run| run.loans_funded as f64),
+        loans_unfunded_mean: average(|run| run.loans_unfunded as f64),
+        loans_declined_mean: average(|run| run.loans_declined as f64),
+        loans_repaid_late_mean: average(|run| run.loans_repaid_late as f64),
+        loans_liquidated_mean: average(|run| run.loans_liquidated as f64),
+        utilization_mean_bps: average(|run| run.mean_utilization_bps).round() as u64,
+        interest_paid_mean: average(|run| run.interest_paid as f64).round() as u64,
+        late_fees_paid_mean: average(|run| run.late_fees_paid as f64).round() as u64,
+        platform_fees_mean: average(|run| run.platform_fees as f64).round() as u64,
+        early_payoff_rebates_mean: average(|run| run.early_payoff_rebates as f64).round() as u64,
+        rate_step_downs_mean: average(|run| run.rate_step_downs as f64),
+    }
+}
+
+pub fn write_json(writer: impl Write, report: &[Summary]) -> Result<()> {
+    serde_json::to_writer_pretty(writer, report)?;
+    Ok(())
+}
+
+pub fn write_csv(writer: impl Write, report: &[Summary]) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for summary in report {
+        writer.serialize(summary)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,277 @@
+//! Scenario inputs: the borrower and lender behaviour of a region, and the pool parameters
+//! to sweep over it.
+
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+// Borrowers whose credit scores fall in [min_score, max_score]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreditBand {
+    pub name: String,
+    pub min_score: u16,
+    pub max_score: u16,
+    // Relative share of loan requests from this band
+    pub weight: f64,
+    // Probability a funded loan is never repaid
+    pub default_probability: f64,
+    pub delay: RepaymentDelay,
+}
+
+// Repayment timing of borrowers who do repay. A late borrower pays off an exponentially
+// distributed number of days after the due date, rounded up. An early borrower pays off on
+// a uniformly drawn day before it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepaymentDelay {
+    pub late_probability: f64,
+    pub mean_days_late: f64,
+    #[serde(default)]
+    pub early_payoff_probability: f64,
+}
+
+// Daily lender behaviour, drawn independently for each lender
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LenderFlows {
+    pub deposit_probability: f64,
+    pub min_deposit: u64,
+    pub max_deposit: u64,
+    pub withdraw_probability: f64,
+    // Basis points of the lender's shares redeemed by a withdrawal
+    pub withdraw_fraction_bps: u16,
+}
+
+impl Default for LenderFlows {
+    fn default() -> Self {
+        LenderFlows {
+            deposit_probability: 0.01,
+            min_deposit: 100_000_000,
+            max_deposit: 1_000_000_000,
+            withdraw_probability: 0.01,
+            withdraw_fraction_bps: 5000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Scenario {
+    // Days of lending; outstanding loans then run off to repayment or liquidation
+    pub days: u32,
+    pub lenders: usize,
+    pub initial_deposit: u64, // per lender
+    // Platform loan limits, which set the credit score a loan amount requires
+    pub min_loan_amount: u64,
+    pub max_loan_amount: u64,
+    // Mean loan requests per day, Poisson distributed
+    pub loans_per_day: f64,
+    pub min_loan: u64,
+    pub max_loan: u64,
+    pub durations_days: Vec<u32>,
+    // Days past the grace period before a keeper liquidates an unpaid loan
+    pub liquidation_delay_days: u32,
+    pub bands: Vec<CreditBand>,
+    pub flows: LenderFlows,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        let band =
+            |name: &str, min_score, max_score, weight, default_probability, late| CreditBand {
+                name: name.to_string(),
+                min_score,
+                max_score,
+                weight,
+                default_probability,
+                delay: RepaymentDelay {
+                    late_probability: late,
+                    mean_days_late: 10.0,
+                    early_payoff_probability: 0.15,
+                },
+            };
+        Scenario {
+            days: 365,
+            lenders: 10,
+            initial_deposit: 5_000_000_000,
+            min_loan_amount: 1_000_000,
+            max_loan_amount: 1_000_000_000,
+            loans_per_day: 4.0,
+            min_loan: 10_000_000,
+            max_loan: 200_000_000,
+            durations_days: vec![30, 60, 90, 180],
+            liquidation_delay_days: 30,
+            bands: vec![
+                band("thin-file", 300, 499, 0.35, 0.12, 0.30),
+                band("fair", 500, 649, 0.35, 0.06, 0.20),
+                band("good", 650, 749, 0.20, 0.03, 0.10),
+                band("excellent", 750, 1000, 0.10, 0.01, 0.05),
+            ],
+            flows: LenderFlows::default(),
+        }
+    }
+}
+
+fn is_probability(p: f64) -> bool {
+    (0.0..=1.0).contains(&p)
+}
+
+impl Scenario {
+    pub fn load(path: &Path) -> Result<Self> {
+        let scenario: Scenario = serde_json::from_slice(&fs::read(path)?)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        ensure!(self.days > 0, "days must be positive");
+        ensure!(self.lenders > 0, "at least one lender is required");
+        ensure!(self.initial_deposit > 0, "initial_deposit must be positive");
+        ensure!(
+            self.min_loan > 0 && self.min_loan <= self.max_loan,
+            "loan amounts must satisfy 0 < min_loan <= max_loan"
+        );
+        ensure!(self.max_loan_amount > 0, "max_loan_amount must be positive");
+        ensure!(
+            self.loans_per_day >= 0.0 && self.loans_per_day <= 500.0,
+            "loans_per_day must be between 0 and 500"
+        );
+        ensure!(
+            !self.durations_days.is_empty() && !self.durations_days.contains(&0),
+            "durations_days must list positive durations"
+        );
+        ensure!(
+            !self.bands.is_empty(),
+            "at least one credit band is required"
+        );
+        for band in &self.bands {
+            ensure!(
+                band.min_score <= band.max_score,
+                "band {}: min_score is above max_score",
+                band.name
+            );
+            ensure!(
+                band.weight > 0.0,
+                "band {}: weight must be positive",
+                band.name
+            );
+            ensure!(
+                is_probability(band.default_probability)
+                    && is_probability(band.delay.late_probability)
+                    && is_probability(band.delay.early_payoff_probability)
+                    && band.delay.late_probability + band.delay.early_payoff_probability <= 1.0,
+                "band {}: probabilities must be between 0 and 1, and late and early payoff together at most 1",
+                band.name
+            );
+            ensure!(
+                band.delay.mean_days_late >= 0.0,
+                "band {}: mean_days_late must not be negative",
+                band.name
+            );
+        }
+        let flows = &self.flows;
+        ensure!(
+            is_probability(flows.deposit_probability) && is_probability(flows.withdraw_probability),
+            "lender flow probabilities must be between 0 and 1"
+        );
+        ensure!(
+            flows.min_deposit > 0 && flows.min_deposit <= flows.max_deposit,
+            "deposit amounts must satisfy 0 < min_deposit <= max_deposit"
+        );
+        ensure!(
+            flows.withdraw_fraction_bps > 0 && flows.withdraw_fraction_bps <= 10000,
+            "withdraw_fraction_bps must be between 1 and 10000"
+        );
+        Ok(())
+    }
+}
+
+// Pool and platform settings under test
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct PoolParams {
+    pub base_interest_rate: u16, // basis points
+    pub late_fee_rate: u16,      // basis points
+    pub grace_period_days: u8,
+    pub platform_fee: u16, // basis points
+    // The pool's RepaymentIncentives
+    pub streak_length: u8,
+    pub rate_step_down_bps: u16,
+    pub min_interest_rate: u16,
+    pub early_payoff_rebate_bps: u16,
+}
+
+// Values to sweep for each parameter; every combination is simulated
+#[derive(Debug, Clone)]
+pub struct ParamGrid {
+    pub base_interest_rates: Vec<u16>,
+    pub late_fee_rates: Vec<u16>,
+    pub grace_periods_days: Vec<u8>,
+    pub platform_fees: Vec<u16>,
+    pub rate_step_downs_bps: Vec<u16>,
+    pub early_payoff_rebates_bps: Vec<u16>,
+    // Held fixed across the sweep
+    pub streak_length: u8,
+    pub min_interest_rate: u16,
+}
+
+impl ParamGrid {
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            !self.base_interest_rates.is_empty()
+                && !self.late_fee_rates.is_empty()
+                && !self.grace_periods_days.is_empty()
+                && !self.platform_fees.is_empty()
+                && !self.rate_step_downs_bps.is_empty()
+                && !self.early_payoff_rebates_bps.is_empty(),
+            "every parameter needs at least one value"
+        );
+        // initialize_platform's limit
+        ensure!(
+            self.platform_fees.iter().all(|&fee| fee <= 1000),
+            "platform fees are capped at 1000 bps"
+        );
+        // set_repayment_incentives' limits
+        ensure!(
+            self.rate_step_downs_bps.iter().all(|&step| step <= 1000),
+            "rate step-downs are capped at 1000 bps"
+        );
+        ensure!(
+            self.min_interest_rate <= 5000,
+            "the minimum interest rate is capped at 5000 bps"
+        );
+        ensure!(
+            self.early_payoff_rebates_bps
+                .iter()
+                .all(|&rebate| rebate <= 10000),
+            "early payoff rebates are capped at 10000 bps"
+        );
+        Ok(())
+    }
+
+    pub fn combinations(&self) -> Vec<PoolParams> {
+        let mut combinations = Vec::new();
+        for &base_interest_rate in &self.base_interest_rates {
+            for &late_fee_rate in &self.late_fee_rates {
+                for &grace_period_days in &self.grace_periods_days {
+                    for &platform_fee in &self.platform_fees {
+                        for &rate_step_down_bps in &self.rate_step_downs_bps {
+                            for &early_payoff_rebate_bps in &self.early_payoff_rebates_bps {
+                                combinations.push(PoolParams {
+                                    base_interest_rate,
+                                    late_fee_rate,
+                                    grace_period_days,
+                                    platform_fee,
+                                    streak_length: self.streak_length,
+                                    rate_step_down_bps,
+                                    min_interest_rate: self.min_interest_rate,
+                                    early_payoff_rebate_bps,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        combinations
+    }
+}
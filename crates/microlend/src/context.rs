@@ -0,0 +1,86 @@
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Context as _, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct Context {
+    pub rpc: RpcClient,
+    pub payer: Keypair,
+}
+
+impl Context {
+    pub fn new(url: &str, keypair: Option<PathBuf>, commitment: CommitmentConfig) -> Result<Self> {
+        let keypair_path = match keypair {
+            Some(path) => path,
+            None => default_keypair_path()?,
+        };
+        let payer = read_keypair_file(&keypair_path)
+            .map_err(|err| anyhow!("reading keypair {}: {}", keypair_path.display(), err))?;
+        Ok(Context {
+            rpc: RpcClient::new_with_commitment(resolve_url(url), commitment),
+            payer,
+        })
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    // Sign with the payer plus any co-signers and wait for confirmation
+    pub fn send(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let mut all_signers: Vec<&Keypair> = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer()),
+            &all_signers,
+            blockhash,
+        );
+        Ok(self.rpc.send_and_confirm_transaction(&transaction)?)
+    }
+
+    // Token program that owns a mint, so Token-2022 pools work as well as classic SPL ones
+    pub fn token_program_for(&self, mint: &Pubkey) -> Result<Pubkey> {
+        let account = self
+            .rpc
+            .get_account(mint)
+            .with_context(|| format!("fetching mint {mint}"))?;
+        Ok(account.owner)
+    }
+
+    // Cluster time from the latest block, falling back to the local clock
+    pub fn now(&self) -> i64 {
+        if let Ok(slot) = self.rpc.get_slot() {
+            if let Ok(block_time) = self.rpc.get_block_time(slot) {
+                return block_time;
+            }
+        }
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or_default()
+    }
+}
+
+// Accepts the Solana CLI monikers as well as full RPC URLs
+pub fn resolve_url(url: &str) -> String {
+    match url {
+        "l" | "localhost" => "http://127.0.0.1:8899",
+        "d" | "devnet" => "https://api.devnet.solana.com",
+        "t" | "testnet" => "https://api.testnet.solana.com",
+        "m" | "mainnet-beta" => "https://api.mainnet-beta.solana.com",
+        other => other,
+    }
+    .to_string()
+}
+
+fn default_keypair_path() -> Result<PathBuf> {
+    let home = std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set"))?;
+    Ok(PathBuf::from(home).join(".config/solana/id.json"))
+}
@@ -0,0 +1,46 @@
+use crate::context::Context;
+use anchor_lang::prelude::Pubkey;
+use anyhow::{bail, Result};
+use micro_lending_client::{accounts, instructions};
+
+// Recompute scores for the given users, or every profile on the platform when none are given
+pub fn update_scores(ctx: &Context, users: Vec<Pubkey>) -> Result<()> {
+    let users = if users.is_empty() {
+        accounts::fetch_user_profiles(&ctx.rpc)?
+            .into_iter()
+            .map(|(_, profile)| profile.owner)
+            .collect()
+    } else {
+        users
+    };
+
+    let mut failed = 0usize;
+    for user in &users {
+        match update_score(ctx, user) {
+            Ok((old_score, new_score)) => println!("{user}: {old_score} -> {new_score}"),
+            Err(err) => {
+                failed += 1;
+                eprintln!("{user}: {err:#}");
+            }
+        }
+    }
+    println!("{} updated, {failed} failed", users.len() - failed);
+    if failed > 0 {
+        bail!("{failed} update(s) failed");
+    }
+    Ok(())
+}
+
+fn update_score(ctx: &Context, user: &Pubkey) -> Result<(u16, u16)> {
+    let old_score = accounts::fetch_user_profile(&ctx.rpc, user)?.credit_score;
+    // The program filters out expired, revoked and unverified attestations itself, along with
+    // those whose attester is no longer authorized
+    let attesters: Vec<Pubkey> = accounts::fetch_attestations_for(&ctx.rpc, user)?
+        .into_iter()
+        .map(|(_, attestation)| attestation.attester)
+        .collect();
+    let instruction = instructions::update_credit_score(&ctx.payer(), user, &attesters);
+    ctx.send(&[instruction], &[])?;
+    let new_score = accounts::fetch_user_profile(&ctx.rpc, user)?.credit_score;
+    Ok((old_score, new_score))
+}
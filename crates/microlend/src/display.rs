@@ -0,0 +1,74 @@
+use clap::ValueEnum;
+use micro_lending_client::micro_lending::{CollateralType, LoanStatus};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum StatusArg {
+    Requested,
+    Approved,
+    Disbursed,
+    Active,
+    Repaid,
+    Defaulted,
+    Liquidated,
+}
+
+impl From<StatusArg> for LoanStatus {
+    fn from(status: StatusArg) -> Self {
+        match status {
+            StatusArg::Requested => LoanStatus::Requested,
+            StatusArg::Approved => LoanStatus::Approved,
+            StatusArg::Disbursed => LoanStatus::Disbursed,
+            StatusArg::Active => LoanStatus::Active,
+            StatusArg::Repaid => LoanStatus::Repaid,
+            StatusArg::Defaulted => LoanStatus::Defaulted,
+            StatusArg::Liquidated => LoanStatus::Liquidated,
+        }
+    }
+}
+
+pub fn status_name(status: &LoanStatus) -> &'static str {
+    match status {
+        LoanStatus::Requested => "requested",
+        LoanStatus::Approved => "approved",
+        LoanStatus::Disbursed => "disbursed",
+        LoanStatus::Active => "active",
+        LoanStatus::Repaid => "repaid",
+        LoanStatus::Defaulted => "defaulted",
+        LoanStatus::Liquidated => "liquidated",
+    }
+}
+
+pub fn collateral_name(collateral_type: &CollateralType) -> &'static str {
+    match collateral_type {
+        CollateralType::None => "none",
+        CollateralType::Social => "social",
+        CollateralType::Asset => "asset",
+        CollateralType::Income => "income",
+        CollateralType::Group => "group",
+    }
+}
+
+// Basis points as a percentage, e.g. 1250 -> "12.50%"
+pub fn bps(value: u64) -> String {
+    format!("{}.{:02}%", value / 100, value % 100)
+}
+
+pub fn ratio_bps(numerator: u64, denominator: u64) -> u64 {
+    if denominator == 0 {
+        0
+    } else {
+        (numerator as u128 * 10000 / denominator as u128) as u64
+    }
+}
+
+pub fn timestamp(value: i64) -> String {
+    if value == 0 {
+        "-".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn row(label: &str, value: impl std::fmt::Display) {
+    println!("{:<28}{}", label, value);
+}
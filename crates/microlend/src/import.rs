@@ -0,0 +1,193 @@
+//! Bulk posting of attestations and transaction histories from CSV files.
+//! Each row is sent as its own transaction; failures are reported and skipped.
+
+use crate::context::Context;
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, bail, Result};
+use micro_lending_client::micro_lending::TransactionType;
+use micro_lending_client::{accounts, instructions, pda};
+use serde::Deserialize;
+use solana_sdk::signature::Signature;
+use std::collections::HashMap;
+use std::path::Path;
+
+// user,attestation_type,score,metadata,expires_at
+#[derive(Deserialize)]
+struct AttestationRow {
+    user: String,
+    attestation_type: String,
+    score: u16,
+    #[serde(default)]
+    metadata: String,
+    expires_at: Option<i64>,
+}
+
+// user,transaction_type,amount,counterparty,timestamp,frequency_score,consistency_score
+#[derive(Deserialize)]
+struct TransactionRow {
+    user: String,
+    transaction_type: String,
+    amount: u64,
+    counterparty: Option<String>,
+    timestamp: i64,
+    frequency_score: u16,
+    consistency_score: u16,
+}
+
+// Index into the program's AttestationType, by name or number
+fn parse_attestation_type(value: &str) -> Result<u8> {
+    const NAMES: [&str; 6] = [
+        "community",
+        "employer",
+        "family",
+        "business",
+        "education",
+        "reference",
+    ];
+    let value = value.trim().to_ascii_lowercase();
+    if let Some(index) = NAMES.iter().position(|name| *name == value) {
+        return Ok(index as u8);
+    }
+    match value.parse::<u8>() {
+        Ok(index) if (index as usize) < NAMES.len() => Ok(index),
+        _ => bail!("unknown attestation type '{value}'"),
+    }
+}
+
+fn parse_transaction_type(value: &str) -> Result<TransactionType> {
+    Ok(
+        match value.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "mobile_money" => TransactionType::MobileMoney,
+            "remittance" => TransactionType::Remittance,
+            "merchant" => TransactionType::Merchant,
+            "utility" => TransactionType::Utility,
+            "savings" => TransactionType::Savings,
+            "investment" => TransactionType::Investment,
+            "loan" => TransactionType::Loan,
+            "repayment" => TransactionType::Repayment,
+            other => bail!("unknown transaction type '{other}'"),
+        },
+    )
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("invalid public key '{value}'"))
+}
+
+// Post each row with `post`, printing the outcome of each
+fn post_rows<R>(path: &Path, mut post: impl FnMut(R) -> Result<Signature>) -> Result<()>
+where
+    R: for<'de> Deserialize<'de>,
+{
+    let mut reader = csv::Reader::from_path(path)?;
+    let (mut posted, mut failed) = (0usize, 0usize);
+    for (index, record) in reader.deserialize::<R>().enumerate() {
+        // Header is line 1
+        let line = index + 2;
+        match record.map_err(anyhow::Error::from).and_then(&mut post) {
+            Ok(signature) => {
+                posted += 1;
+                println!("line {line}: {signature}");
+            }
+            Err(err) => {
+                failed += 1;
+                eprintln!("line {line}: {err:#}");
+            }
+        }
+    }
+    println!("{posted} posted, {failed} failed");
+    if failed > 0 {
+        bail!("{failed} row(s) failed");
+    }
+    Ok(())
+}
+
+// The signing keypair is the attester for every row
+pub fn attestations(ctx: &Context, path: &Path) -> Result<()> {
+    let attester = ctx.payer();
+    let registered = ctx
+        .rpc
+        .get_account_with_commitment(&pda::attester(&attester).0, ctx.rpc.commitment())?
+        .value
+        .is_some();
+    post_rows(path, |row: AttestationRow| {
+        let instruction = instructions::add_attestation(
+            &attester,
+            &parse_pubkey(&row.user)?,
+            registered,
+            parse_attestation_type(&row.attestation_type)?,
+            row.score,
+            row.metadata,
+            row.expires_at,
+        );
+        ctx.send(&[instruction], &[])
+    })
+}
+
+// History entries are keyed by the user's running count, tracked locally across rows
+pub fn transactions(ctx: &Context, path: &Path) -> Result<()> {
+    let authority = ctx.payer();
+    let mut next_index: HashMap<Pubkey, u16> = HashMap::new();
+    post_rows(path, |row: TransactionRow| {
+        let user = parse_pubkey(&row.user)?;
+        let index = match next_index.get(&user) {
+            Some(index) => *index,
+            None => accounts::fetch_user_profile(&ctx.rpc, &user)?.transaction_history_count,
+        };
+        let counterparty = match row.counterparty.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(counterparty) => Some(parse_pubkey(counterparty)?),
+        };
+        let instruction = instructions::add_transaction_history(
+            &authority,
+            &user,
+            index,
+            parse_transaction_type(&row.transaction_type)?,
+            row.amount,
+            counterparty,
+            row.timestamp,
+            row.frequency_score,
+            row.consistency_score,
+        );
+        let signature = ctx.send(&[instruction], &[])?;
+        next_index.insert(user, index + 1);
+        Ok(signature)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attestation_types_parse_by_name_or_index() {
+        assert_eq!(parse_attestation_type("community").unwrap(), 0);
+        assert_eq!(parse_attestation_type(" Employer ").unwrap(), 1);
+        assert_eq!(parse_attestation_type("5").unwrap(), 5);
+        assert!(parse_attestation_type("6").is_err());
+        assert!(parse_attestation_type("neighbour").is_err());
+    }
+
+    #[test]
+    fn transaction_types_accept_either_separator() {
+        assert!(matches!(
+            parse_transaction_type("Mobile-Money").unwrap(),
+            TransactionType::MobileMoney
+        ));
+        assert!(matches!(
+            parse_transaction_type("repayment").unwrap(),
+            TransactionType::Repayment
+        ));
+        assert!(parse_transaction_type("barter").is_err());
+    }
+
+    #[test]
+    fn pubkeys_are_trimmed() {
+        let key = Pubkey::new_unique();
+        assert_eq!(parse_pubkey(&format!(" {key}\t")).unwrap(), key);
+        assert!(parse_pubkey("not-a-key").is_err());
+    }
+}
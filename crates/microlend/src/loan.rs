@@ -0,0 +1,109 @@
+use crate::context::Context;
+use crate::display::{bps, collateral_name, row, status_name, timestamp, StatusArg};
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use micro_lending_client::micro_lending::Loan;
+use micro_lending_client::{accounts, instructions, pda, quote};
+
+pub fn list(ctx: &Context, pool: Option<Pubkey>, status: Option<StatusArg>) -> Result<()> {
+    let mut loans = match (pool, status) {
+        (Some(pool), _) => accounts::fetch_loans_by_pool(&ctx.rpc, &pool)?,
+        (None, Some(status)) => accounts::fetch_loans_by_status(&ctx.rpc, status.into())?,
+        (None, None) => accounts::fetch_all::<Loan>(&ctx.rpc, vec![])?,
+    };
+    if let (Some(_), Some(status)) = (pool, status) {
+        let status = status.into();
+        loans.retain(|(_, loan)| loan.status == status);
+    }
+    loans.sort_by_key(|(_, loan)| loan.created_at);
+
+    println!(
+        "{:<45} {:<45} {:>12} {:>12} {:>8} {:<11} {:>12}",
+        "LOAN", "BORROWER", "AMOUNT", "REPAID", "RATE", "STATUS", "DUE"
+    );
+    for (address, loan) in &loans {
+        println!(
+            "{:<45} {:<45} {:>12} {:>12} {:>8} {:<11} {:>12}",
+            address.to_string(),
+            loan.borrower.to_string(),
+            loan.amount,
+            loan.amount_repaid,
+            bps(loan.interest_rate as u64),
+            status_name(&loan.status),
+            timestamp(loan.due_date)
+        );
+    }
+    println!("{} loan(s)", loans.len());
+    Ok(())
+}
+
+pub fn show(ctx: &Context, pool: Pubkey, borrower: Pubkey) -> Result<()> {
+    let loan = accounts::fetch_loan(&ctx.rpc, &borrower, &pool)?;
+    row("Loan", pda::loan(&borrower, &pool).0);
+    row("Borrower", loan.borrower);
+    row("Pool", loan.lender_pool);
+    row("Status", status_name(&loan.status));
+    row("Amount", loan.amount);
+    row("Amount repaid", loan.amount_repaid);
+    row("Interest rate", bps(loan.interest_rate as u64));
+    row("Duration (days)", loan.duration_days);
+    row("Purpose", &loan.purpose);
+    row("Collateral", collateral_name(&loan.collateral_type));
+    row("Created at", loan.created_at);
+    row("Approved at", timestamp(loan.approved_at));
+    row("Disbursed at", timestamp(loan.disbursed_at));
+    row("Due date", timestamp(loan.due_date));
+    row("Payments", loan.payment_count);
+    row("Scheduled repayment", loan.scheduled_repayment);
+    row("On-time streak", loan.on_time_streak);
+    row("Restructures", loan.restructure_count);
+    row("Forbearance until", timestamp(loan.forbearance_until));
+    if let Some(officer) = loan.loan_officer {
+        row("Loan officer", officer);
+    }
+    for attester in &loan.backing_attesters {
+        row("Backing attester", attester);
+    }
+
+    if loan.disbursed_at != 0 && loan.amount_repaid < loan.amount {
+        let platform = accounts::fetch_platform(&ctx.rpc)?;
+        let lending_pool = accounts::fetch_lending_pool(&ctx.rpc, &pool)?;
+        let payoff = quote::quote_payoff(&platform, &lending_pool, &loan, ctx.now())?;
+        row("Payoff now", payoff.total_due);
+        row("  interest", payoff.interest_accrued);
+        row("  late fee", payoff.late_fee);
+        row("  early payoff rebate", payoff.early_payoff_rebate);
+    }
+    Ok(())
+}
+
+pub fn approve(ctx: &Context, pool: Pubkey, borrower: Pubkey) -> Result<()> {
+    let lending_pool = accounts::fetch_lending_pool(&ctx.rpc, &pool)?;
+    let instruction = instructions::approve_loan(
+        &ctx.payer(),
+        &pool,
+        &borrower,
+        lending_pool.committee.is_some(),
+    );
+    let signature = ctx.send(&[instruction], &[])?;
+    println!("Loan approved for {borrower}");
+    println!("Signature: {signature}");
+    Ok(())
+}
+
+pub fn disburse(ctx: &Context, pool: Pubkey, borrower: Pubkey) -> Result<()> {
+    let lending_pool = accounts::fetch_lending_pool(&ctx.rpc, &pool)?;
+    let loan = accounts::fetch_loan(&ctx.rpc, &borrower, &pool)?;
+    let token_program = ctx.token_program_for(&lending_pool.mint)?;
+    let instruction = instructions::disburse_loan(
+        &ctx.payer(),
+        &lending_pool.mint,
+        &token_program,
+        &borrower,
+        loan.loan_officer.as_ref(),
+    );
+    let signature = ctx.send(&[instruction], &[])?;
+    println!("Disbursed {} to {borrower}", loan.amount);
+    println!("Signature: {signature}");
+    Ok(())
+}
@@ -0,0 +1,187 @@
+//! `microlend`: operator CLI for the micro_lending program.
+
+mod context;
+mod credit;
+mod display;
+mod import;
+mod loan;
+mod platform;
+mod pool;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use context::Context;
+use display::StatusArg;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(
+    name = "microlend",
+    version,
+    about = "Operate a micro_lending deployment"
+)]
+struct Cli {
+    /// RPC URL or moniker (localhost, devnet, testnet, mainnet-beta)
+    #[arg(
+        long,
+        short = 'u',
+        global = true,
+        env = "MICROLEND_URL",
+        default_value = "localhost"
+    )]
+    url: String,
+
+    /// Signing keypair; defaults to the Solana CLI keypair
+    #[arg(long, short = 'k', global = true, env = "MICROLEND_KEYPAIR")]
+    keypair: Option<PathBuf>,
+
+    /// Commitment level for reads and confirmations
+    #[arg(long, global = true, default_value = "confirmed")]
+    commitment: CommitmentConfig,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Platform configuration
+    #[command(subcommand)]
+    Platform(PlatformCommand),
+    /// Lending pools
+    #[command(subcommand)]
+    Pool(PoolCommand),
+    /// Loans
+    #[command(subcommand)]
+    Loan(LoanCommand),
+    /// Post social attestations from a CSV file, signed by the keypair as attester
+    ImportAttestations { csv: PathBuf },
+    /// Post transaction histories from a CSV file, signed by the keypair as platform authority
+    ImportTransactions { csv: PathBuf },
+    /// Recompute credit scores for the given users, or for every user
+    UpdateScores { users: Vec<Pubkey> },
+}
+
+#[derive(Subcommand)]
+enum PlatformCommand {
+    /// Initialize the platform with the keypair as payer
+    Init {
+        /// Platform authority; defaults to the keypair
+        #[arg(long)]
+        authority: Option<Pubkey>,
+        /// Platform fee in basis points
+        #[arg(long, default_value_t = 100)]
+        fee_bps: u16,
+        #[arg(long)]
+        max_loan_amount: u64,
+        #[arg(long)]
+        min_loan_amount: u64,
+        #[arg(long, default_value_t = 180)]
+        score_half_life_days: u16,
+        #[arg(long, default_value_t = 365)]
+        inactivity_threshold_days: u16,
+    },
+    /// Print the platform account
+    Show,
+}
+
+#[derive(Subcommand)]
+enum PoolCommand {
+    /// Create a lending pool for a mint with the keypair as pool authority
+    Create {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        name: String,
+        /// Base interest rate in basis points
+        #[arg(long)]
+        base_rate_bps: u16,
+        #[arg(long)]
+        max_duration_days: i64,
+    },
+    /// Print a lending pool account
+    Show { pool: Pubkey },
+    /// List all lending pools
+    List,
+    /// Print utilization, TVL, delinquency and defaults for one or all pools
+    Health { pool: Option<Pubkey> },
+}
+
+#[derive(Subcommand)]
+enum LoanCommand {
+    /// List loans, optionally by pool and status
+    List {
+        #[arg(long)]
+        pool: Option<Pubkey>,
+        #[arg(long, value_enum)]
+        status: Option<StatusArg>,
+    },
+    /// Print a loan and its current payoff
+    Show {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        borrower: Pubkey,
+    },
+    /// Approve a requested loan as pool authority
+    Approve {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        borrower: Pubkey,
+    },
+    /// Disburse an approved loan as pool authority
+    Disburse {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        borrower: Pubkey,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let ctx = Context::new(&cli.url, cli.keypair, cli.commitment)?;
+
+    match cli.command {
+        Command::Platform(PlatformCommand::Init {
+            authority,
+            fee_bps,
+            max_loan_amount,
+            min_loan_amount,
+            score_half_life_days,
+            inactivity_threshold_days,
+        }) => platform::init(
+            &ctx,
+            authority,
+            fee_bps,
+            max_loan_amount,
+            min_loan_amount,
+            score_half_life_days,
+            inactivity_threshold_days,
+        ),
+        Command::Platform(PlatformCommand::Show) => platform::show(&ctx),
+        Command::Pool(PoolCommand::Create {
+            mint,
+            name,
+            base_rate_bps,
+            max_duration_days,
+        }) => pool::create(&ctx, mint, name, base_rate_bps, max_duration_days),
+        Command::Pool(PoolCommand::Show { pool }) => pool::show(&ctx, pool),
+        Command::Pool(PoolCommand::List) => pool::list(&ctx),
+        Command::Pool(PoolCommand::Health { pool }) => pool::health(&ctx, pool),
+        Command::Loan(LoanCommand::List { pool, status }) => loan::list(&ctx, pool, status),
+        Command::Loan(LoanCommand::Show { pool, borrower }) => loan::show(&ctx, pool, borrower),
+        Command::Loan(LoanCommand::Approve { pool, borrower }) => {
+            loan::approve(&ctx, pool, borrower)
+        }
+        Command::Loan(LoanCommand::Disburse { pool, borrower }) => {
+            loan::disburse(&ctx, pool, borrower)
+        }
+        Command::ImportAttestations { csv } => import::attestations(&ctx, &csv),
+        Command::ImportTransactions { csv } => import::transactions(&ctx, &csv),
+        Command::UpdateScores { users } => credit::update_scores(&ctx, users),
+    }
+}
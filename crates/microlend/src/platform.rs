@@ -0,0 +1,61 @@
+use crate::context::Context;
+use crate::display::{bps, row};
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use micro_lending_client::{accounts, instructions, pda};
+
+pub fn init(
+    ctx: &Context,
+    authority: Option<Pubkey>,
+    platform_fee: u16,
+    max_loan_amount: u64,
+    min_loan_amount: u64,
+    score_half_life_days: u16,
+    inactivity_threshold_days: u16,
+) -> Result<()> {
+    let instruction = instructions::initialize_platform(
+        &ctx.payer(),
+        authority.unwrap_or_else(|| ctx.payer()),
+        platform_fee,
+        max_loan_amount,
+        min_loan_amount,
+        score_half_life_days,
+        inactivity_threshold_days,
+    );
+    let signature = ctx.send(&[instruction], &[])?;
+    println!("Platform initialized: {}", pda::platform().0);
+    println!("Signature: {signature}");
+    Ok(())
+}
+
+pub fn show(ctx: &Context) -> Result<()> {
+    let platform = accounts::fetch_platform(&ctx.rpc)?;
+    row("Platform", pda::platform().0);
+    row("Authority", platform.authority);
+    row("Treasury", platform.treasury);
+    row("Platform fee", bps(platform.platform_fee as u64));
+    row("Min loan amount", platform.min_loan_amount);
+    row("Max loan amount", platform.max_loan_amount);
+    row("Loans issued", platform.total_loans_issued);
+    row("Total volume", platform.total_volume);
+    row("Total defaults", platform.total_defaults);
+    row("Active", platform.is_active);
+    row("Score half-life (days)", platform.score_half_life_days);
+    row(
+        "Inactivity threshold (days)",
+        platform.inactivity_threshold_days,
+    );
+    row("Verifiers", platform.verifiers.len());
+    for verifier in &platform.verifiers {
+        row("", verifier);
+    }
+    let ladder = &platform.lending_ladder;
+    if ladder.first_loan_cap == 0 {
+        row("Lending ladder", "disabled");
+    } else {
+        row("Lending ladder first cap", ladder.first_loan_cap);
+        row("Lending ladder step up", bps(ladder.step_up_bps as u64));
+        row("Lending ladder step down", bps(ladder.step_down_bps as u64));
+    }
+    Ok(())
+}
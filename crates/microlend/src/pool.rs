@@ -0,0 +1,350 @@
+use crate::context::Context;
+use crate::display::{bps, ratio_bps, row, timestamp};
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use micro_lending_client::micro_lending::utils::{
+    is_in_forbearance, is_loan_overdue, sync_disaster_forbearance,
+};
+use micro_lending_client::micro_lending::{LendingPool, Loan, LoanStatus};
+use micro_lending_client::{accounts, instructions, pda};
+
+pub fn create(
+    ctx: &Context,
+    mint: Pubkey,
+    name: String,
+    base_interest_rate: u16,
+    max_loan_duration_days: i64,
+) -> Result<()> {
+    let token_program = ctx.token_program_for(&mint)?;
+    let instruction = instructions::create_lending_pool(
+        &ctx.payer(),
+        &mint,
+        &token_program,
+        name,
+        base_interest_rate,
+        max_loan_duration_days,
+    );
+    let signature = ctx.send(&[instruction], &[])?;
+    println!(
+        "Lending pool created: {}",
+        pda::lending_pool(&ctx.payer(), &mint).0
+    );
+    println!("Signature: {signature}");
+    Ok(())
+}
+
+pub fn show(ctx: &Context, address: Pubkey) -> Result<()> {
+    let pool = accounts::fetch_lending_pool(&ctx.rpc, &address)?;
+    row("Pool", address);
+    row("Name", &pool.name);
+    row("Authority", pool.authority);
+    row("Mint", pool.mint);
+    row("Token account", pool.token_account);
+    row("Active", pool.is_active);
+    row("Base interest rate", bps(pool.base_interest_rate as u64));
+    row("Max loan duration (days)", pool.max_loan_duration);
+    row("Total deposited", pool.total_deposited);
+    row("Total borrowed", pool.total_borrowed);
+    row("Total reserved", pool.total_reserved);
+    row("Available liquidity", pool.available_liquidity);
+    row("Total shares", pool.total_shares);
+    row("Active loans", pool.active_loans);
+    row("Interest earned", pool.total_interest_earned);
+    row("Interest distributed", pool.total_interest_distributed);
+    row("Request TTL (s)", pool.request_ttl);
+    row("Approval TTL (s)", pool.approval_ttl);
+    row(
+        "Committee",
+        pool.committee
+            .map(|committee| committee.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+    );
+    row("Disaster declared at", timestamp(pool.disaster_declared_at));
+    row("Created at", pool.created_at);
+    Ok(())
+}
+
+pub fn list(ctx: &Context) -> Result<()> {
+    let mut pools = accounts::fetch_lending_pools(&ctx.rpc)?;
+    pools.sort_by_key(|(_, pool)| pool.created_at);
+    println!(
+        "{:<45} {:<20} {:>16} {:>16} {:>8}",
+        "POOL", "NAME", "LIQUIDITY", "BORROWED", "LOANS"
+    );
+    for (address, pool) in pools {
+        println!(
+            "{:<45} {:<20} {:>16} {:>16} {:>8}",
+            address.to_string(),
+            truncate(&pool.name, 20),
+            pool.available_liquidity,
+            pool.total_borrowed,
+            pool.active_loans
+        );
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct PoolHealth {
+    tvl: u64,
+    utilization_bps: u64,
+    outstanding_loans: u64,
+    outstanding_principal: u64,
+    overdue_loans: u64,
+    overdue_principal: u64,
+    repaid_loans: u64,
+    defaulted_loans: u64,
+    defaulted_principal: u64,
+    pending_requests: u64,
+}
+
+fn pool_health(pool: &LendingPool, loans: &[(Pubkey, Loan)], now: i64) -> PoolHealth {
+    let tvl = pool.available_liquidity.saturating_add(pool.total_borrowed);
+    let mut health = PoolHealth {
+        tvl,
+        utilization_bps: ratio_bps(pool.total_borrowed, tvl),
+        ..PoolHealth::default()
+    };
+    for (_, loan) in loans {
+        // Archived refinanced copies would double count the loan they were rolled into
+        if loan.refinanced {
+            continue;
+        }
+        let outstanding = loan.amount.saturating_sub(loan.amount_repaid);
+        match loan.status {
+            LoanStatus::Requested | LoanStatus::Approved => health.pending_requests += 1,
+            LoanStatus::Disbursed | LoanStatus::Active => {
+                health.outstanding_loans += 1;
+                health.outstanding_principal += outstanding;
+                // Including a disaster forbearance the loan has not picked up yet
+                let mut loan = loan.clone();
+                sync_disaster_forbearance(&mut loan, pool);
+                if !is_in_forbearance(&loan, now)
+                    && is_loan_overdue(loan.due_date, now, loan.grace_period_days)
+                {
+                    health.overdue_loans += 1;
+                    health.overdue_principal += outstanding;
+                }
+            }
+            LoanStatus::Repaid => health.repaid_loans += 1,
+            LoanStatus::Defaulted | LoanStatus::Liquidated => {
+                health.defaulted_loans += 1;
+                health.defaulted_principal += outstanding;
+            }
+        }
+    }
+    health
+}
+
+pub fn health(ctx: &Context, address: Option<Pubkey>) -> Result<()> {
+    let pools = match address {
+        Some(address) => vec![(address, accounts::fetch_lending_pool(&ctx.rpc, &address)?)],
+        None => accounts::fetch_lending_pools(&ctx.rpc)?,
+    };
+    let now = ctx.now();
+    for (index, (address, pool)) in pools.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        let loans = accounts::fetch_loans_by_pool(&ctx.rpc, address)?;
+        let health = pool_health(pool, &loans, now);
+        let closed = health.repaid_loans + health.defaulted_loans;
+        row("Pool", format!("{} ({})", address, pool.name));
+        row("TVL", health.tvl);
+        row("Available liquidity", pool.available_liquidity);
+        row("Reserved", pool.total_reserved);
+        row("Utilization", bps(health.utilization_bps));
+        row("Outstanding loans", health.outstanding_loans);
+        row("Outstanding principal", health.outstanding_principal);
+        row(
+            "Overdue loans",
+            format!(
+                "{} ({} principal, {} of portfolio)",
+                health.overdue_loans,
+                health.overdue_principal,
+                bps(ratio_bps(
+                    health.overdue_principal,
+                    health.outstanding_principal
+                ))
+            ),
+        );
+        row("Pending requests", health.pending_requests);
+        row("Repaid loans", health.repaid_loans);
+        row(
+            "Defaults",
+            format!(
+                "{} ({} principal, {} of closed loans)",
+                health.defaulted_loans,
+                health.defaulted_principal,
+                bps(ratio_bps(health.defaulted_loans, closed))
+            ),
+        );
+        row("Interest earned", pool.total_interest_earned);
+        row("Interest distributed", pool.total_interest_distributed);
+        if pool.disaster_forbearance_until > now {
+            row(
+                "Disaster forbearance until",
+                pool.disaster_forbearance_until,
+            );
+        }
+    }
+    Ok(())
+}
+
+fn truncate(value: &str, width: usize) -> String {
+    value.chars().take(width).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use micro_lending_client::micro_lending::{
+        AutoApprovalRules, BorrowerPolicy, CollateralType, RepaymentIncentives,
+        ALL_COLLATERAL_TYPES,
+    };
+
+    const DAY: i64 = 86_400;
+
+    fn pool() -> LendingPool {
+        LendingPool {
+            authority: Pubkey::default(),
+            mint: Pubkey::default(),
+            token_account: Pubkey::default(),
+            name: "health".to_string(),
+            base_interest_rate: 1000,
+            max_loan_duration: 365,
+            total_deposited: 1_000_000,
+            total_borrowed: 250_000,
+            total_shares: 1_000_000,
+            available_liquidity: 750_000,
+            active_loans: 0,
+            total_interest_earned: 0,
+            total_interest_distributed: 0,
+            interest_per_share: 0,
+            is_active: true,
+            created_at: 0,
+            borrower_policy: BorrowerPolicy {
+                min_credit_score: 0,
+                required_verifications: 0,
+                min_attestations: 0,
+                allowed_collateral_types: ALL_COLLATERAL_TYPES,
+                max_loan_per_borrower: 0,
+                allowed_purposes: Vec::new(),
+            },
+            total_reserved: 0,
+            auto_approval: AutoApprovalRules {
+                enabled: false,
+                max_utilization_bps: 0,
+                score_bands: Vec::new(),
+            },
+            request_ttl: 0,
+            approval_ttl: 0,
+            committee: None,
+            disaster_declared_at: 0,
+            disaster_forbearance_until: 0,
+            repayment_incentives: RepaymentIncentives {
+                streak_length: 0,
+                rate_step_down_bps: 0,
+                min_interest_rate: 0,
+                early_payoff_rebate_bps: 0,
+            },
+        }
+    }
+
+    // 100_000 disbursed on day 1, due on day 30 with 3 days' grace
+    fn loan(status: LoanStatus, amount_repaid: u64) -> (Pubkey, Loan) {
+        let loan = Loan {
+            borrower: Pubkey::default(),
+            lender_pool: Pubkey::default(),
+            amount: 100_000,
+            interest_rate: 1000,
+            duration_days: 30,
+            disbursed_at: DAY,
+            due_date: 30 * DAY,
+            amount_repaid,
+            interest_accrued: 0,
+            status,
+            purpose: String::new(),
+            collateral_type: CollateralType::None,
+            collateral_value: 0,
+            payment_count: 0,
+            last_payment_date: 0,
+            grace_period_days: 3,
+            late_fee_rate: 0,
+            created_at: 0,
+            liquidated_at: None,
+            backing_attesters: Vec::new(),
+            approved_at: 0,
+            committee_votes: Vec::new(),
+            loan_officer: None,
+            accrual_start: 0,
+            scheduled_repayment: 100_000,
+            restructure_count: 0,
+            restructured_at: None,
+            forbearance_until: 0,
+            forbearance_count: 0,
+            refinanced: false,
+            refinanced_from: None,
+            on_time_streak: 0,
+            rate_step_downs: 0,
+            carried_interest: 0,
+        };
+        (Pubkey::new_unique(), loan)
+    }
+
+    #[test]
+    fn health_buckets_loans_by_status() {
+        let mut refinanced = loan(LoanStatus::Repaid, 100_000);
+        refinanced.1.refinanced = true;
+        let loans = [
+            loan(LoanStatus::Requested, 0),
+            loan(LoanStatus::Approved, 0),
+            loan(LoanStatus::Active, 40_000),
+            loan(LoanStatus::Repaid, 100_000),
+            refinanced,
+            loan(LoanStatus::Liquidated, 30_000),
+        ];
+        let health = pool_health(&pool(), &loans, 10 * DAY);
+        assert_eq!(health.tvl, 1_000_000);
+        assert_eq!(health.utilization_bps, 2_500);
+        assert_eq!(health.pending_requests, 2);
+        assert_eq!(
+            (health.outstanding_loans, health.outstanding_principal),
+            (1, 60_000)
+        );
+        assert_eq!(health.overdue_loans, 0);
+        assert_eq!(health.repaid_loans, 1);
+        assert_eq!(
+            (health.defaulted_loans, health.defaulted_principal),
+            (1, 70_000)
+        );
+    }
+
+    #[test]
+    fn overdue_starts_after_the_grace_period() {
+        let loans = [loan(LoanStatus::Disbursed, 0)];
+        assert_eq!(pool_health(&pool(), &loans, 33 * DAY).overdue_loans, 0);
+        let health = pool_health(&pool(), &loans, 33 * DAY + 1);
+        assert_eq!(
+            (health.overdue_loans, health.overdue_principal),
+            (1, 100_000)
+        );
+    }
+
+    #[test]
+    fn forbearance_is_not_overdue() {
+        let now = 40 * DAY;
+        let mut forborne = loan(LoanStatus::Active, 0);
+        forborne.1.forbearance_until = 45 * DAY;
+        assert_eq!(pool_health(&pool(), &[forborne], now).overdue_loans, 0);
+
+        // A disaster declared on the pool covers loans that have not picked it up yet
+        let mut pool = pool();
+        pool.disaster_declared_at = 20 * DAY;
+        pool.disaster_forbearance_until = 50 * DAY;
+        let loans = [loan(LoanStatus::Active, 0)];
+        assert_eq!(pool_health(&pool, &loans, now).overdue_loans, 0);
+        pool.disaster_declared_at = 0;
+        assert_eq!(pool_health(&pool, &loans, now).overdue_loans, 1);
+    }
+}
@@ -5,3 +5,19 @@ use anchor_lang::prelude::*;
 pub const SEEDS_PLATFORM: &[u8] = b"platform";
 pub const SEEDS_TREASURY: &[u8] = b"treasury";
 pub const SEEDS_USER: &[u8] = b"user_profile";
+pub const SEEDS_ATTESTER: &[u8] = b"attester";
+pub const SEEDS_VERIFICATION: &[u8] = b"verification";
+
+pub const NEUTRAL_CREDIT_SCORE: u16 = 500;
+pub const MAX_RECENT_OUTCOMES: usize = 8;
+pub const MAX_BACKING_ATTESTERS: usize = 5;
+pub const INITIAL_ATTESTER_REPUTATION: u16 = 500;
+pub const MIN_ATTESTER_REPUTATION: u16 = 200;
+pub const MAX_VERIFIERS: usize = 10;
+pub const ALL_COLLATERAL_TYPES: u8 = 0b1_1111;
+pub const MAX_COMMITTEE_MEMBERS: usize = 10;
+pub const MAX_LOAN_RESTRUCTURES: u8 = 3;
+pub const RESTRUCTURE_SCORE_PENALTY: u16 = 15;
+pub const MAX_FORBEARANCE_DAYS: u16 = 180;
+// Prefix of the message a borrower signs to consent to an officer-submitted loan
+pub const LOAN_CONSENT_DOMAIN: &[u8] = b"micro_lending:loan_consent";
@@ -44,4 +44,72 @@ pub enum MicroLendingError {
     LoanNotYetDueForLiquidation,
     #[msg("Invalid Borrower Account")]
     InvalidBorrowerAccount,
+    #[msg("Attestation has expired")]
+    AttestationExpired,
+    #[msg("Attestation has not expired yet")]
+    AttestationNotExpired,
+    #[msg("Not enough valid attestations")]
+    InsufficientAttestations,
+    #[msg("Attester account missing for a backed loan")]
+    MissingAttesterAccount,
+    #[msg("Verifier is already registered")]
+    VerifierAlreadyRegistered,
+    #[msg("Verifier is not registered")]
+    VerifierNotRegistered,
+    #[msg("Verifier registry is full")]
+    VerifierRegistryFull,
+    #[msg("Borrower lacks a verification required by the pool")]
+    MissingRequiredVerification,
+    #[msg("Credit score is below the pool minimum")]
+    CreditScoreBelowPoolMinimum,
+    #[msg("Not enough valid attestations for this pool")]
+    PoolAttestationMinimumNotMet,
+    #[msg("Collateral type not accepted by this pool")]
+    CollateralTypeNotAllowed,
+    #[msg("Loan purpose not accepted by this pool")]
+    LoanPurposeNotAllowed,
+    #[msg("Loan amount exceeds the pool's per-borrower limit")]
+    LoanExceedsPoolBorrowerLimit,
+    #[msg("Loan has not expired yet")]
+    LoanNotExpired,
+    #[msg("Invalid committee configuration")]
+    InvalidCommitteeConfiguration,
+    #[msg("Signer is not a committee member")]
+    NotCommitteeMember,
+    #[msg("Voting window has closed")]
+    VotingWindowClosed,
+    #[msg("Pool requires its loan committee")]
+    CommitteeRequired,
+    #[msg("Committee quorum not reached")]
+    QuorumNotReached,
+    #[msg("Loan officer account missing or mismatched")]
+    LoanOfficerAccountRequired,
+    #[msg("Loan officer is inactive")]
+    LoanOfficerInactive,
+    #[msg("Borrower must sign the request or consent to it through the loan officer")]
+    BorrowerConsentRequired,
+    #[msg("Borrower consent is missing, expired or does not match the request")]
+    InvalidBorrowerConsent,
+    #[msg("Invalid restructuring terms")]
+    InvalidRestructureTerms,
+    #[msg("Loan has been restructured the maximum number of times")]
+    RestructureLimitReached,
+    #[msg("Invalid forbearance period")]
+    InvalidForbearancePeriod,
+    #[msg("Loan is in a forbearance period")]
+    LoanInForbearance,
+    #[msg("Overdue loans cannot be refinanced")]
+    RefinanceNotAllowed,
+    #[msg("New loan does not cover the payoff of the current loan")]
+    RefinanceAmountTooLow,
+    #[msg("Loan amount exceeds the borrower's current limit")]
+    LoanExceedsBorrowerLimit,
+    #[msg("Pool has no assets backing its shares")]
+    PoolInsolvent,
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+    #[msg("Account is not in a layout that can be migrated")]
+    AccountNotMigratable,
+    #[msg("Loan has outlived the pool's TTL and can only be expired")]
+    LoanExpired,
 }
@@ -0,0 +1,408 @@
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PlatformInitialized {
+    pub platform: Pubkey,
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub platform_fee: u16,
+    pub max_loan_amount: u64,
+    pub min_loan_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LendingPoolCreated {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub base_interest_rate: u16,
+    pub max_loan_duration: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserInitialized {
+    pub user_profile: Pubkey,
+    pub user: Pubkey,
+    pub credit_score: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierChanged {
+    pub verifier: Pubkey,
+    pub added: bool, // false when removed
+    pub verifier_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LendingLadderUpdated {
+    pub lending_ladder: LendingLadder,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BorrowerPolicyUpdated {
+    pub pool: Pubkey,
+    pub policy: BorrowerPolicy,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AutoApprovalRulesUpdated {
+    pub pool: Pubkey,
+    pub rules: AutoApprovalRules,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoanTtlsUpdated {
+    pub pool: Pubkey,
+    pub request_ttl: i64,
+    pub approval_ttl: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RepaymentIncentivesUpdated {
+    pub pool: Pubkey,
+    pub incentives: RepaymentIncentives,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RegistryAction {
+    Created,
+    Updated,
+}
+
+#[event]
+pub struct LoanCommitteeChanged {
+    pub committee: Pubkey,
+    pub pool: Pubkey,
+    pub action: RegistryAction,
+    pub members: Vec<Pubkey>,
+    pub quorum: u8,
+    pub voting_window: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoanOfficerChanged {
+    pub loan_officer: Pubkey,
+    pub pool: Pubkey,
+    pub officer: Pubkey,
+    pub action: RegistryAction,
+    pub commission_bps: u16,
+    pub is_active: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttesterChanged {
+    pub attester_registry: Pubkey,
+    pub attester: Pubkey,
+    pub action: RegistryAction,
+    pub name: String,
+    pub allowed_types: u8,
+    pub is_active: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Deposited {
+    pub pool: Pubkey,
+    pub lender: Pubkey,
+    pub amount: u64,
+    pub shares_minted: u64,
+    pub lender_shares_before: u64,
+    pub lender_shares_after: u64,
+    pub pool_liquidity_before: u64,
+    pub pool_liquidity_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub pool: Pubkey,
+    pub lender: Pubkey,
+    pub shares_burned: u64,
+    pub principal_amount: u64,
+    pub interest_amount: u64,
+    pub total_amount: u64,
+    pub lender_shares_before: u64,
+    pub lender_shares_after: u64,
+    pub pool_liquidity_before: u64,
+    pub pool_liquidity_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InterestClaimed {
+    pub pool: Pubkey,
+    pub lender: Pubkey,
+    pub amount: u64,
+    pub lifetime_claimed: u64,
+    pub pool_liquidity_before: u64,
+    pub pool_liquidity_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoanRequested {
+    pub loan: Pubkey,
+    pub borrower: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub duration_days: u32,
+    pub interest_rate: u16,
+    pub collateral_type: CollateralType,
+    pub loan_officer: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoanApproved {
+    pub loan: Pubkey,
+    pub borrower: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub auto_approved: bool,
+    pub approve_votes: u8,
+    pub pool_liquidity_before: u64,
+    pub pool_liquidity_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoanVoteCast {
+    pub loan: Pubkey,
+    pub member: Pubkey,
+    pub approve: bool,
+    pub approve_votes: u8,
+    pub reject_votes: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoanClosedBeforeDisbursement {
+    pub loan: Pubkey,
+    pub borrower: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub previous_status: LoanStatus,
+    pub reason: LoanClosureReason,
+    pub rejection_code: Option<u8>,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LoanClosureReason {
+    Rejected,
+    Cancelled,
+    Expired,
+}
+
+#[event]
+pub struct LoanDisbursed {
+    pub loan: Pubkey,
+    pub borrower: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub interest_rate: u16,
+    pub due_date: i64,
+    pub pool_borrowed_before: u64,
+    pub pool_borrowed_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PaymentMade {
+    pub loan: Pubkey,
+    pub borrower: Pubkey,
+    pub pool: Pubkey,
+    pub payment_amount: u64,
+    pub platform_fee: u64,
+    pub interest_paid: u64,
+    pub late_fee: u64,
+    pub principal_paid: u64,
+    pub officer_commission: u64,
+    pub outstanding_before: u64,
+    pub outstanding_after: u64,
+    pub pool_liquidity_before: u64,
+    pub pool_liquidity_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoanRepaid {
+    pub loan: Pubkey,
+    pub borrower: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub amount_repaid: u64,
+    pub outcome: LoanOutcome,
+    pub payment_count: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoanLiquidated {
+    pub loan: Pubkey,
+    pub borrower: Pubkey,
+    pub pool: Pubkey,
+    pub liquidator: Pubkey,
+    pub outstanding_amount: u64,
+    pub pool_borrowed_before: u64,
+    pub pool_borrowed_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoanRestructured {
+    pub loan: Pubkey,
+    pub borrower: Pubkey,
+    pub capitalized: u64,
+    pub outstanding_before: u64,
+    pub outstanding_after: u64,
+    pub old_interest_rate: u16,
+    pub new_interest_rate: u16,
+    pub old_due_date: i64,
+    pub new_due_date: i64,
+    pub restructure_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ForbearanceGranted {
+    pub loan: Pubkey,
+    pub borrower: Pubkey,
+    pub days: u16,
+    pub forbearance_until: i64,
+    pub old_due_date: i64,
+    pub new_due_date: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisasterDeclared {
+    pub pool: Pubkey,
+    pub forbearance_days: u16,
+    pub forbearance_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisasterForbearanceApplied {
+    pub loan: Pubkey,
+    pub borrower: Pubkey,
+    pub pool: Pubkey,
+    pub forbearance_until: i64,
+    pub old_due_date: i64,
+    pub new_due_date: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoanRefinanced {
+    pub loan: Pubkey,
+    pub refinanced_loan: Pubkey,
+    pub borrower: Pubkey,
+    pub pool: Pubkey,
+    pub payoff: u64,
+    pub platform_fee: u64,
+    pub officer_commission: u64,
+    pub new_amount: u64,
+    pub net_disbursement: u64,
+    pub new_interest_rate: u16,
+    pub due_date: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InterestRateSteppedDown {
+    pub loan: Pubkey,
+    pub borrower: Pubkey,
+    pub on_time_streak: u8,
+    pub old_interest_rate: u16,
+    pub new_interest_rate: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EarlyPayoffRebated {
+    pub loan: Pubkey,
+    pub borrower: Pubkey,
+    pub rebate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestationChanged {
+    pub attestation: Pubkey,
+    pub user: Pubkey,
+    pub attester: Pubkey,
+    pub action: AttestationAction,
+    pub attestation_type: AttestationType,
+    pub score: u16,
+    pub verified: bool,
+    pub is_active: bool,
+    pub expires_at: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AttestationAction {
+    Added,
+    Updated,
+    Renewed,
+    Revoked,
+    Expired,
+}
+
+#[event]
+pub struct TransactionHistoryAdded {
+    pub transaction_history: Pubkey,
+    pub user: Pubkey,
+    pub transaction_type: TransactionType,
+    pub amount: u64,
+    pub counterparty: Option<Pubkey>,
+    pub transaction_timestamp: i64,
+    pub frequency_score: u16,
+    pub consistency_score: u16,
+    pub history_count: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerificationUpdated {
+    pub user: Pubkey,
+    pub verifier: Pubkey,
+    pub verification_type: VerificationType,
+    pub verified: bool,
+    pub expires_at: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreditScoreUpdated {
+    pub user: Pubkey,
+    pub old_score: u16,
+    pub new_score: u16,
+    pub successful_loans: u16,
+    pub defaulted_loans: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub account_type: MigratedAccountType,
+    pub old_space: u32,
+    pub new_space: u32,
+    pub timestamp: i64,
+}
@@ -1,7 +1,8 @@
 use crate::error::*;
+use crate::events::*;
 use crate::states::*;
 use crate::utils::*;
-use crate::SEEDS_USER;
+use crate::{SEEDS_ATTESTER, SEEDS_USER};
 use anchor_lang::prelude::*;
 
 pub fn add_attestation(
@@ -18,6 +19,12 @@ pub fn add_attestation(
     // Validate inputs
     require!(score <= 1000, MicroLendingError::InvalidAttestation); // Example max score
     require!(metadata.len() <= 500, MicroLendingError::InvalidAttestation);
+    if let Some(expires_at) = expires_at {
+        require!(
+            expires_at > clock.unix_timestamp,
+            MicroLendingError::InvalidAttestation
+        );
+    }
 
     // Validate attestation using utility function
     validate_social_attestation(
@@ -36,15 +43,27 @@ pub fn add_attestation(
         5 => AttestationType::Reference,
         _ => return Err(MicroLendingError::InvalidAttestationType.into()),
     };
+    // Only registered attesters authorized for this type issue verified attestations
+    let verified = match ctx.accounts.attester_registry.as_mut() {
+        Some(attester_registry) if is_attester_authorized(attester_registry, att_type) => {
+            attester_registry.attestations_issued =
+                attester_registry.attestations_issued.saturating_add(1);
+            true
+        }
+        _ => false,
+    };
+
     // Initialize the attestation account
     attestation.user = ctx.accounts.user.key();
     attestation.attester = ctx.accounts.attester.key();
     attestation.attestation_type = att_type;
     attestation.score = score;
     attestation.metadata = metadata;
-    attestation.verified = true; // The attester is implicitly trusted in this context
+    attestation.verified = verified;
     attestation.created_at = clock.unix_timestamp;
     attestation.expires_at = expires_at;
+    attestation.is_active = true;
+    attestation.updated_at = clock.unix_timestamp;
 
     // Update the user's profile
     user_profile.social_attestations_count = user_profile
@@ -54,9 +73,22 @@ pub fn add_attestation(
     user_profile.last_updated = clock.unix_timestamp;
 
     msg!(
-        "Social attestation added for user: {}",
-        ctx.accounts.user.key()
+        "Social attestation added for user: {}, verified: {}",
+        ctx.accounts.user.key(),
+        verified
     );
+    emit!(AttestationChanged {
+        attestation: attestation.key(),
+        user: attestation.user,
+        attester: attestation.attester,
+        action: AttestationAction::Added,
+        attestation_type: attestation.attestation_type,
+        score: attestation.score,
+        verified: attestation.verified,
+        is_active: attestation.is_active,
+        expires_at: attestation.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
     Ok(())
 }
 
@@ -85,5 +117,12 @@ pub struct AddAttestation<'info> {
     )]
     pub social_attestation: Account<'info, SocialAttestation>,
 
+    #[account(
+        mut,
+        seeds = [SEEDS_ATTESTER, attester.key().as_ref()],
+        bump
+    )]
+    pub attester_registry: Option<Account<'info, Attester>>,
+
     pub system_program: Program<'info, System>,
 }
@@ -1,3 +1,4 @@
+use crate::events::*;
 use crate::states::*;
 use crate::SEEDS_PLATFORM;
 use crate::SEEDS_USER;
@@ -37,6 +38,18 @@ pub fn add_transaction_history(
         "Transaction history added for user: {}",
         ctx.accounts.user.key()
     );
+    emit!(TransactionHistoryAdded {
+        transaction_history: trans_hist.key(),
+        user: trans_hist.user,
+        transaction_type: trans_hist.transaction_type,
+        amount,
+        counterparty,
+        transaction_timestamp: timestamp,
+        frequency_score,
+        consistency_score,
+        history_count: user_profile.transaction_history_count,
+        timestamp: current,
+    });
     Ok(())
 }
 
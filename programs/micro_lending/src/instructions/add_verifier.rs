@@ -0,0 +1,42 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::{MAX_VERIFIERS, SEEDS_PLATFORM};
+use anchor_lang::prelude::*;
+
+pub fn add_verifier(ctx: Context<AddVerifier>, verifier: Pubkey) -> Result<()> {
+    let platform = &mut ctx.accounts.platform;
+
+    require!(
+        !platform.verifiers.contains(&verifier),
+        MicroLendingError::VerifierAlreadyRegistered
+    );
+    require!(
+        platform.verifiers.len() < MAX_VERIFIERS,
+        MicroLendingError::VerifierRegistryFull
+    );
+
+    platform.verifiers.push(verifier);
+
+    msg!("Verifier registered: {}", verifier);
+    emit!(VerifierChanged {
+        verifier,
+        added: true,
+        verifier_count: platform.verifiers.len() as u8,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddVerifier<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEEDS_PLATFORM],
+        bump,
+        has_one = authority
+    )]
+    pub platform: Account<'info, Platform>,
+}
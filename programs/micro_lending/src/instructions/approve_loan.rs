@@ -1,16 +1,39 @@
 use crate::error::*;
+use crate::events::*;
 use crate::states::*;
+use crate::utils::*;
 use anchor_lang::prelude::*;
 
 pub fn approve_loan(ctx: Context<ApproveLoan>) -> Result<()> {
     let loan = &mut ctx.accounts.loan;
     let lending_pool = &mut ctx.accounts.lending_pool;
+    let current = Clock::get()?.unix_timestamp;
 
     // Validate loan state
     require!(
         loan.status == LoanStatus::Requested,
         MicroLendingError::InvalidLoanState
     );
+    require!(
+        !is_loan_stale(loan, lending_pool, current),
+        MicroLendingError::LoanExpired
+    );
+
+    // Committee pools need the quorum of approve votes from current members
+    let mut approve_votes = 0;
+    if let Some(committee_key) = lending_pool.committee {
+        let committee = ctx
+            .accounts
+            .loan_committee
+            .as_ref()
+            .filter(|committee| committee.key() == committee_key)
+            .ok_or(MicroLendingError::CommitteeRequired)?;
+        approve_votes = committee_tally(loan, committee).0;
+        require!(
+            approve_votes >= committee.quorum,
+            MicroLendingError::QuorumNotReached
+        );
+    }
 
     // Check pool liquidity
     require!(
@@ -19,16 +42,25 @@ pub fn approve_loan(ctx: Context<ApproveLoan>) -> Result<()> {
     );
 
     // Update loan status
+    let pool_liquidity_before = lending_pool.available_liquidity;
     loan.status = LoanStatus::Approved;
+    loan.approved_at = current;
 
     // Reserve liquidity in the pool
-    lending_pool.available_liquidity = lending_pool
-        .available_liquidity
-        .checked_sub(loan.amount)
-        .ok_or(MicroLendingError::InsufficientLiquidity)?;
+    reserve_loan_liquidity(lending_pool, loan.amount)?;
 
-    lending_pool.active_loans = lending_pool.active_loans.checked_add(1).unwrap();
     msg!("Loan approved for borrower: {}", loan.borrower);
+    emit!(LoanApproved {
+        loan: loan.key(),
+        borrower: loan.borrower,
+        pool: lending_pool.key(),
+        amount: loan.amount,
+        auto_approved: false,
+        approve_votes,
+        pool_liquidity_before,
+        pool_liquidity_after: lending_pool.available_liquidity,
+        timestamp: current,
+    });
     Ok(())
 }
 
@@ -48,5 +80,11 @@ pub struct ApproveLoan<'info> {
     )]
     pub lending_pool: Account<'info, LendingPool>,
 
+    #[account(
+        seeds = [b"loan_committee", lending_pool.key().as_ref()],
+        bump
+    )]
+    pub loan_committee: Option<Account<'info, LoanCommittee>>,
+
     pub authority: Signer<'info>,
 }
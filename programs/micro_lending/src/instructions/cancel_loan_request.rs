@@ -0,0 +1,53 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+pub fn cancel_loan_request(ctx: Context<CancelLoanRequest>) -> Result<()> {
+    let loan = &ctx.accounts.loan;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+
+    let previous_status = loan.status;
+
+    // Validate loan state
+    require!(
+        loan.status == LoanStatus::Requested || loan.status == LoanStatus::Approved,
+        MicroLendingError::InvalidLoanState
+    );
+
+    // Approved loans hold a reservation that goes back to the pool
+    if loan.status == LoanStatus::Approved {
+        release_loan_reservation(lending_pool, loan.amount);
+    }
+
+    msg!("Loan request cancelled by borrower: {}", loan.borrower);
+    emit!(LoanClosedBeforeDisbursement {
+        loan: loan.key(),
+        borrower: loan.borrower,
+        pool: lending_pool.key(),
+        amount: loan.amount,
+        previous_status,
+        reason: LoanClosureReason::Cancelled,
+        rejection_code: None,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelLoanRequest<'info> {
+    #[account(
+        mut,
+        close = borrower,
+        seeds = [b"loan", borrower.key().as_ref(), lending_pool.key().as_ref()],
+        bump
+    )]
+    pub loan: Account<'info, Loan>,
+
+    #[account(mut)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+}
@@ -1,4 +1,5 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
@@ -56,11 +57,18 @@ pub fn claim_interest(ctx: Context<ClaimInterest>) -> Result<()> {
     let unclaimed_interest = get_unclaimed_interest(lender_deposit, lending_pool);
     msg!("unclaimed interest is {}", unclaimed_interest);
     require!(unclaimed_interest > 0, MicroLendingError::NoInterestToClaim);
+    // Interest sits in the pool's liquidity until claimed, but may be lent out meanwhile
+    require!(
+        lending_pool.available_liquidity >= unclaimed_interest,
+        MicroLendingError::InsufficientLiquidity
+    );
 
     // Calculate total interest earned for updating the record
     let total_interest_earned =
         (lender_deposit.shares as u128 * lending_pool.interest_per_share as u128) / 1_000_000_000;
 
+    let pool_liquidity_before = lending_pool.available_liquidity;
+
     // Transfer interest from pool to lender
     let pool_key = lending_pool.key();
     let signer_seeds: &[&[&[u8]]] = &[&[
@@ -84,7 +92,10 @@ pub fn claim_interest(ctx: Context<ClaimInterest>) -> Result<()> {
 
     // Update records
     lender_deposit.interest_debt = total_interest_earned as u64; // Update debt to prevent double-claiming
-    lender_deposit.interest_earned = total_interest_earned as u64; // Track total lifetime earnings
+    lender_deposit.interest_earned = lender_deposit
+        .interest_earned
+        .checked_add(unclaimed_interest)
+        .unwrap(); // Track total lifetime earnings
     lender_deposit.interest_claimed = lender_deposit
         .interest_claimed
         .checked_add(unclaimed_interest)
@@ -97,13 +108,23 @@ pub fn claim_interest(ctx: Context<ClaimInterest>) -> Result<()> {
 
     lending_pool.available_liquidity = lending_pool
         .available_liquidity
-        .saturating_sub(unclaimed_interest);
+        .checked_sub(unclaimed_interest)
+        .unwrap();
 
     msg!(
         "Interest claimed: {} tokens by lender: {}",
         unclaimed_interest,
         ctx.accounts.lender.key()
     );
+    emit!(InterestClaimed {
+        pool: lending_pool.key(),
+        lender: ctx.accounts.lender.key(),
+        amount: unclaimed_interest,
+        lifetime_claimed: lender_deposit.interest_claimed,
+        pool_liquidity_before,
+        pool_liquidity_after: lending_pool.available_liquidity,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     Ok(())
 }
 
@@ -1,5 +1,7 @@
 use crate::error::*;
+use crate::events::*;
 use crate::states::*;
+use crate::ALL_COLLATERAL_TYPES;
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
@@ -38,9 +40,43 @@ pub fn create_lending_pool(
     lending_pool.total_interest_earned = 0;
     lending_pool.is_active = true;
     lending_pool.created_at = current;
+    lending_pool.borrower_policy = BorrowerPolicy {
+        min_credit_score: 0,
+        required_verifications: 0,
+        min_attestations: 0,
+        allowed_collateral_types: ALL_COLLATERAL_TYPES,
+        max_loan_per_borrower: 0,
+        allowed_purposes: Vec::new(),
+    };
+    lending_pool.total_reserved = 0;
+    lending_pool.auto_approval = AutoApprovalRules {
+        enabled: false,
+        max_utilization_bps: 0,
+        score_bands: Vec::new(),
+    };
+    lending_pool.request_ttl = 0;
+    lending_pool.approval_ttl = 0;
+    lending_pool.committee = None;
+    lending_pool.disaster_declared_at = 0;
+    lending_pool.disaster_forbearance_until = 0;
+    lending_pool.repayment_incentives = RepaymentIncentives {
+        streak_length: 0,
+        rate_step_down_bps: 0,
+        min_interest_rate: 0,
+        early_payoff_rebate_bps: 0,
+    };
 
 
     msg!("Lending pool created: {}", lending_pool.name);
+    emit!(LendingPoolCreated {
+        pool: lending_pool.key(),
+        authority: lending_pool.authority,
+        mint: lending_pool.mint,
+        name: lending_pool.name.clone(),
+        base_interest_rate,
+        max_loan_duration,
+        timestamp: current,
+    });
     Ok(())
 }
 
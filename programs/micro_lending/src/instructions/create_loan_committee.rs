@@ -0,0 +1,65 @@
+use crate::events::*;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+pub fn create_loan_committee(
+    ctx: Context<CreateLoanCommittee>,
+    members: Vec<Pubkey>,
+    quorum: u8,
+    voting_window: i64,
+) -> Result<()> {
+    validate_committee(&members, quorum, voting_window)?;
+
+    let committee = &mut ctx.accounts.loan_committee;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+
+    committee.pool = lending_pool.key();
+    committee.members = members;
+    committee.quorum = quorum;
+    committee.voting_window = voting_window;
+    committee.created_at = Clock::get()?.unix_timestamp;
+
+    // From now on approve_loan needs the committee's quorum
+    lending_pool.committee = Some(committee.key());
+
+    msg!(
+        "Loan committee created for pool {} with quorum {} of {}",
+        lending_pool.name,
+        quorum,
+        committee.members.len()
+    );
+    emit!(LoanCommitteeChanged {
+        committee: committee.key(),
+        pool: lending_pool.key(),
+        action: RegistryAction::Created,
+        members: committee.members.clone(),
+        quorum,
+        voting_window,
+        timestamp: committee.created_at,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateLoanCommittee<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LoanCommittee::INIT_SPACE,
+        seeds = [b"loan_committee", lending_pool.key().as_ref()],
+        bump
+    )]
+    pub loan_committee: Account<'info, LoanCommittee>,
+
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
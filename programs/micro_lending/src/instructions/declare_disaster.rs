@@ -0,0 +1,47 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::{MAX_FORBEARANCE_DAYS, SEEDS_PLATFORM};
+use anchor_lang::prelude::*;
+
+// Loans outstanding in the pool pick up the forbearance the next time they are touched
+pub fn declare_disaster(ctx: Context<DeclareDisaster>, forbearance_days: u16) -> Result<()> {
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let current = Clock::get()?.unix_timestamp;
+
+    require!(
+        forbearance_days > 0 && forbearance_days <= MAX_FORBEARANCE_DAYS,
+        MicroLendingError::InvalidForbearancePeriod
+    );
+
+    lending_pool.disaster_declared_at = current;
+    lending_pool.disaster_forbearance_until = current + (forbearance_days as i64 * 86400);
+
+    msg!(
+        "Disaster declared for pool {}, forbearance until {}",
+        lending_pool.name,
+        lending_pool.disaster_forbearance_until
+    );
+    emit!(DisasterDeclared {
+        pool: lending_pool.key(),
+        forbearance_days,
+        forbearance_until: lending_pool.disaster_forbearance_until,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DeclareDisaster<'info> {
+    #[account(
+        seeds = [SEEDS_PLATFORM],
+        bump,
+        has_one = authority
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(mut)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    pub authority: Signer<'info>,
+}
@@ -1,5 +1,7 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
+use crate::utils::*;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{
@@ -13,6 +15,8 @@ pub fn deposit_to_pool(ctx: Context<DepositToPool>, amount: u64) -> Result<()> {
 
     require!(amount > 0, MicroLendingError::InvalidPaymentAmount);
     require!(lending_pool.is_active, MicroLendingError::PoolNotActive);
+    let lender_shares_before = lender_deposit.shares;
+    let pool_liquidity_before = lending_pool.available_liquidity;
 
     // Transfer tokens from lender to pool
     let transfer_cpi_accounts = TransferChecked {
@@ -36,20 +40,14 @@ pub fn deposit_to_pool(ctx: Context<DepositToPool>, amount: u64) -> Result<()> {
         lender_deposit.deposited_at = current;
     }
 
+    // Shares are priced before the deposit lands in the pool
+    let shares_to_mint = calculate_shares_to_mint(lending_pool, amount)?;
+
     lender_deposit.amount_deposited = lender_deposit.amount_deposited.checked_add(amount).unwrap();
     // Update lending pool
     lending_pool.total_deposited = lending_pool.total_deposited.checked_add(amount).unwrap();
 
     //Update lender's shares
-    let shares_to_mint = if lending_pool.total_shares == 0 {
-        amount
-    } else {
-        amount
-            .checked_mul(lending_pool.total_shares)
-            .unwrap()
-            .checked_div(lending_pool.total_deposited)
-            .unwrap()
-    };
     lender_deposit.shares = lender_deposit.shares.checked_add(shares_to_mint).unwrap();
     lending_pool.total_shares = lending_pool
         .total_shares
@@ -74,6 +72,17 @@ pub fn deposit_to_pool(ctx: Context<DepositToPool>, amount: u64) -> Result<()> {
         amount,
         ctx.accounts.lender.key()
     );
+    emit!(Deposited {
+        pool: lending_pool.key(),
+        lender: ctx.accounts.lender.key(),
+        amount,
+        shares_minted: shares_to_mint,
+        lender_shares_before,
+        lender_shares_after: lender_deposit.shares,
+        pool_liquidity_before,
+        pool_liquidity_after: lending_pool.available_liquidity,
+        timestamp: current,
+    });
     Ok(())
 }
 
@@ -1,6 +1,8 @@
 use crate::error::*;
 use crate::states::*;
+use crate::utils::*;
 use crate::SEEDS_USER;
+use crate::events::*;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{
@@ -22,6 +24,10 @@ pub fn disburse_loan(ctx: Context<DisburseLoan>) -> Result<()> {
         loan.status == LoanStatus::Approved,
         MicroLendingError::InvalidLoanState
     );
+    require!(
+        !is_loan_stale(loan, lending_pool, current),
+        MicroLendingError::LoanExpired
+    );
 
     // Calculate due date
     let due_date = current + (loan.duration_days as i64 * 86400);
@@ -45,24 +51,40 @@ pub fn disburse_loan(ctx: Context<DisburseLoan>) -> Result<()> {
 
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, transfer_cpi_accounts, signer_seeds);
     let decimals = ctx.accounts.mint.decimals;
-    transfer_checked(cpi_ctx, loan.amount, decimals);
+    transfer_checked(cpi_ctx, loan.amount, decimals)?;
 
     // Update loan
     loan.status = LoanStatus::Disbursed;
     loan.disbursed_at = current;
     loan.due_date = due_date;
+    loan.accrual_start = current;
+    loan.scheduled_repayment =
+        calculate_loan_payment(loan.amount, loan.interest_rate, loan.duration_days)?;
 
     // Update user profile
     user_profile.active_loans = user_profile.active_loans.checked_add(1).unwrap();
+    user_profile.last_activity_at = current;
     user_profile.total_borrowed = user_profile
         .total_borrowed
         .checked_add(loan.amount)
         .unwrap();
     // Update lending pool
+    let pool_borrowed_before = lending_pool.total_borrowed;
     lending_pool.total_borrowed = lending_pool
         .total_borrowed
         .checked_add(loan.amount)
         .unwrap();
+    lending_pool.total_reserved = lending_pool.total_reserved.saturating_sub(loan.amount);
+
+    // Track the officer's originations and outstanding portfolio
+    if let Some(loan_officer) = loan_officer_for(loan, &mut ctx.accounts.loan_officer)? {
+        loan_officer.loans_originated = loan_officer.loans_originated.saturating_add(1);
+        loan_officer.total_disbursed = loan_officer.total_disbursed.saturating_add(loan.amount);
+        loan_officer.outstanding_principal = loan_officer
+            .outstanding_principal
+            .saturating_add(loan.amount);
+        update_officer_write_off_rate(loan_officer);
+    }
 
     //Update Platform
     platform.total_loans_issued += 1;
@@ -72,6 +94,17 @@ pub fn disburse_loan(ctx: Context<DisburseLoan>) -> Result<()> {
         loan.amount,
         loan.borrower
     );
+    emit!(LoanDisbursed {
+        loan: loan.key(),
+        borrower: loan.borrower,
+        pool: lending_pool.key(),
+        amount: loan.amount,
+        interest_rate: loan.interest_rate,
+        due_date: loan.due_date,
+        pool_borrowed_before,
+        pool_borrowed_after: lending_pool.total_borrowed,
+        timestamp: current,
+    });
     Ok(())
 }
 
@@ -133,6 +166,8 @@ pub struct DisburseLoan<'info> {
         // constraint = borrower_token_account.owner == loan.borrower // Can keep or remove
     )]
     pub borrower_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub loan_officer: Option<Account<'info, LoanOfficer>>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -0,0 +1,60 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::*;
+use crate::SEEDS_USER;
+use anchor_lang::prelude::*;
+
+// Permissionless: anyone can drop an expired attestation from the user's count
+pub fn expire_attestation(ctx: Context<ExpireAttestation>) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    let attestation = &mut ctx.accounts.social_attestation;
+    let current = Clock::get()?.unix_timestamp;
+
+    require!(
+        attestation.is_active && !is_attestation_valid(attestation, current),
+        MicroLendingError::AttestationNotExpired
+    );
+
+    attestation.is_active = false;
+    attestation.updated_at = current;
+    user_profile.social_attestations_count =
+        user_profile.social_attestations_count.saturating_sub(1);
+    user_profile.last_updated = current;
+
+    msg!(
+        "Attestation by {} expired for user: {}",
+        attestation.attester,
+        attestation.user
+    );
+    emit!(AttestationChanged {
+        attestation: attestation.key(),
+        user: attestation.user,
+        attester: attestation.attester,
+        action: AttestationAction::Expired,
+        attestation_type: attestation.attestation_type,
+        score: attestation.score,
+        verified: attestation.verified,
+        is_active: attestation.is_active,
+        expires_at: attestation.expires_at,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExpireAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [SEEDS_USER, social_attestation.user.as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"social_attestation", social_attestation.user.as_ref(), social_attestation.attester.as_ref()],
+        bump
+    )]
+    pub social_attestation: Account<'info, SocialAttestation>,
+}
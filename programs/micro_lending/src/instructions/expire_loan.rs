@@ -0,0 +1,68 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+// Permissionless: any keeper can expire a stale request or approval
+pub fn expire_loan(ctx: Context<ExpireLoan>) -> Result<()> {
+    let loan = &ctx.accounts.loan;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let current = Clock::get()?.unix_timestamp;
+
+    let previous_status = loan.status;
+
+    require!(
+        loan.status == LoanStatus::Requested || loan.status == LoanStatus::Approved,
+        MicroLendingError::InvalidLoanState
+    );
+    require!(
+        is_loan_stale(loan, lending_pool, current),
+        MicroLendingError::LoanNotExpired
+    );
+
+    // Approved loans hold a reservation that goes back to the pool
+    if loan.status == LoanStatus::Approved {
+        release_loan_reservation(lending_pool, loan.amount);
+    }
+
+    msg!(
+        "Loan for borrower {} expired by keeper: {}",
+        loan.borrower,
+        ctx.accounts.keeper.key()
+    );
+    emit!(LoanClosedBeforeDisbursement {
+        loan: loan.key(),
+        borrower: loan.borrower,
+        pool: lending_pool.key(),
+        amount: loan.amount,
+        previous_status,
+        reason: LoanClosureReason::Expired,
+        rejection_code: None,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExpireLoan<'info> {
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        close = borrower,
+        seeds = [b"loan", loan.borrower.as_ref(), lending_pool.key().as_ref()],
+        bump
+    )]
+    pub loan: Account<'info, Loan>,
+
+    #[account(mut)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: Receives the loan account rent; must match loan.borrower.
+    #[account(
+        mut,
+        constraint = borrower.key() == loan.borrower @ MicroLendingError::InvalidBorrowerAccount
+    )]
+    pub borrower: AccountInfo<'info>,
+}
@@ -0,0 +1,56 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::*;
+use crate::MAX_FORBEARANCE_DAYS;
+use anchor_lang::prelude::*;
+
+pub fn grant_forbearance(ctx: Context<GrantForbearance>, days: u16) -> Result<()> {
+    let loan = &mut ctx.accounts.loan;
+    let lending_pool = &ctx.accounts.lending_pool;
+    let current = Clock::get()?.unix_timestamp;
+
+    require!(
+        loan.status == LoanStatus::Disbursed || loan.status == LoanStatus::Active,
+        MicroLendingError::InvalidLoanState
+    );
+    require!(
+        days > 0 && days <= MAX_FORBEARANCE_DAYS,
+        MicroLendingError::InvalidForbearancePeriod
+    );
+
+    apply_disaster_forbearance(loan, lending_pool, current);
+    let old_due_date = loan.due_date;
+    apply_forbearance(loan, days, current);
+
+    msg!(
+        "Forbearance granted to borrower {} until {}, new due date: {}",
+        loan.borrower,
+        loan.forbearance_until,
+        loan.due_date
+    );
+    emit!(ForbearanceGranted {
+        loan: loan.key(),
+        borrower: loan.borrower,
+        days,
+        forbearance_until: loan.forbearance_until,
+        old_due_date,
+        new_due_date: loan.due_date,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GrantForbearance<'info> {
+    #[account(
+        mut,
+        constraint = loan.lender_pool == lending_pool.key() @ MicroLendingError::InvalidLoanState
+    )]
+    pub loan: Account<'info, Loan>,
+
+    #[account(has_one = authority)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    pub authority: Signer<'info>,
+}
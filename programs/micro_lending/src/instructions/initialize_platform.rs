@@ -1,8 +1,10 @@
 use crate::error::*;
+use crate::events::*;
 use crate::states::*;
 use crate::{SEEDS_PLATFORM, SEEDS_TREASURY};
 use anchor_lang::prelude::*;
 
+#[allow(clippy::too_many_arguments)]
 pub fn initialize_platform(
     ctx: Context<InitializePlatform>,
     platform_authority: Pubkey,
@@ -10,6 +12,8 @@ pub fn initialize_platform(
     platform_fee: u16,
     max_loan_amount: u64,
     min_loan_amount: u64,
+    score_half_life_days: u16,
+    inactivity_threshold_days: u16,
 ) -> Result<()> {
     require!(
         platform_fee <= 1000,
@@ -34,11 +38,28 @@ pub fn initialize_platform(
     platform.total_defaults = 0;
     platform.is_active = true;
     platform.created_at = clock.unix_timestamp;
+    platform.score_half_life_days = score_half_life_days;
+    platform.inactivity_threshold_days = inactivity_threshold_days;
+    platform.verifiers = Vec::new();
+    platform.lending_ladder = LendingLadder {
+        first_loan_cap: 0,
+        step_up_bps: 10000,
+        step_down_bps: 10000,
+    };
 
     msg!(
         "Platform initialized with authority: {}",
         platform_authority
     );
+    emit!(PlatformInitialized {
+        platform: platform.key(),
+        authority: platform_authority,
+        treasury: platform.treasury,
+        platform_fee,
+        max_loan_amount,
+        min_loan_amount,
+        timestamp: clock.unix_timestamp,
+    });
     Ok(())
 }
 
@@ -1,3 +1,4 @@
+use crate::events::*;
 use crate::states::*;
 use crate::SEEDS_USER;
 use anchor_lang::prelude::*;
@@ -19,10 +20,27 @@ pub fn initialize_user(ctx: Context<InitializeUser>) -> Result<()> {
     user_profile.kyc_verified = false;
     user_profile.phone_verified = false;
     user_profile.email_verified = false;
+    user_profile.kyc_expires_at = None;
+    user_profile.phone_expires_at = None;
+    user_profile.email_expires_at = None;
     user_profile.transaction_history_count = 0;
     user_profile.social_attestations_count = 0;
+    user_profile.last_activity_at = current;
+    user_profile.recent_outcomes = Vec::new();
+    user_profile.restructured_loans = 0;
+    user_profile.borrowing_limit = 0;
+    user_profile.on_time_streak = 0;
+    user_profile.kyc_verifier = Pubkey::default();
+    user_profile.phone_verifier = Pubkey::default();
+    user_profile.email_verifier = Pubkey::default();
 
     msg!("User profile initialized for: {}", ctx.accounts.user.key());
+    emit!(UserInitialized {
+        user_profile: user_profile.key(),
+        user: user_profile.owner,
+        credit_score: user_profile.credit_score,
+        timestamp: current,
+    });
     Ok(())
 }
 
@@ -1,10 +1,13 @@
 use crate::error::*;
+use crate::events::*;
 use crate::states::*;
 use crate::utils::*;
 use crate::{SEEDS_PLATFORM, SEEDS_USER};
 use anchor_lang::prelude::*;
 
-pub fn liquidate_loan(ctx: Context<LiquidateLoan>) -> Result<()> {
+pub fn liquidate_loan<'info>(
+    ctx: Context<'_, '_, 'info, 'info, LiquidateLoan<'info>>,
+) -> Result<()> {
     let loan = &mut ctx.accounts.loan;
     let user_profile = &mut ctx.accounts.user_profile;
     let platform = &mut ctx.accounts.platform;
@@ -17,6 +20,12 @@ pub fn liquidate_loan(ctx: Context<LiquidateLoan>) -> Result<()> {
         MicroLendingError::InvalidLoanState
     );
 
+    apply_disaster_forbearance(loan, lending_pool, current);
+    require!(
+        !is_in_forbearance(loan, current),
+        MicroLendingError::LoanInForbearance
+    );
+
     require!(
         is_loan_overdue(loan.due_date, current, loan.grace_period_days),
         MicroLendingError::LoanNotYetDueForLiquidation
@@ -30,6 +39,10 @@ pub fn liquidate_loan(ctx: Context<LiquidateLoan>) -> Result<()> {
     user_profile.active_loans = user_profile.active_loans.saturating_sub(1);
     user_profile.defaulted_loans = user_profile.defaulted_loans.checked_add(1).unwrap();
     user_profile.last_updated = current;
+    record_loan_outcome(user_profile, LoanOutcome::Defaulted, current);
+    step_borrowing_limit(user_profile, platform, LoanOutcome::Defaulted);
+    user_profile.on_time_streak = 0;
+    settle_backing_attesters(&loan.backing_attesters, ctx.remaining_accounts, false)?;
 
     // Update platform-wide statistics for defaults
     platform.total_defaults = platform.total_defaults.checked_add(1).unwrap();
@@ -37,11 +50,34 @@ pub fn liquidate_loan(ctx: Context<LiquidateLoan>) -> Result<()> {
     // Update lending pool statistics
     lending_pool.active_loans = lending_pool.active_loans.saturating_sub(1);
     let outstanding_amount = loan.amount.checked_sub(loan.amount_repaid).unwrap();
+    let pool_borrowed_before = lending_pool.total_borrowed;
     lending_pool.total_borrowed = lending_pool
         .total_borrowed
         .saturating_sub(outstanding_amount);
 
+    // The defaulted balance counts against the originating officer
+    if let Some(loan_officer) = loan_officer_for(loan, &mut ctx.accounts.loan_officer)? {
+        loan_officer.defaulted_loans = loan_officer.defaulted_loans.saturating_add(1);
+        loan_officer.defaulted_principal = loan_officer
+            .defaulted_principal
+            .saturating_add(outstanding_amount);
+        loan_officer.outstanding_principal = loan_officer
+            .outstanding_principal
+            .saturating_sub(outstanding_amount);
+        update_officer_write_off_rate(loan_officer);
+    }
+
     msg!("Loan for borrower {} has been liquidated.", loan.borrower);
+    emit!(LoanLiquidated {
+        loan: loan.key(),
+        borrower: loan.borrower,
+        pool: lending_pool.key(),
+        liquidator: ctx.accounts.liquidator.key(),
+        outstanding_amount,
+        pool_borrowed_before,
+        pool_borrowed_after: lending_pool.total_borrowed,
+        timestamp: current,
+    });
     Ok(())
 }
 
@@ -73,4 +109,7 @@ pub struct LiquidateLoan<'info> {
         bump
     )]
     pub user_profile: Account<'info, UserProfile>,
+
+    #[account(mut)]
+    pub loan_officer: Option<Account<'info, LoanOfficer>>,
 }
@@ -1,4 +1,6 @@
+use crate::calculate_payoff_quote;
 use crate::error::*;
+use crate::events::*;
 use crate::states::*;
 use crate::utils::*;
 use crate::SEEDS_PLATFORM;
@@ -9,7 +11,10 @@ use anchor_spl::token_interface::{
     transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
 };
 
-pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()> {
+pub fn make_payment<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MakePayment<'info>>,
+    payment_amount: u64,
+) -> Result<()> {
     let loan = &mut ctx.accounts.loan;
     let lending_pool = &mut ctx.accounts.lending_pool;
     let user_profile = &mut ctx.accounts.user_profile;
@@ -22,45 +27,81 @@ pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()
         MicroLendingError::InvalidLoanState
     );
 
-    // Calculate interest accrued
-    let days_elapsed = days_between(loan.disbursed_at, current);
-    let interest_accrued = calculate_simple_interest(
-        loan.amount - loan.amount_repaid,
-        loan.interest_rate,
-        days_elapsed,
-    )?;
-
+    apply_disaster_forbearance(loan, lending_pool, current);
+    let outstanding_before = loan.amount.checked_sub(loan.amount_repaid).unwrap();
+    let pool_liquidity_before = lending_pool.available_liquidity;
+    let days_elapsed = days_between(loan.accrual_start, current);
+
+    // Interest, late fees and the platform fee come out of the payment before principal
+    let quote = calculate_payoff_quote(platform, lending_pool, loan, current)?;
+    msg!("Total amount due before repayment : {}", quote.total_due);
+    let settlement = settle_payment(&quote, platform.platform_fee, payment_amount)?;
+    let interest_accrued = settlement.interest_paid;
+    let late_fee = settlement.late_fee;
+    let platform_fee = settlement.platform_fee;
     loan.interest_accrued = interest_accrued;
     msg!("Interest Accrued : {}", interest_accrued);
-    // Calculate total amount owed
-    let total_owed = loan
-        .amount
-        .checked_add(interest_accrued)
-        .unwrap()
-        .checked_sub(loan.amount_repaid)
-        .unwrap();
+    msg!("Platform fee charged : {}", platform_fee);
+    let net_payment = payment_amount.checked_sub(platform_fee).unwrap();
+    msg!("Net Payment after platform fee : {}", net_payment);
 
-    // Check if loan is overdue and calculate late fees
-    let mut late_fee = 0u64;
-    if is_loan_overdue(loan.due_date, current, loan.grace_period_days) {
-        let days_overdue = days_between(loan.due_date, current);
-        late_fee = calculate_late_fee(total_owed, loan.late_fee_rate, days_overdue)?;
+    // A payment clearing the balance before the due date forgoes part of the interest
+    if settlement.early_payoff_rebate > 0 {
+        msg!("Early payoff rebate : {}", settlement.early_payoff_rebate);
+        emit!(EarlyPayoffRebated {
+            loan: loan.key(),
+            borrower: loan.borrower,
+            rebate: settlement.early_payoff_rebate,
+            timestamp: current,
+        });
     }
 
-    let total_amount_due = total_owed.checked_add(late_fee).unwrap();
-    msg!("Total amount due before repayment : {}", total_amount_due);
-    // Validate payment amount
-    require!(payment_amount > 0, MicroLendingError::InvalidPaymentAmount);
-    require!(
-        payment_amount <= total_amount_due,
-        MicroLendingError::InvalidPaymentAmount
-    );
+    // Late fees go to lenders with the interest rather than toward principal
+    let lender_charges = interest_accrued.checked_add(late_fee).unwrap();
+
+    // Loan officer commission comes out of the interest, not the principal
+    let mut officer_commission = 0u64;
+    if let Some(loan_officer) = loan_officer_for(loan, &mut ctx.accounts.loan_officer)? {
+        officer_commission =
+            (interest_accrued as u128 * loan_officer.commission_bps as u128 / 10000) as u64;
+        officer_commission = officer_commission.min(net_payment);
+
+        if officer_commission > 0 {
+            let officer_token_account = ctx
+                .accounts
+                .officer_token_account
+                .as_ref()
+                .filter(|token_account| token_account.owner == loan_officer.officer)
+                .ok_or(MicroLendingError::LoanOfficerAccountRequired)?;
+
+            let transfer_to_officer = TransferChecked {
+                from: ctx.accounts.borrower_token_account.to_account_info(),
+                to: officer_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.borrower.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, transfer_to_officer);
+            transfer_checked(cpi_ctx, officer_commission, ctx.accounts.mint.decimals)?;
+        }
+
+        let principal_paid = settlement.principal_paid;
+        loan_officer.outstanding_principal = loan_officer
+            .outstanding_principal
+            .saturating_sub(principal_paid);
+        loan_officer.commission_earned = loan_officer
+            .commission_earned
+            .saturating_add(officer_commission);
+        if loan.amount_repaid.saturating_add(principal_paid) >= loan.amount
+            && is_loan_overdue(loan.due_date, current, loan.grace_period_days)
+        {
+            loan_officer.late_repayments = loan_officer.late_repayments.saturating_add(1);
+        }
+        msg!("Loan officer commission : {}", officer_commission);
+    }
+    let pool_payment = net_payment.checked_sub(officer_commission).unwrap();
+    let lender_interest = lender_charges.saturating_sub(officer_commission);
 
-    // Calculate platform fee
-    let platform_fee = (payment_amount as u128 * platform.platform_fee as u128 / 10000) as u64;
-    msg!("Platform fee charged : {}", platform_fee);
-    let net_payment = payment_amount.checked_sub(platform_fee).unwrap();
-    msg!("Net Payment after platform fee : {}", net_payment);
     // Transfer payment from borrower to pool
     let transfer_to_pool = TransferChecked {
         from: ctx.accounts.borrower_token_account.to_account_info(),
@@ -72,7 +113,7 @@ pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new(cpi_program, transfer_to_pool);
     let decimal = ctx.accounts.mint.decimals;
-    transfer_checked(cpi_ctx, net_payment, decimal)?;
+    transfer_checked(cpi_ctx, pool_payment, decimal)?;
 
     // Transfer platform fee to treasury if applicable
     let transfer_to_treasury = TransferChecked {
@@ -88,7 +129,7 @@ pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()
 
     transfer_checked(cpi_ctx, platform_fee, decimal)?;
 
-    let net_deduction_in_borrowed_amount = net_payment.checked_sub(interest_accrued).unwrap();
+    let net_deduction_in_borrowed_amount = settlement.principal_paid;
     msg!(
         "Net deduction in borrowed amount after interest : {}",
         net_deduction_in_borrowed_amount
@@ -100,6 +141,33 @@ pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()
         .unwrap();
     loan.payment_count = loan.payment_count.checked_add(1).unwrap();
     loan.last_payment_date = current;
+    // The interest accrued so far is paid, so accrue afresh, at any new rate, from here.
+    // Only whole days are charged, so the partial day carries over.
+    loan.accrual_start += days_elapsed as i64 * 86400;
+    loan.carried_interest = 0;
+
+    // Track on-time streaks; installments below the scheduled size do not count
+    if is_loan_overdue(loan.due_date, current, loan.grace_period_days) {
+        loan.on_time_streak = 0;
+        user_profile.on_time_streak = 0;
+    } else if payment_amount >= expected_installment(loan) {
+        loan.on_time_streak = loan.on_time_streak.saturating_add(1);
+        user_profile.on_time_streak = user_profile.on_time_streak.saturating_add(1);
+        if let Some(old_interest_rate) =
+            step_down_interest_rate(loan, &lending_pool.repayment_incentives)
+        {
+            msg!("Interest rate stepped down to : {}", loan.interest_rate);
+            emit!(InterestRateSteppedDown {
+                loan: loan.key(),
+                borrower: loan.borrower,
+                on_time_streak: loan.on_time_streak,
+                old_interest_rate,
+                new_interest_rate: loan.interest_rate,
+                timestamp: current,
+            });
+        }
+    }
+    user_profile.last_activity_at = current;
 
     // Check if loan is fully repaid
     if loan.amount_repaid >= loan.amount {
@@ -108,6 +176,24 @@ pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()
         // Update user profile
         user_profile.active_loans = user_profile.active_loans.saturating_sub(1);
         user_profile.successful_loans = user_profile.successful_loans.checked_add(1).unwrap();
+        let outcome = if is_loan_overdue(loan.due_date, current, loan.grace_period_days) {
+            LoanOutcome::RepaidLate
+        } else {
+            LoanOutcome::RepaidOnTime
+        };
+        record_loan_outcome(user_profile, outcome, current);
+        step_borrowing_limit(user_profile, platform, outcome);
+        settle_backing_attesters(&loan.backing_attesters, ctx.remaining_accounts, true)?;
+        emit!(LoanRepaid {
+            loan: loan.key(),
+            borrower: loan.borrower,
+            pool: lending_pool.key(),
+            amount: loan.amount,
+            amount_repaid: loan.amount_repaid,
+            outcome,
+            payment_count: loan.payment_count,
+            timestamp: current,
+        });
 
         user_profile.total_repaid = user_profile.total_repaid.checked_add(net_payment).unwrap();
         // Update lending pool
@@ -118,7 +204,7 @@ pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()
         );
         lending_pool.available_liquidity = lending_pool
             .available_liquidity
-            .checked_add(net_payment)
+            .checked_add(pool_payment)
             .unwrap();
 
         lending_pool.total_borrowed = lending_pool
@@ -131,12 +217,15 @@ pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()
             lending_pool.available_liquidity
         );
 
-        msg!("Total Borrwed reduced to : {}", lending_pool.total_borrowed);
+        msg!(
+            "Total Borrowed reduced to : {}",
+            lending_pool.total_borrowed
+        );
 
-        if interest_accrued > 0 && lending_pool.total_shares > 0 {
+        if lender_interest > 0 && lending_pool.total_shares > 0 {
             // Calculate interest per share (scaled by 1e9 for precision)
             let interest_per_share_increase =
-                (interest_accrued as u128 * 1_000_000_000) / lending_pool.total_shares as u128;
+                (lender_interest as u128 * 1_000_000_000) / lending_pool.total_shares as u128;
 
             lending_pool.interest_per_share = lending_pool
                 .interest_per_share
@@ -146,7 +235,7 @@ pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()
 
         lending_pool.total_interest_earned = lending_pool
             .total_interest_earned
-            .checked_add(interest_accrued)
+            .checked_add(lender_interest)
             .unwrap();
 
         msg!("Loan fully repaid by borrower: {}", loan.borrower);
@@ -171,7 +260,7 @@ pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()
 
         lending_pool.available_liquidity = lending_pool
             .available_liquidity
-            .checked_add(net_payment)
+            .checked_add(pool_payment)
             .unwrap();
 
         msg!(
@@ -180,10 +269,10 @@ pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()
             payment_amount
         );
 
-        if interest_accrued > 0 && lending_pool.total_shares > 0 {
+        if lender_interest > 0 && lending_pool.total_shares > 0 {
             // Calculate interest per share (scaled by 1e9 for precision)
             let interest_per_share_increase =
-                (interest_accrued as u128 * 1_000_000_000) / lending_pool.total_shares as u128;
+                (lender_interest as u128 * 1_000_000_000) / lending_pool.total_shares as u128;
 
             lending_pool.interest_per_share = lending_pool
                 .interest_per_share
@@ -193,10 +282,26 @@ pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()
 
         lending_pool.total_interest_earned = lending_pool
             .total_interest_earned
-            .checked_add(interest_accrued)
+            .checked_add(lender_interest)
             .unwrap();
     }
 
+    emit!(PaymentMade {
+        loan: loan.key(),
+        borrower: loan.borrower,
+        pool: lending_pool.key(),
+        payment_amount,
+        platform_fee,
+        interest_paid: interest_accrued,
+        late_fee,
+        principal_paid: net_deduction_in_borrowed_amount,
+        officer_commission,
+        outstanding_before,
+        outstanding_after: loan.amount.saturating_sub(loan.amount_repaid),
+        pool_liquidity_before,
+        pool_liquidity_after: lending_pool.available_liquidity,
+        timestamp: current,
+    });
     Ok(())
 }
 
@@ -256,6 +361,16 @@ pub struct MakePayment<'info> {
     pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account(mut)]
     pub borrower: Signer<'info>,
+
+    #[account(mut)]
+    pub loan_officer: Option<Account<'info, LoanOfficer>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub officer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
 }
@@ -0,0 +1,337 @@
+use crate::error::*;
+use crate::events::*;
+use crate::legacy::*;
+use crate::states::*;
+use crate::{calculate_loan_payment, ALL_COLLATERAL_TYPES};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+// Rewrite an account created under its first layout in the current one, growing it to the
+// current size. Anyone may migrate an account other than a pool; the payer covers the extra
+// rent. A pool is migrated by its authority, passing its Approved loans as remaining accounts.
+pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account = ctx.accounts.account.to_account_info();
+    let current = Clock::get()?.unix_timestamp;
+
+    let (account_type, new_space, new_data) = {
+        let data = account.try_borrow_data()?;
+        require!(data.len() >= 8, MicroLendingError::AccountNotMigratable);
+        let (discriminator, fields) = data.split_at(8);
+        let is_legacy = |discriminator_v1: &[u8], space_v1: usize| {
+            discriminator == discriminator_v1 && data.len() == 8 + space_v1
+        };
+
+        if is_legacy(Platform::DISCRIMINATOR, PlatformV1::INIT_SPACE) {
+            (
+                MigratedAccountType::Platform,
+                8 + Platform::INIT_SPACE,
+                rewrite(fields, |legacy| Ok(migrate_platform(legacy)))?,
+            )
+        } else if is_legacy(UserProfile::DISCRIMINATOR, UserProfileV1::INIT_SPACE) {
+            (
+                MigratedAccountType::UserProfile,
+                8 + UserProfile::INIT_SPACE,
+                rewrite(fields, |legacy| Ok(migrate_user_profile(legacy)))?,
+            )
+        } else if is_legacy(LendingPool::DISCRIMINATOR, LendingPoolV1::INIT_SPACE) {
+            let total_reserved = reserved_for_approvals(&account.key(), ctx.remaining_accounts)?;
+            (
+                MigratedAccountType::LendingPool,
+                8 + LendingPool::INIT_SPACE,
+                rewrite(fields, |legacy: LendingPoolV1| {
+                    // The authority vouches that the loans passed are all of the pool's approvals
+                    require!(
+                        ctx.accounts.payer.key() == legacy.authority,
+                        MicroLendingError::Unauthorized
+                    );
+                    Ok(migrate_lending_pool(legacy, total_reserved))
+                })?,
+            )
+        } else if is_legacy(Loan::DISCRIMINATOR, LoanV1::INIT_SPACE) {
+            (
+                MigratedAccountType::Loan,
+                8 + Loan::INIT_SPACE,
+                rewrite(fields, |legacy| migrate_loan(legacy, current))?,
+            )
+        } else if is_legacy(
+            SocialAttestation::DISCRIMINATOR,
+            SocialAttestationV1::INIT_SPACE,
+        ) {
+            (
+                MigratedAccountType::SocialAttestation,
+                8 + SocialAttestation::INIT_SPACE,
+                rewrite(fields, |legacy| Ok(migrate_social_attestation(legacy)))?,
+            )
+        } else {
+            return err!(MicroLendingError::AccountNotMigratable);
+        }
+    };
+    let old_space = account.data_len();
+
+    // Keep the grown account rent exempt
+    let rent = Rent::get()?.minimum_balance(new_space);
+    let top_up = rent.saturating_sub(account.lamports());
+    if top_up > 0 {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: account.clone(),
+            },
+        );
+        transfer(cpi_ctx, top_up)?;
+    }
+
+    account.resize(new_space)?;
+    let mut data = account.try_borrow_mut_data()?;
+    data.fill(0);
+    data[..new_data.len()].copy_from_slice(&new_data);
+
+    msg!("Account migrated: {}", account.key());
+    emit!(AccountMigrated {
+        account: account.key(),
+        account_type,
+        old_space: old_space as u32,
+        new_space: new_space as u32,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+// Read the fields after the discriminator in the first layout, and serialize the migrated
+// account with its discriminator
+fn rewrite<L: AnchorDeserialize, T: AccountSerialize>(
+    mut fields: &[u8],
+    migrate: impl FnOnce(L) -> Result<T>,
+) -> Result<Vec<u8>> {
+    let legacy = L::deserialize(&mut fields)?;
+    let mut data = Vec::new();
+    migrate(legacy)?.try_serialize(&mut data)?;
+    Ok(data)
+}
+
+// New settings take the values initialize_platform gives them, with recency weighting and
+// decay left off
+pub fn migrate_platform(legacy: PlatformV1) -> Platform {
+    Platform {
+        authority: legacy.authority,
+        treasury: legacy.treasury,
+        treasury_bump: legacy.treasury_bump,
+        platform_fee: legacy.platform_fee,
+        max_loan_amount: legacy.max_loan_amount,
+        min_loan_amount: legacy.min_loan_amount,
+        total_loans_issued: legacy.total_loans_issued,
+        total_volume: legacy.total_volume,
+        total_defaults: legacy.total_defaults,
+        is_active: legacy.is_active,
+        created_at: legacy.created_at,
+        score_half_life_days: 0,
+        inactivity_threshold_days: 0,
+        verifiers: Vec::new(),
+        lending_ladder: LendingLadder {
+            first_loan_cap: 0,
+            step_up_bps: 10000,
+            step_down_bps: 10000,
+        },
+    }
+}
+
+// The first layout had no way to set verifications, so none carry over. Activity counts
+// from the last update, so inactivity decay doesn't apply to the whole time before migrating.
+pub fn migrate_user_profile(legacy: UserProfileV1) -> UserProfile {
+    UserProfile {
+        owner: legacy.owner,
+        credit_score: legacy.credit_score,
+        total_borrowed: legacy.total_borrowed,
+        total_repaid: legacy.total_repaid,
+        active_loans: legacy.active_loans,
+        successful_loans: legacy.successful_loans,
+        defaulted_loans: legacy.defaulted_loans,
+        reputation_score: legacy.reputation_score,
+        created_at: legacy.created_at,
+        last_updated: legacy.last_updated,
+        kyc_verified: legacy.kyc_verified,
+        phone_verified: legacy.phone_verified,
+        email_verified: legacy.email_verified,
+        kyc_expires_at: None,
+        phone_expires_at: None,
+        email_expires_at: None,
+        transaction_history_count: legacy.transaction_history_count,
+        social_attestations_count: legacy.social_attestations_count,
+        last_activity_at: legacy.last_updated,
+        recent_outcomes: Vec::new(),
+        restructured_loans: 0,
+        borrowing_limit: 0,
+        on_time_streak: 0,
+        kyc_verifier: Pubkey::default(),
+        phone_verifier: Pubkey::default(),
+        email_verifier: Pubkey::default(),
+    }
+}
+
+// Liquidity the first layout took out of available_liquidity for approved loans not yet
+// disbursed. Only the loans themselves record it, so each must be one of the pool's
+// Approved loans, in either layout.
+pub fn reserved_for_approvals(pool: &Pubkey, loans: &[AccountInfo]) -> Result<u64> {
+    let mut counted: Vec<Pubkey> = Vec::new();
+    let mut total_reserved = 0u64;
+    for account_info in loans {
+        if counted.contains(account_info.key) {
+            continue;
+        }
+        require!(
+            account_info.owner == &crate::ID,
+            MicroLendingError::InvalidLoanState
+        );
+        let data = account_info.try_borrow_data()?;
+        let (lender_pool, status, amount) = if data.len() == 8 + LoanV1::INIT_SPACE {
+            require!(
+                data.starts_with(Loan::DISCRIMINATOR),
+                MicroLendingError::InvalidLoanState
+            );
+            let legacy = LoanV1::deserialize(&mut &data[8..])?;
+            (legacy.lender_pool, legacy.status, legacy.amount)
+        } else {
+            let loan = Loan::try_deserialize(&mut &data[..])?;
+            (loan.lender_pool, loan.status, loan.amount)
+        };
+        require!(
+            lender_pool == *pool && status == LoanStatus::Approved,
+            MicroLendingError::InvalidLoanState
+        );
+        total_reserved = total_reserved.checked_add(amount).unwrap();
+        counted.push(account_info.key());
+    }
+    Ok(total_reserved)
+}
+
+// New settings take the values create_lending_pool gives them. The first layout's approvals
+// already came out of available_liquidity, so total_reserved carries them.
+pub fn migrate_lending_pool(legacy: LendingPoolV1, total_reserved: u64) -> LendingPool {
+    LendingPool {
+        authority: legacy.authority,
+        mint: legacy.mint,
+        token_account: legacy.token_account,
+        name: legacy.name,
+        base_interest_rate: legacy.base_interest_rate,
+        max_loan_duration: legacy.max_loan_duration,
+        total_deposited: legacy.total_deposited,
+        total_borrowed: legacy.total_borrowed,
+        total_shares: legacy.total_shares,
+        available_liquidity: legacy.available_liquidity,
+        active_loans: legacy.active_loans,
+        total_interest_earned: legacy.total_interest_earned,
+        total_interest_distributed: legacy.total_interest_distributed,
+        interest_per_share: legacy.interest_per_share,
+        is_active: legacy.is_active,
+        created_at: legacy.created_at,
+        borrower_policy: BorrowerPolicy {
+            min_credit_score: 0,
+            required_verifications: 0,
+            min_attestations: 0,
+            allowed_collateral_types: ALL_COLLATERAL_TYPES,
+            max_loan_per_borrower: 0,
+            allowed_purposes: Vec::new(),
+        },
+        total_reserved,
+        auto_approval: AutoApprovalRules {
+            enabled: false,
+            max_utilization_bps: 0,
+            score_bands: Vec::new(),
+        },
+        request_ttl: 0,
+        approval_ttl: 0,
+        committee: None,
+        disaster_declared_at: 0,
+        disaster_forbearance_until: 0,
+        repayment_incentives: RepaymentIncentives {
+            streak_length: 0,
+            rate_step_down_bps: 0,
+            min_interest_rate: 0,
+            early_payoff_rebate_bps: 0,
+        },
+    }
+}
+
+// Each payment under the first layout settled the interest to date, so interest accrues
+// again from the last payment. Pending approvals start their TTL at the migration.
+pub fn migrate_loan(legacy: LoanV1, current_time: i64) -> Result<Loan> {
+    let disbursed = matches!(legacy.status, LoanStatus::Disbursed | LoanStatus::Active);
+    let scheduled_repayment = if disbursed {
+        calculate_loan_payment(legacy.amount, legacy.interest_rate, legacy.duration_days)?
+    } else {
+        0
+    };
+    Ok(Loan {
+        borrower: legacy.borrower,
+        lender_pool: legacy.lender_pool,
+        amount: legacy.amount,
+        interest_rate: legacy.interest_rate,
+        duration_days: legacy.duration_days,
+        disbursed_at: legacy.disbursed_at,
+        due_date: legacy.due_date,
+        amount_repaid: legacy.amount_repaid,
+        interest_accrued: legacy.interest_accrued,
+        status: legacy.status,
+        purpose: legacy.purpose,
+        collateral_type: legacy.collateral_type,
+        collateral_value: legacy.collateral_value,
+        payment_count: legacy.payment_count,
+        last_payment_date: legacy.last_payment_date,
+        grace_period_days: legacy.grace_period_days,
+        late_fee_rate: legacy.late_fee_rate,
+        created_at: legacy.created_at,
+        liquidated_at: legacy.liquidated_at,
+        backing_attesters: Vec::new(),
+        approved_at: if legacy.status == LoanStatus::Approved {
+            current_time
+        } else {
+            0
+        },
+        committee_votes: Vec::new(),
+        loan_officer: None,
+        accrual_start: if legacy.payment_count > 0 {
+            legacy.last_payment_date
+        } else {
+            legacy.disbursed_at
+        },
+        scheduled_repayment,
+        restructure_count: 0,
+        restructured_at: None,
+        forbearance_until: 0,
+        forbearance_count: 0,
+        refinanced: false,
+        refinanced_from: None,
+        on_time_streak: 0,
+        rate_step_downs: 0,
+        carried_interest: 0,
+    })
+}
+
+// Every attestation under the first layout was counted in its user's profile
+pub fn migrate_social_attestation(legacy: SocialAttestationV1) -> SocialAttestation {
+    SocialAttestation {
+        user: legacy.user,
+        attester: legacy.attester,
+        attestation_type: legacy.attestation_type,
+        score: legacy.score,
+        metadata: legacy.metadata,
+        verified: legacy.verified,
+        created_at: legacy.created_at,
+        expires_at: legacy.expires_at,
+        is_active: true,
+        updated_at: legacy.created_at,
+    }
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: read in the first layout its discriminator and size identify
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,31 +1,89 @@
 pub mod add_attestation;
 pub mod add_transaction_history;
+pub mod add_verifier;
 pub mod approve_loan;
+pub mod cancel_loan_request;
 pub mod claim_interest;
 pub mod create_lending_pool;
+pub mod create_loan_committee;
+pub mod declare_disaster;
 pub mod deposit_to_pool;
 pub mod disburse_loan;
+pub mod expire_attestation;
+pub mod expire_loan;
+pub mod grant_forbearance;
 pub mod initialize_platform;
 pub mod initialize_user;
 pub mod liquidate_loan;
 pub mod make_payments;
+pub mod migrate_account;
+pub mod quote;
+pub mod refinance_loan;
+pub mod register_attester;
+pub mod register_loan_officer;
+pub mod reject_loan;
+pub mod remove_verifier;
+pub mod renew_attestation;
 pub mod request_loan;
+pub mod restructure_loan;
+pub mod revoke_attestation;
+pub mod set_auto_approval_rules;
+pub mod set_borrower_policy;
+pub mod set_lending_ladder;
+pub mod set_loan_ttls;
+pub mod set_pool_verification_requirements;
+pub mod set_repayment_incentives;
+pub mod set_verification;
+pub mod update_attestation;
+pub mod update_attester;
 pub mod update_credit_score;
+pub mod update_loan_committee;
+pub mod update_loan_officer;
 pub mod utils;
+pub mod vote_on_loan;
 pub mod withdraw_from_pool;
 
 pub use add_attestation::*;
 pub use add_transaction_history::*;
+pub use add_verifier::*;
 pub use approve_loan::*;
+pub use cancel_loan_request::*;
 pub use claim_interest::*;
 pub use create_lending_pool::*;
+pub use create_loan_committee::*;
+pub use declare_disaster::*;
 pub use deposit_to_pool::*;
 pub use disburse_loan::*;
+pub use expire_attestation::*;
+pub use expire_loan::*;
+pub use grant_forbearance::*;
 pub use initialize_platform::*;
 pub use initialize_user::*;
 pub use liquidate_loan::*;
 pub use make_payments::*;
+pub use migrate_account::*;
+pub use quote::*;
+pub use refinance_loan::*;
+pub use register_attester::*;
+pub use register_loan_officer::*;
+pub use reject_loan::*;
+pub use remove_verifier::*;
+pub use renew_attestation::*;
 pub use request_loan::*;
+pub use restructure_loan::*;
+pub use revoke_attestation::*;
+pub use set_auto_approval_rules::*;
+pub use set_borrower_policy::*;
+pub use set_lending_ladder::*;
+pub use set_loan_ttls::*;
+pub use set_pool_verification_requirements::*;
+pub use set_repayment_incentives::*;
+pub use set_verification::*;
+pub use update_attestation::*;
+pub use update_attester::*;
 pub use update_credit_score::*;
+pub use update_loan_committee::*;
+pub use update_loan_officer::*;
 pub use utils::*;
+pub use vote_on_loan::*;
 pub use withdraw_from_pool::*;
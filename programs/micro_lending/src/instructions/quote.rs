@@ -0,0 +1,307 @@
+use crate::error::*;
+use crate::states::*;
+use crate::utils::*;
+use crate::{get_unclaimed_interest, SEEDS_PLATFORM, SEEDS_USER};
+use anchor_lang::prelude::*;
+
+// Read-only quotes. Each instruction returns its quote as return data, so clients can
+// simulate it and get the exact numbers the corresponding instruction would use.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoanQuote {
+    pub interest_rate: u16,
+    pub total_repayment: u64,
+    pub required_credit_score: u16,
+    pub borrower_limit: u64,
+    // Error code of the first check request_loan would fail on, if any
+    pub rejection_code: Option<u32>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PayoffQuote {
+    pub outstanding_principal: u64,
+    pub interest_accrued: u64,
+    pub early_payoff_rebate: u64,
+    pub late_fee: u64,
+    pub total_due: u64,
+    pub platform_fee: u64, // charged on top of total_due, so a payoff pays both
+    pub in_forbearance: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WithdrawalQuote {
+    pub principal_amount: u64,
+    pub interest_amount: u64,
+    pub total_amount: u64,
+    pub sufficient_liquidity: bool,
+}
+
+// First check request_loan would fail on, given how many of the borrower's currently-valid
+// attestations are verified
+#[allow(clippy::too_many_arguments)]
+pub fn loan_rejection(
+    platform: &Platform,
+    lending_pool: &LendingPool,
+    user_profile: &UserProfile,
+    amount: u64,
+    duration_days: u32,
+    purpose: &str,
+    collateral_type: u8,
+    verified_attestations: usize,
+    current_time: i64,
+) -> Result<Option<MicroLendingError>> {
+    let required_credit_score = calculate_required_credit_score(amount, platform.max_loan_amount)?;
+    let rejection = if !lending_pool.is_active {
+        Some(MicroLendingError::PoolNotActive)
+    } else if amount < platform.min_loan_amount {
+        Some(MicroLendingError::LoanAmountTooLow)
+    } else if amount > platform.max_loan_amount {
+        Some(MicroLendingError::LoanAmountTooHigh)
+    } else if duration_days as i64 > lending_pool.max_loan_duration {
+        Some(MicroLendingError::LoanDurationTooLong)
+    } else if purpose.len() > 100 {
+        Some(MicroLendingError::InvalidPoolConfiguration)
+    } else if user_profile.active_loans != 0 {
+        Some(MicroLendingError::BorrowerHasActiveLoan)
+    } else if lending_pool.available_liquidity < amount {
+        Some(MicroLendingError::InsufficientLiquidity)
+    } else if user_profile.credit_score < required_credit_score {
+        Some(MicroLendingError::InsufficientCreditScore)
+    } else if amount > borrower_loan_limit(user_profile, &platform.lending_ladder) {
+        // Borrowers start small and move up the lending ladder with each on-time repayment
+        Some(MicroLendingError::LoanExceedsBorrowerLimit)
+    } else {
+        match collateral_type_from_u8(collateral_type) {
+            None => Some(MicroLendingError::InvalidCollateralType),
+            Some(CollateralType::Social) if verified_attestations == 0 => {
+                Some(MicroLendingError::InsufficientAttestations)
+            }
+            // Apply the pool's own eligibility policy
+            Some(collateral_type) => borrower_policy_rejection(
+                &lending_pool.borrower_policy,
+                user_profile,
+                &platform.verifiers,
+                amount,
+                collateral_type,
+                purpose,
+                verified_attestations,
+                current_time,
+            ),
+        }
+    };
+    Ok(rejection)
+}
+
+// Terms request_loan would assign
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_loan_quote(
+    platform: &Platform,
+    lending_pool: &LendingPool,
+    user_profile: &UserProfile,
+    amount: u64,
+    duration_days: u32,
+    purpose: &str,
+    collateral_type: u8,
+    verified_attestations: usize,
+    current_time: i64,
+) -> Result<LoanQuote> {
+    let interest_rate = calculate_interest_rate(
+        user_profile.credit_score,
+        lending_pool.base_interest_rate,
+        duration_days,
+    )?;
+    let rejection = loan_rejection(
+        platform,
+        lending_pool,
+        user_profile,
+        amount,
+        duration_days,
+        purpose,
+        collateral_type,
+        verified_attestations,
+        current_time,
+    )?;
+
+    Ok(LoanQuote {
+        interest_rate,
+        total_repayment: calculate_loan_payment(amount, interest_rate, duration_days)?,
+        required_credit_score: calculate_required_credit_score(amount, platform.max_loan_amount)?,
+        borrower_limit: borrower_loan_limit(user_profile, &platform.lending_ladder),
+        rejection_code: rejection.map(u32::from),
+    })
+}
+
+// Amount a single repay_loan call at `current_time` would need to clear the loan:
+// total_due plus platform_fee
+pub fn calculate_payoff_quote(
+    platform: &Platform,
+    lending_pool: &LendingPool,
+    loan: &Loan,
+    current_time: i64,
+) -> Result<PayoffQuote> {
+    let mut loan = loan.clone();
+    sync_disaster_forbearance(&mut loan, lending_pool);
+
+    let outstanding_principal = loan.amount.checked_sub(loan.amount_repaid).unwrap();
+    let days_elapsed = days_between(loan.accrual_start, current_time);
+    let mut interest_accrued =
+        calculate_simple_interest(outstanding_principal, loan.interest_rate, days_elapsed)?
+            .checked_add(loan.carried_interest)
+            .unwrap();
+
+    // A full payoff before the due date always qualifies for the pool's rebate
+    let mut early_payoff_rebate = 0u64;
+    let rebate_bps = lending_pool.repayment_incentives.early_payoff_rebate_bps;
+    if rebate_bps > 0 && current_time < loan.due_date {
+        early_payoff_rebate = (interest_accrued as u128 * rebate_bps as u128 / 10000) as u64;
+        interest_accrued -= early_payoff_rebate;
+    }
+
+    let total_owed = outstanding_principal.checked_add(interest_accrued).unwrap();
+    let in_forbearance = is_in_forbearance(&loan, current_time);
+    let mut late_fee = 0u64;
+    if !in_forbearance && is_loan_overdue(loan.due_date, current_time, loan.grace_period_days) {
+        let days_overdue = days_between(loan.due_date, current_time);
+        late_fee = calculate_late_fee(total_owed, loan.late_fee_rate, days_overdue)?;
+    }
+
+    let total_due = total_owed.checked_add(late_fee).unwrap();
+    let platform_fee = (total_due as u128 * platform.platform_fee as u128 / 10000) as u64;
+
+    Ok(PayoffQuote {
+        outstanding_principal,
+        interest_accrued,
+        early_payoff_rebate,
+        late_fee,
+        total_due,
+        platform_fee,
+        in_forbearance,
+    })
+}
+
+// Tokens withdraw_from_pool would pay out for `shares_to_withdraw`, including unclaimed interest
+pub fn calculate_withdrawal_quote(
+    lender_deposit: &LenderDeposit,
+    lending_pool: &LendingPool,
+    shares_to_withdraw: u64,
+) -> WithdrawalQuote {
+    let interest_amount = get_unclaimed_interest(lender_deposit, lending_pool);
+    let principal_amount = calculate_share_value(lending_pool, shares_to_withdraw);
+    let total_amount = principal_amount.saturating_add(interest_amount);
+
+    WithdrawalQuote {
+        principal_amount,
+        interest_amount,
+        total_amount,
+        sufficient_liquidity: lending_pool.available_liquidity >= total_amount,
+    }
+}
+
+// The borrower's attestations are passed as remaining accounts, as for request_loan
+pub fn quote_loan<'info>(
+    ctx: Context<'_, '_, 'info, 'info, QuoteLoan<'info>>,
+    amount: u64,
+    duration_days: u32,
+    purpose: String,
+    collateral_type: u8,
+) -> Result<LoanQuote> {
+    let current = Clock::get()?.unix_timestamp;
+    let verified_attestations = load_verified_attestations(
+        ctx.remaining_accounts,
+        &ctx.accounts.user_profile.owner,
+        current,
+    )?
+    .len();
+    calculate_loan_quote(
+        &ctx.accounts.platform,
+        &ctx.accounts.lending_pool,
+        &ctx.accounts.user_profile,
+        amount,
+        duration_days,
+        &purpose,
+        collateral_type,
+        verified_attestations,
+        current,
+    )
+}
+
+pub fn quote_payoff(ctx: Context<QuotePayoff>) -> Result<PayoffQuote> {
+    let loan = &ctx.accounts.loan;
+    require!(
+        loan.status == LoanStatus::Disbursed || loan.status == LoanStatus::Active,
+        MicroLendingError::InvalidLoanState
+    );
+    calculate_payoff_quote(
+        &ctx.accounts.platform,
+        &ctx.accounts.lending_pool,
+        loan,
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+pub fn quote_withdrawal(
+    ctx: Context<QuoteLenderPosition>,
+    shares_to_withdraw: u64,
+) -> Result<WithdrawalQuote> {
+    require!(
+        ctx.accounts.lender_deposit.shares >= shares_to_withdraw,
+        MicroLendingError::InsufficientShares
+    );
+    Ok(calculate_withdrawal_quote(
+        &ctx.accounts.lender_deposit,
+        &ctx.accounts.lending_pool,
+        shares_to_withdraw,
+    ))
+}
+
+pub fn quote_pending_interest(ctx: Context<QuoteLenderPosition>) -> Result<u64> {
+    Ok(get_unclaimed_interest(
+        &ctx.accounts.lender_deposit,
+        &ctx.accounts.lending_pool,
+    ))
+}
+
+#[derive(Accounts)]
+pub struct QuoteLoan<'info> {
+    #[account(
+        seeds = [SEEDS_PLATFORM],
+        bump
+    )]
+    pub platform: Account<'info, Platform>,
+
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        seeds = [SEEDS_USER, user_profile.owner.as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+}
+
+#[derive(Accounts)]
+pub struct QuotePayoff<'info> {
+    #[account(
+        seeds = [SEEDS_PLATFORM],
+        bump
+    )]
+    pub platform: Account<'info, Platform>,
+
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        seeds = [b"loan", loan.borrower.as_ref(), lending_pool.key().as_ref()],
+        bump
+    )]
+    pub loan: Account<'info, Loan>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteLenderPosition<'info> {
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        seeds = [b"lender_deposit", lender_deposit.lender.as_ref(), lending_pool.key().as_ref()],
+        bump
+    )]
+    pub lender_deposit: Account<'info, LenderDeposit>,
+}
@@ -0,0 +1,420 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::*;
+use crate::{calculate_payoff_quote, MAX_BACKING_ATTESTERS, SEEDS_PLATFORM, SEEDS_USER};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+// The follow-on loan takes over the loan PDA; the paid-off loan is archived as Repaid.
+// Remaining accounts are the borrower's attestations backing the new loan, then the old
+// loan's attester registries to settle, then, in committee pools, the approving members,
+// who co-sign.
+pub fn refinance_loan<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefinanceLoan<'info>>,
+    amount: u64,
+    duration_days: u32,
+) -> Result<()> {
+    let platform = &ctx.accounts.platform;
+    let loan = &mut ctx.accounts.loan;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let user_profile = &mut ctx.accounts.user_profile;
+    let current = Clock::get()?.unix_timestamp;
+
+    require!(
+        loan.status == LoanStatus::Disbursed || loan.status == LoanStatus::Active,
+        MicroLendingError::InvalidLoanState
+    );
+    apply_disaster_forbearance(loan, lending_pool, current);
+    require!(
+        !is_loan_overdue(loan.due_date, current, loan.grace_period_days),
+        MicroLendingError::RefinanceNotAllowed
+    );
+
+    // A follow-on loan is a new credit decision, so committee pools need the quorum
+    if let Some(committee_key) = lending_pool.committee {
+        let committee = ctx
+            .accounts
+            .loan_committee
+            .as_ref()
+            .filter(|committee| committee.key() == committee_key)
+            .ok_or(MicroLendingError::CommitteeRequired)?;
+        let mut approvers: Vec<Pubkey> = Vec::new();
+        for account_info in ctx.remaining_accounts {
+            if account_info.is_signer
+                && committee.members.contains(account_info.key)
+                && !approvers.contains(account_info.key)
+            {
+                approvers.push(*account_info.key);
+            }
+        }
+        require!(
+            approvers.len() >= committee.quorum as usize,
+            MicroLendingError::QuorumNotReached
+        );
+    }
+
+    // Only currently-valid, verified attestations back the new loan
+    let verified_attesters: Vec<Pubkey> = load_verified_attestations(
+        ctx.remaining_accounts,
+        &ctx.accounts.borrower.key(),
+        current,
+    )?
+    .iter()
+    .map(|attestation| attestation.attester)
+    .collect();
+    let (_, registry_accounts) = split_attestation_accounts(ctx.remaining_accounts);
+
+    // Re-underwrite the follow-on loan against the borrower's current standing
+    require!(
+        amount >= platform.min_loan_amount,
+        MicroLendingError::LoanAmountTooLow
+    );
+    require!(
+        amount <= platform.max_loan_amount,
+        MicroLendingError::LoanAmountTooHigh
+    );
+    require!(
+        duration_days as i64 <= lending_pool.max_loan_duration,
+        MicroLendingError::LoanDurationTooLong
+    );
+    let required_credit_score = calculate_required_credit_score(amount, platform.max_loan_amount)?;
+    require!(
+        user_profile.credit_score >= required_credit_score,
+        MicroLendingError::InsufficientCreditScore
+    );
+    require!(
+        amount <= borrower_loan_limit(user_profile, &platform.lending_ladder),
+        MicroLendingError::LoanExceedsBorrowerLimit
+    );
+    if loan.collateral_type == CollateralType::Social {
+        require!(
+            !verified_attesters.is_empty(),
+            MicroLendingError::InsufficientAttestations
+        );
+    }
+    check_borrower_policy(
+        &lending_pool.borrower_policy,
+        user_profile,
+        &platform.verifiers,
+        amount,
+        loan.collateral_type,
+        &loan.purpose,
+        verified_attesters.len(),
+        current,
+    )?;
+    let interest_rate = calculate_interest_rate(
+        user_profile.credit_score,
+        lending_pool.base_interest_rate,
+        duration_days,
+    )?;
+
+    // Pay off the current loan exactly as a full make_payment would, rebate included
+    let quote = calculate_payoff_quote(platform, lending_pool, loan, current)?;
+    let payoff_total = quote.total_due.checked_add(quote.platform_fee).unwrap();
+    require!(
+        amount > payoff_total,
+        MicroLendingError::RefinanceAmountTooLow
+    );
+    let settlement = settle_payment(&quote, platform.platform_fee, payoff_total)?;
+    let principal_outstanding = settlement.principal_paid;
+    let interest_accrued = settlement.interest_paid;
+    let lender_charges = interest_accrued.checked_add(settlement.late_fee).unwrap();
+    let payoff = principal_outstanding.checked_add(lender_charges).unwrap();
+    let platform_fee = settlement.platform_fee;
+    let net_disbursement = amount.checked_sub(payoff_total).unwrap();
+    msg!(
+        "Refinance payoff: {}, platform fee: {}, net disbursement: {}",
+        payoff,
+        platform_fee,
+        net_disbursement
+    );
+    if settlement.early_payoff_rebate > 0 {
+        msg!("Early payoff rebate : {}", settlement.early_payoff_rebate);
+        emit!(EarlyPayoffRebated {
+            loan: loan.key(),
+            borrower: loan.borrower,
+            rebate: settlement.early_payoff_rebate,
+            timestamp: current,
+        });
+    }
+
+    let mut officer_commission = 0u64;
+    if let Some(loan_officer) = loan_officer_for(loan, &mut ctx.accounts.loan_officer)? {
+        require!(
+            loan_officer.is_active,
+            MicroLendingError::LoanOfficerInactive
+        );
+        officer_commission =
+            (interest_accrued as u128 * loan_officer.commission_bps as u128 / 10000) as u64;
+        loan_officer.commission_earned = loan_officer
+            .commission_earned
+            .saturating_add(officer_commission);
+        loan_officer.loans_originated = loan_officer.loans_originated.saturating_add(1);
+        // The payoff never leaves the pool, so only the difference counts as disbursed
+        loan_officer.total_disbursed = loan_officer
+            .total_disbursed
+            .saturating_add(net_disbursement);
+        loan_officer.outstanding_principal = loan_officer
+            .outstanding_principal
+            .saturating_sub(principal_outstanding)
+            .saturating_add(amount);
+        update_officer_write_off_rate(loan_officer);
+    }
+    let pool_receipt = payoff.checked_sub(officer_commission).unwrap();
+    let lender_interest = lender_charges.checked_sub(officer_commission).unwrap();
+
+    require!(
+        lending_pool
+            .available_liquidity
+            .checked_add(pool_receipt)
+            .unwrap()
+            >= amount,
+        MicroLendingError::InsufficientLiquidity
+    );
+
+    // Only the fee, the commission and the net difference leave the pool
+    let pool_key = lending_pool.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"pool_token_account",
+        pool_key.as_ref(),
+        &[ctx.bumps.pool_token_account],
+    ]];
+    let decimals = ctx.accounts.mint.decimals;
+
+    let transfer_to_treasury = TransferChecked {
+        from: ctx.accounts.pool_token_account.to_account_info(),
+        to: ctx.accounts.treasury_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        authority: ctx.accounts.pool_token_account.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, transfer_to_treasury, signer_seeds);
+    transfer_checked(cpi_ctx, platform_fee, decimals)?;
+
+    if officer_commission > 0 {
+        let officer_token_account = ctx
+            .accounts
+            .officer_token_account
+            .as_ref()
+            .filter(|token_account| Some(token_account.owner) == loan.loan_officer)
+            .ok_or(MicroLendingError::LoanOfficerAccountRequired)?;
+
+        let transfer_to_officer = TransferChecked {
+            from: ctx.accounts.pool_token_account.to_account_info(),
+            to: officer_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.pool_token_account.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, transfer_to_officer, signer_seeds);
+        transfer_checked(cpi_ctx, officer_commission, decimals)?;
+    }
+
+    let transfer_to_borrower = TransferChecked {
+        from: ctx.accounts.pool_token_account.to_account_info(),
+        to: ctx.accounts.borrower_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        authority: ctx.accounts.pool_token_account.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, transfer_to_borrower, signer_seeds);
+    transfer_checked(cpi_ctx, net_disbursement, decimals)?;
+
+    // Archive the paid-off loan
+    let mut refinanced_loan = (**loan).clone();
+    refinanced_loan.status = LoanStatus::Repaid;
+    refinanced_loan.amount_repaid = refinanced_loan.amount;
+    refinanced_loan.interest_accrued = interest_accrued;
+    refinanced_loan.carried_interest = 0;
+    refinanced_loan.payment_count = refinanced_loan.payment_count.saturating_add(1);
+    refinanced_loan.last_payment_date = current;
+    refinanced_loan.refinanced = true;
+    ctx.accounts.refinanced_loan.set_inner(refinanced_loan);
+
+    // Pool accounting: repayment of the old loan, then disbursement of the new one
+    lending_pool.available_liquidity = lending_pool
+        .available_liquidity
+        .checked_add(pool_receipt)
+        .unwrap()
+        .checked_sub(amount)
+        .unwrap();
+    lending_pool.total_borrowed = lending_pool
+        .total_borrowed
+        .saturating_sub(principal_outstanding)
+        .checked_add(amount)
+        .unwrap();
+    if lender_interest > 0 && lending_pool.total_shares > 0 {
+        // Calculate interest per share (scaled by 1e9 for precision)
+        let interest_per_share_increase =
+            (lender_interest as u128 * 1_000_000_000) / lending_pool.total_shares as u128;
+
+        lending_pool.interest_per_share = lending_pool
+            .interest_per_share
+            .checked_add(interest_per_share_increase as u64)
+            .unwrap();
+    }
+    lending_pool.total_interest_earned = lending_pool
+        .total_interest_earned
+        .checked_add(lender_interest)
+        .unwrap();
+
+    // The old loan counts as successfully repaid; the borrower still has one active loan
+    user_profile.successful_loans = user_profile.successful_loans.checked_add(1).unwrap();
+    user_profile.total_repaid = user_profile.total_repaid.checked_add(payoff).unwrap();
+    user_profile.total_borrowed = user_profile.total_borrowed.checked_add(amount).unwrap();
+    user_profile.last_activity_at = current;
+    record_loan_outcome(user_profile, LoanOutcome::RepaidOnTime, current);
+    step_borrowing_limit(user_profile, platform, LoanOutcome::RepaidOnTime);
+
+    // The old loan's attesters are settled as for a repayment; the new loan is backed by
+    // the attestations valid now
+    settle_backing_attesters(&loan.backing_attesters, registry_accounts, true)?;
+    loan.backing_attesters = verified_attesters
+        .into_iter()
+        .take(MAX_BACKING_ATTESTERS)
+        .collect();
+    loan.amount = amount;
+    loan.interest_rate = interest_rate;
+    loan.duration_days = duration_days;
+    loan.disbursed_at = current;
+    loan.due_date = current + (duration_days as i64 * 86400);
+    loan.amount_repaid = 0;
+    loan.interest_accrued = 0;
+    loan.status = LoanStatus::Disbursed;
+    loan.payment_count = 0;
+    loan.last_payment_date = 0;
+    loan.created_at = current;
+    loan.liquidated_at = None;
+    loan.approved_at = current;
+    loan.committee_votes = Vec::new();
+    loan.accrual_start = current;
+    loan.scheduled_repayment = calculate_loan_payment(amount, interest_rate, duration_days)?;
+    loan.restructure_count = 0;
+    loan.restructured_at = None;
+    loan.forbearance_until = 0;
+    loan.forbearance_count = 0;
+    loan.refinanced = false;
+    loan.refinanced_from = Some(ctx.accounts.refinanced_loan.key());
+    loan.on_time_streak = 0;
+    loan.rate_step_downs = 0;
+    loan.carried_interest = 0;
+
+    msg!(
+        "Loan refinanced for borrower {}: {} tokens for {} days",
+        loan.borrower,
+        amount,
+        duration_days
+    );
+    emit!(LoanRefinanced {
+        loan: loan.key(),
+        refinanced_loan: ctx.accounts.refinanced_loan.key(),
+        borrower: loan.borrower,
+        pool: lending_pool.key(),
+        payoff,
+        platform_fee,
+        officer_commission,
+        new_amount: amount,
+        net_disbursement,
+        new_interest_rate: interest_rate,
+        due_date: loan.due_date,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefinanceLoan<'info> {
+    #[account(
+        seeds = [SEEDS_PLATFORM],
+        bump
+    )]
+    pub platform: Account<'info, Platform>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"loan", borrower.key().as_ref(), lending_pool.key().as_ref()],
+        bump
+    )]
+    pub loan: Account<'info, Loan>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Loan::INIT_SPACE,
+        seeds = [b"refinanced_loan", loan.key().as_ref(), loan.created_at.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub refinanced_loan: Account<'info, Loan>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.is_active @ MicroLendingError::PoolNotActive,
+        seeds = [b"lending_pool", authority.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        seeds = [b"loan_committee", lending_pool.key().as_ref()],
+        bump
+    )]
+    pub loan_committee: Option<Account<'info, LoanCommittee>>,
+
+    #[account(
+        mut,
+        seeds = [SEEDS_USER, borrower.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        constraint = pool_token_account.key() == lending_pool.token_account,
+        seeds = [b"pool_token_account", lending_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = borrower,
+        associated_token::token_program = token_program,
+    )]
+    pub borrower_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = platform,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub loan_officer: Option<Account<'info, LoanOfficer>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub officer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // Borrower consents to the new loan by co-signing
+    pub borrower: Signer<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
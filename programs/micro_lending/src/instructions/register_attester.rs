@@ -0,0 +1,70 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::{INITIAL_ATTESTER_REPUTATION, SEEDS_ATTESTER, SEEDS_PLATFORM};
+use anchor_lang::prelude::*;
+
+pub fn register_attester(
+    ctx: Context<RegisterAttester>,
+    name: String,
+    allowed_types: u8,
+) -> Result<()> {
+    require!(name.len() <= 50, MicroLendingError::InvalidAttestation);
+    require!(
+        allowed_types < 1 << 6,
+        MicroLendingError::InvalidAttestationType
+    ); // One bit per AttestationType
+
+    let attester_registry = &mut ctx.accounts.attester_registry;
+    let current = Clock::get()?.unix_timestamp;
+
+    attester_registry.attester = ctx.accounts.attester.key();
+    attester_registry.name = name;
+    attester_registry.allowed_types = allowed_types;
+    attester_registry.reputation_score = INITIAL_ATTESTER_REPUTATION;
+    attester_registry.attestations_issued = 0;
+    attester_registry.successful_outcomes = 0;
+    attester_registry.defaulted_outcomes = 0;
+    attester_registry.is_active = true;
+    attester_registry.registered_at = current;
+    attester_registry.updated_at = current;
+
+    msg!("Attester registered: {}", attester_registry.attester);
+    emit!(AttesterChanged {
+        attester_registry: attester_registry.key(),
+        attester: attester_registry.attester,
+        action: RegistryAction::Created,
+        name: attester_registry.name.clone(),
+        allowed_types,
+        is_active: true,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterAttester<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [SEEDS_PLATFORM],
+        bump,
+        has_one = authority
+    )]
+    pub platform: Account<'info, Platform>,
+
+    /// CHECK: The signer key being registered as an attester.
+    pub attester: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Attester::INIT_SPACE,
+        seeds = [SEEDS_ATTESTER, attester.key().as_ref()],
+        bump
+    )]
+    pub attester_registry: Account<'info, Attester>,
+
+    pub system_program: Program<'info, System>,
+}
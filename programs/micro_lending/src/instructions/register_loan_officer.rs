@@ -0,0 +1,66 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+pub fn register_loan_officer(ctx: Context<RegisterLoanOfficer>, commission_bps: u16) -> Result<()> {
+    require!(
+        commission_bps <= 5000,
+        MicroLendingError::InvalidPoolConfiguration
+    ); // Max half of the interest
+
+    let loan_officer = &mut ctx.accounts.loan_officer;
+
+    loan_officer.pool = ctx.accounts.lending_pool.key();
+    loan_officer.officer = ctx.accounts.officer.key();
+    loan_officer.commission_bps = commission_bps;
+    loan_officer.is_active = true;
+    loan_officer.loans_originated = 0;
+    loan_officer.late_repayments = 0;
+    loan_officer.defaulted_loans = 0;
+    loan_officer.total_disbursed = 0;
+    loan_officer.outstanding_principal = 0;
+    loan_officer.defaulted_principal = 0;
+    loan_officer.write_off_rate_bps = 0;
+    loan_officer.commission_earned = 0;
+    loan_officer.registered_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Loan officer {} registered for pool: {}",
+        loan_officer.officer,
+        ctx.accounts.lending_pool.name
+    );
+    emit!(LoanOfficerChanged {
+        loan_officer: loan_officer.key(),
+        pool: loan_officer.pool,
+        officer: loan_officer.officer,
+        action: RegistryAction::Created,
+        commission_bps,
+        is_active: true,
+        timestamp: loan_officer.registered_at,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterLoanOfficer<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LoanOfficer::INIT_SPACE,
+        seeds = [b"loan_officer", lending_pool.key().as_ref(), officer.key().as_ref()],
+        bump
+    )]
+    pub loan_officer: Account<'info, LoanOfficer>,
+
+    #[account(has_one = authority)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: The key being registered as a loan officer.
+    pub officer: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
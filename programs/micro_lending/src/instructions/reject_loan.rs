@@ -0,0 +1,66 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+pub fn reject_loan(ctx: Context<RejectLoan>, reason_code: u8) -> Result<()> {
+    let loan = &ctx.accounts.loan;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+
+    let previous_status = loan.status;
+
+    // Validate loan state
+    require!(
+        loan.status == LoanStatus::Requested || loan.status == LoanStatus::Approved,
+        MicroLendingError::InvalidLoanState
+    );
+
+    // Auto-approved loans hold a reservation that goes back to the pool
+    if loan.status == LoanStatus::Approved {
+        release_loan_reservation(lending_pool, loan.amount);
+    }
+
+    msg!(
+        "Loan rejected for borrower: {}, reason code: {}",
+        loan.borrower,
+        reason_code
+    );
+    emit!(LoanClosedBeforeDisbursement {
+        loan: loan.key(),
+        borrower: loan.borrower,
+        pool: lending_pool.key(),
+        amount: loan.amount,
+        previous_status,
+        reason: LoanClosureReason::Rejected,
+        rejection_code: Some(reason_code),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RejectLoan<'info> {
+    #[account(
+        mut,
+        close = borrower,
+        seeds = [b"loan", loan.borrower.as_ref(), lending_pool.key().as_ref()],
+        bump
+    )]
+    pub loan: Account<'info, Loan>,
+
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: Receives the loan account rent; must match loan.borrower.
+    #[account(
+        mut,
+        constraint = borrower.key() == loan.borrower @ MicroLendingError::InvalidBorrowerAccount
+    )]
+    pub borrower: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,38 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::SEEDS_PLATFORM;
+use anchor_lang::prelude::*;
+
+pub fn remove_verifier(ctx: Context<RemoveVerifier>, verifier: Pubkey) -> Result<()> {
+    let platform = &mut ctx.accounts.platform;
+
+    let index = platform
+        .verifiers
+        .iter()
+        .position(|registered| *registered == verifier)
+        .ok_or(MicroLendingError::VerifierNotRegistered)?;
+    platform.verifiers.remove(index);
+
+    msg!("Verifier removed: {}", verifier);
+    emit!(VerifierChanged {
+        verifier,
+        added: false,
+        verifier_count: platform.verifiers.len() as u8,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveVerifier<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEEDS_PLATFORM],
+        bump,
+        has_one = authority
+    )]
+    pub platform: Account<'info, Platform>,
+}
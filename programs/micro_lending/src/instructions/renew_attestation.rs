@@ -0,0 +1,80 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::*;
+use crate::{SEEDS_ATTESTER, SEEDS_USER};
+use anchor_lang::prelude::*;
+
+pub fn renew_attestation(ctx: Context<RenewAttestation>, expires_at: Option<i64>) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    let attestation = &mut ctx.accounts.social_attestation;
+    let current = Clock::get()?.unix_timestamp;
+
+    if let Some(expires_at) = expires_at {
+        require!(expires_at > current, MicroLendingError::InvalidAttestation);
+    }
+
+    // An attestation dropped from the count on expiry starts counting again
+    if !attestation.is_active {
+        attestation.is_active = true;
+        user_profile.social_attestations_count = user_profile
+            .social_attestations_count
+            .checked_add(1)
+            .unwrap();
+    }
+    // Renewing doesn't carry verification past the attester's own standing
+    attestation.verified =
+        is_still_verified(attestation, ctx.accounts.attester_registry.as_deref());
+    attestation.expires_at = expires_at;
+    attestation.updated_at = current;
+    user_profile.last_updated = current;
+
+    msg!(
+        "Attestation by {} renewed for user: {}",
+        attestation.attester,
+        attestation.user
+    );
+    emit!(AttestationChanged {
+        attestation: attestation.key(),
+        user: attestation.user,
+        attester: attestation.attester,
+        action: AttestationAction::Renewed,
+        attestation_type: attestation.attestation_type,
+        score: attestation.score,
+        verified: attestation.verified,
+        is_active: attestation.is_active,
+        expires_at: attestation.expires_at,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RenewAttestation<'info> {
+    pub attester: Signer<'info>,
+
+    /// CHECK: The user whose attestation is being renewed.
+    pub user: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEEDS_USER, user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        has_one = attester,
+        has_one = user,
+        seeds = [b"social_attestation", user.key().as_ref(), attester.key().as_ref()],
+        bump
+    )]
+    pub social_attestation: Account<'info, SocialAttestation>,
+
+    #[account(
+        seeds = [SEEDS_ATTESTER, attester.key().as_ref()],
+        bump
+    )]
+    pub attester_registry: Option<Account<'info, Attester>>,
+}
@@ -1,48 +1,47 @@
 use crate::error::*;
+use crate::events::*;
 use crate::states::*;
 use crate::utils::*;
-use crate::{SEEDS_PLATFORM, SEEDS_USER};
+use crate::{loan_rejection, MAX_BACKING_ATTESTERS, SEEDS_PLATFORM, SEEDS_USER};
 use anchor_lang::prelude::*;
-pub fn request_loan(
-    ctx: Context<RequestLoan>,
+pub fn request_loan<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RequestLoan<'info>>,
     amount: u64,
     duration_days: u32,
     purpose: String,
     collateral_type: u8,
 ) -> Result<()> {
     let platform = &ctx.accounts.platform;
-    let lending_pool = &ctx.accounts.lending_pool;
+    let lending_pool = &mut ctx.accounts.lending_pool;
     let user_profile = &ctx.accounts.user_profile;
     let loan = &mut ctx.accounts.loan;
     let current = Clock::get()?.unix_timestamp;
 
-    // Validate loan parameters
-    require!(
-        amount >= platform.min_loan_amount,
-        MicroLendingError::LoanAmountTooLow
-    );
-    require!(
-        amount <= platform.max_loan_amount,
-        MicroLendingError::LoanAmountTooHigh
-    );
-    require!(
-        duration_days as i64 <= lending_pool.max_loan_duration,
-        MicroLendingError::LoanDurationTooLong
-    );
-    require!(
-        purpose.len() <= 100,
-        MicroLendingError::InvalidPoolConfiguration
-    );
-    require!(
-        user_profile.active_loans == 0,
-        MicroLendingError::BorrowerHasActiveLoan
-    );
+    // Only currently-valid, verified attestations, passed as remaining accounts, back a loan
+    let verified_attesters: Vec<Pubkey> = load_verified_attestations(
+        ctx.remaining_accounts,
+        &ctx.accounts.borrower.key(),
+        current,
+    )?
+    .iter()
+    .map(|attestation| attestation.attester)
+    .collect();
 
-    // Check pool liquidity
-    require!(
-        lending_pool.available_liquidity >= amount,
-        MicroLendingError::InsufficientLiquidity
-    );
+    // The checks quote_loan reports, so a quote always agrees with the request
+    if let Some(rejection) = loan_rejection(
+        platform,
+        lending_pool,
+        user_profile,
+        amount,
+        duration_days,
+        &purpose,
+        collateral_type,
+        verified_attesters.len(),
+        current,
+    )? {
+        return Err(rejection.into());
+    }
+    let collateral_type = collateral_type_from_u8(collateral_type).unwrap();
 
     // Calculate interest rate based on credit score and pool base rate
     let interest_rate = calculate_interest_rate(
@@ -50,13 +49,54 @@ pub fn request_loan(
         lending_pool.base_interest_rate,
         duration_days,
     )?;
+    let backing_attesters: Vec<Pubkey> = verified_attesters
+        .into_iter()
+        .take(MAX_BACKING_ATTESTERS)
+        .collect();
 
-    // Validate credit score for loan amount
-    let required_credit_score = calculate_required_credit_score(amount, platform.max_loan_amount)?;
-    require!(
-        user_profile.credit_score >= required_credit_score,
-        MicroLendingError::InsufficientCreditScore
-    );
+    // Loans submitted by a field officer record the officer. The borrower either co-signs or
+    // consents off-chain, leaving the officer to submit the request and pay for the account.
+    if !ctx.accounts.borrower.is_signer {
+        let (Some(officer), Some(instructions)) = (
+            ctx.accounts.officer.as_ref(),
+            ctx.accounts.instructions.as_ref(),
+        ) else {
+            return Err(MicroLendingError::BorrowerConsentRequired.into());
+        };
+        let consent = LoanConsent {
+            officer: officer.key(),
+            lending_pool: lending_pool.key(),
+            amount,
+            duration_days,
+            purpose: purpose.clone(),
+            collateral_type: collateral_type as u8,
+            expires_at: 0,
+        };
+        verify_loan_consent(
+            instructions,
+            &ctx.accounts.borrower.key(),
+            &consent,
+            current,
+        )?;
+    }
+    let loan_officer = match (
+        ctx.accounts.loan_officer.as_mut(),
+        ctx.accounts.officer.as_ref(),
+    ) {
+        (Some(loan_officer), Some(officer)) => {
+            require!(
+                loan_officer.officer == officer.key() && loan_officer.pool == lending_pool.key(),
+                MicroLendingError::LoanOfficerAccountRequired
+            );
+            require!(
+                loan_officer.is_active,
+                MicroLendingError::LoanOfficerInactive
+            );
+            Some(officer.key())
+        }
+        (None, None) => None,
+        _ => return Err(MicroLendingError::LoanOfficerAccountRequired.into()),
+    };
 
     // Initialize loan
     loan.borrower = ctx.accounts.borrower.key();
@@ -70,14 +110,7 @@ pub fn request_loan(
     loan.interest_accrued = 0;
     loan.status = LoanStatus::Requested;
     loan.purpose = purpose;
-    loan.collateral_type = match collateral_type {
-        0 => CollateralType::None,
-        1 => CollateralType::Social,
-        2 => CollateralType::Asset,
-        3 => CollateralType::Income,
-        4 => CollateralType::Group,
-        _ => return Err(MicroLendingError::InvalidCollateralType.into()),
-    };
+    loan.collateral_type = collateral_type;
     loan.collateral_value = 0;
     loan.payment_count = 0;
     loan.last_payment_date = 0;
@@ -85,6 +118,53 @@ pub fn request_loan(
     loan.late_fee_rate = 500; // 5% late fee rate
     loan.created_at = current;
     loan.liquidated_at = None;
+    loan.backing_attesters = backing_attesters;
+    loan.approved_at = 0;
+    loan.committee_votes = Vec::new();
+    loan.loan_officer = loan_officer;
+    loan.accrual_start = 0;
+    loan.scheduled_repayment = 0;
+    loan.restructure_count = 0;
+    loan.restructured_at = None;
+    loan.forbearance_until = 0;
+    loan.forbearance_count = 0;
+    loan.refinanced = false;
+    loan.refinanced_from = None;
+    loan.on_time_streak = 0;
+    loan.rate_step_downs = 0;
+    loan.carried_interest = 0;
+
+    emit!(LoanRequested {
+        loan: loan.key(),
+        borrower: loan.borrower,
+        pool: loan.lender_pool,
+        amount,
+        duration_days,
+        interest_rate,
+        collateral_type,
+        loan_officer: loan.loan_officer,
+        timestamp: current,
+    });
+
+    // Loans inside the pool's underwriting rules skip manual approval
+    if qualifies_for_auto_approval(lending_pool, user_profile.credit_score, amount) {
+        let pool_liquidity_before = lending_pool.available_liquidity;
+        reserve_loan_liquidity(lending_pool, amount)?;
+        loan.status = LoanStatus::Approved;
+        loan.approved_at = current;
+        msg!("Loan auto-approved for borrower: {}", loan.borrower);
+        emit!(LoanApproved {
+            loan: loan.key(),
+            borrower: loan.borrower,
+            pool: loan.lender_pool,
+            amount,
+            auto_approved: true,
+            approve_votes: 0,
+            pool_liquidity_before,
+            pool_liquidity_after: lending_pool.available_liquidity,
+            timestamp: current,
+        });
+    }
 
     msg!(
         "Loan requested: {} tokens for {} days",
@@ -111,21 +191,36 @@ pub struct RequestLoan<'info> {
     pub user_profile: Account<'info, UserProfile>,
 
     #[account(
+        mut,
         constraint = lending_pool.is_active @ MicroLendingError::PoolNotActive
     )]
     pub lending_pool: Account<'info, LendingPool>,
 
     #[account(
         init,
-        payer = borrower,
+        payer = payer,
         space = 8 + Loan::INIT_SPACE,
         seeds = [b"loan", borrower.key().as_ref(), lending_pool.key().as_ref()],
         bump
     )]
     pub loan: Account<'info, Loan>,
 
+    /// CHECK: The borrower signs, or consents through an ed25519 instruction checked
+    /// against `instructions`.
+    pub borrower: AccountInfo<'info>,
+
+    // The borrower, or the loan officer submitting on their behalf
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     #[account(mut)]
-    pub borrower: Signer<'info>,
+    pub loan_officer: Option<Account<'info, LoanOfficer>>,
+
+    pub officer: Option<Signer<'info>>,
+
+    /// CHECK: The instructions sysvar, needed when the borrower consents off-chain.
+    #[account(address = solana_instructions_sysvar::ID)]
+    pub instructions: Option<AccountInfo<'info>>,
 
     pub system_program: Program<'info, System>,
 }
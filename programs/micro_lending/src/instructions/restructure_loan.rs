@@ -0,0 +1,160 @@
+use crate::calculate_payoff_quote;
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::*;
+use crate::{MAX_LOAN_RESTRUCTURES, SEEDS_PLATFORM, SEEDS_USER};
+use anchor_lang::prelude::*;
+
+pub fn restructure_loan(
+    ctx: Context<RestructureLoan>,
+    new_duration_days: u32,
+    new_interest_rate: u16,
+    capitalize_arrears: bool,
+) -> Result<()> {
+    let loan = &mut ctx.accounts.loan;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let user_profile = &mut ctx.accounts.user_profile;
+    let current = Clock::get()?.unix_timestamp;
+
+    require!(
+        loan.status == LoanStatus::Disbursed || loan.status == LoanStatus::Active,
+        MicroLendingError::InvalidLoanState
+    );
+    require!(
+        loan.restructure_count < MAX_LOAN_RESTRUCTURES,
+        MicroLendingError::RestructureLimitReached
+    );
+    require!(
+        new_duration_days > 0,
+        MicroLendingError::InvalidRestructureTerms
+    );
+    require!(
+        new_duration_days as i64 <= lending_pool.max_loan_duration,
+        MicroLendingError::LoanDurationTooLong
+    );
+    require!(
+        new_interest_rate <= 5000,
+        MicroLendingError::InvalidRestructureTerms
+    ); // Max 50%
+
+    apply_disaster_forbearance(loan, lending_pool, current);
+
+    // Rescheduling may only push the due date out
+    let new_due_date = current + (new_duration_days as i64 * 86400);
+    require!(
+        new_due_date >= loan.due_date,
+        MicroLendingError::InvalidRestructureTerms
+    );
+
+    let outstanding_before = loan.amount.checked_sub(loan.amount_repaid).unwrap();
+    let old_interest_rate = loan.interest_rate;
+    let old_due_date = loan.due_date;
+    let mut capitalized = 0u64;
+
+    // Arrears as a payment would settle them now, with late fees waived in forbearance.
+    // Rescheduling is not a payoff, so no early payoff rebate applies.
+    let quote = calculate_payoff_quote(&ctx.accounts.platform, lending_pool, loan, current)?;
+    let interest_accrued = quote
+        .interest_accrued
+        .checked_add(quote.early_payoff_rebate)
+        .unwrap();
+    // Interest to date is settled at the old rate, and the new one applies from here. Only
+    // whole days are charged, so the partial day carries over.
+    let days_elapsed = days_between(loan.accrual_start, current);
+    loan.accrual_start += days_elapsed as i64 * 86400;
+
+    if capitalize_arrears {
+        // Fold accrued interest and late fees into principal; interest restarts on the new balance
+        capitalized = interest_accrued.checked_add(quote.late_fee).unwrap();
+        loan.amount = loan.amount.checked_add(capitalized).unwrap();
+        loan.interest_accrued = 0;
+        loan.carried_interest = 0;
+        lending_pool.total_borrowed = lending_pool
+            .total_borrowed
+            .checked_add(capitalized)
+            .unwrap();
+
+        if let Some(loan_officer) = loan_officer_for(loan, &mut ctx.accounts.loan_officer)? {
+            loan_officer.outstanding_principal = loan_officer
+                .outstanding_principal
+                .saturating_add(capitalized);
+        }
+
+        msg!("Capitalized into principal: {}", capitalized);
+    } else {
+        // Still owed, and paid with the next payment
+        loan.carried_interest = interest_accrued;
+    }
+
+    // Rebuild the repayment schedule on the remaining balance
+    let outstanding = loan.amount.checked_sub(loan.amount_repaid).unwrap();
+    loan.interest_rate = new_interest_rate;
+    loan.duration_days = new_duration_days;
+    loan.due_date = new_due_date;
+    loan.scheduled_repayment =
+        calculate_loan_payment(outstanding, new_interest_rate, new_duration_days)?;
+
+    // Only the first restructure of a loan counts against the borrower's history
+    if loan.restructure_count == 0 {
+        user_profile.restructured_loans = user_profile.restructured_loans.saturating_add(1);
+    }
+    loan.restructure_count = loan.restructure_count.checked_add(1).unwrap();
+    loan.restructured_at = Some(current);
+    user_profile.last_activity_at = current;
+
+    msg!(
+        "Loan for borrower {} restructured, due date: {}, scheduled repayment: {}",
+        loan.borrower,
+        loan.due_date,
+        loan.scheduled_repayment
+    );
+    emit!(LoanRestructured {
+        loan: loan.key(),
+        borrower: loan.borrower,
+        capitalized,
+        outstanding_before,
+        outstanding_after: outstanding,
+        old_interest_rate,
+        new_interest_rate,
+        old_due_date,
+        new_due_date,
+        restructure_count: loan.restructure_count,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RestructureLoan<'info> {
+    #[account(
+        seeds = [SEEDS_PLATFORM],
+        bump
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        mut,
+        seeds = [b"loan", borrower.key().as_ref(), lending_pool.key().as_ref()],
+        bump
+    )]
+    pub loan: Account<'info, Loan>,
+
+    #[account(mut, has_one = authority)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        mut,
+        seeds = [SEEDS_USER, borrower.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(mut)]
+    pub loan_officer: Option<Account<'info, LoanOfficer>>,
+
+    pub authority: Signer<'info>,
+
+    // Borrower consents to the new terms by co-signing
+    pub borrower: Signer<'info>,
+}
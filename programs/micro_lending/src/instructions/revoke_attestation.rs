@@ -0,0 +1,62 @@
+use crate::events::*;
+use crate::states::*;
+use crate::SEEDS_USER;
+use anchor_lang::prelude::*;
+
+pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    let attestation = &ctx.accounts.social_attestation;
+    let current = Clock::get()?.unix_timestamp;
+
+    // Expired attestations may already have been removed from the count
+    if attestation.is_active {
+        user_profile.social_attestations_count =
+            user_profile.social_attestations_count.saturating_sub(1);
+    }
+    user_profile.last_updated = current;
+
+    msg!(
+        "Attestation by {} revoked for user: {}",
+        attestation.attester,
+        attestation.user
+    );
+    emit!(AttestationChanged {
+        attestation: attestation.key(),
+        user: attestation.user,
+        attester: attestation.attester,
+        action: AttestationAction::Revoked,
+        attestation_type: attestation.attestation_type,
+        score: attestation.score,
+        verified: attestation.verified,
+        is_active: false,
+        expires_at: attestation.expires_at,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    #[account(mut)]
+    pub attester: Signer<'info>,
+
+    /// CHECK: The user whose attestation is being revoked.
+    pub user: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEEDS_USER, user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        close = attester,
+        has_one = attester,
+        has_one = user,
+        seeds = [b"social_attestation", user.key().as_ref(), attester.key().as_ref()],
+        bump
+    )]
+    pub social_attestation: Account<'info, SocialAttestation>,
+}
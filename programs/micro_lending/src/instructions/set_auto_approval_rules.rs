@@ -0,0 +1,48 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+pub fn set_auto_approval_rules(
+    ctx: Context<SetAutoApprovalRules>,
+    rules: AutoApprovalRules,
+) -> Result<()> {
+    require!(
+        rules.max_utilization_bps <= 10000,
+        MicroLendingError::InvalidPoolConfiguration
+    );
+    require!(
+        rules.score_bands.len() <= 5
+            && rules
+                .score_bands
+                .iter()
+                .all(|band| band.min_credit_score <= 850 && band.max_amount > 0),
+        MicroLendingError::InvalidPoolConfiguration
+    );
+
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    lending_pool.auto_approval = rules;
+
+    msg!(
+        "Auto-approval for pool {} enabled: {}",
+        lending_pool.name,
+        lending_pool.auto_approval.enabled
+    );
+    emit!(AutoApprovalRulesUpdated {
+        pool: lending_pool.key(),
+        rules: lending_pool.auto_approval.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAutoApprovalRules<'info> {
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,51 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::ALL_COLLATERAL_TYPES;
+use anchor_lang::prelude::*;
+
+pub fn set_borrower_policy(ctx: Context<SetBorrowerPolicy>, policy: BorrowerPolicy) -> Result<()> {
+    require!(
+        policy.min_credit_score <= 850,
+        MicroLendingError::InvalidPoolConfiguration
+    );
+    require!(
+        policy.required_verifications < 1 << 3,
+        MicroLendingError::InvalidPoolConfiguration
+    ); // One bit per VerificationType
+    require!(
+        policy.allowed_collateral_types != 0
+            && policy.allowed_collateral_types & !ALL_COLLATERAL_TYPES == 0,
+        MicroLendingError::InvalidPoolConfiguration
+    );
+    require!(
+        policy.allowed_purposes.len() <= 5
+            && policy
+                .allowed_purposes
+                .iter()
+                .all(|purpose| purpose.len() <= 32),
+        MicroLendingError::InvalidPoolConfiguration
+    );
+
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    lending_pool.borrower_policy = policy;
+
+    msg!("Borrower policy updated for pool: {}", lending_pool.name);
+    emit!(BorrowerPolicyUpdated {
+        pool: lending_pool.key(),
+        policy: lending_pool.borrower_policy.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetBorrowerPolicy<'info> {
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,61 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::SEEDS_PLATFORM;
+use anchor_lang::prelude::*;
+
+pub fn set_lending_ladder(
+    ctx: Context<SetLendingLadder>,
+    first_loan_cap: u64,
+    step_up_bps: u16,
+    step_down_bps: u16,
+) -> Result<()> {
+    let platform = &mut ctx.accounts.platform;
+
+    if first_loan_cap > 0 {
+        require!(
+            first_loan_cap >= platform.min_loan_amount
+                && first_loan_cap <= platform.max_loan_amount,
+            MicroLendingError::InvalidPlatformConfiguration
+        );
+    }
+    require!(
+        (10000..=30000).contains(&step_up_bps),
+        MicroLendingError::InvalidPlatformConfiguration
+    ); // Limits never shrink after an on-time repayment, at most triple
+    require!(
+        step_down_bps <= 10000,
+        MicroLendingError::InvalidPlatformConfiguration
+    );
+
+    platform.lending_ladder = LendingLadder {
+        first_loan_cap,
+        step_up_bps,
+        step_down_bps,
+    };
+
+    msg!(
+        "Lending ladder set: first loan cap {}, step up {} bps, step down {} bps",
+        first_loan_cap,
+        step_up_bps,
+        step_down_bps
+    );
+    emit!(LendingLadderUpdated {
+        lending_ladder: platform.lending_ladder,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetLendingLadder<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEEDS_PLATFORM],
+        bump,
+        has_one = authority
+    )]
+    pub platform: Account<'info, Platform>,
+}
@@ -0,0 +1,40 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+pub fn set_loan_ttls(ctx: Context<SetLoanTtls>, request_ttl: i64, approval_ttl: i64) -> Result<()> {
+    require!(
+        request_ttl >= 0 && approval_ttl >= 0,
+        MicroLendingError::InvalidPoolConfiguration
+    );
+
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    lending_pool.request_ttl = request_ttl;
+    lending_pool.approval_ttl = approval_ttl;
+
+    msg!(
+        "Pool {} loan TTLs set: request {}s, approval {}s",
+        lending_pool.name,
+        request_ttl,
+        approval_ttl
+    );
+    emit!(LoanTtlsUpdated {
+        pool: lending_pool.key(),
+        request_ttl,
+        approval_ttl,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetLoanTtls<'info> {
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    pub authority: Signer<'info>,
+}
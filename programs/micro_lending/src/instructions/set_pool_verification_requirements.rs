@@ -0,0 +1,42 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+// Sets only the verification part of the pool's borrower policy, for pools that do not
+// manage the rest of it
+pub fn set_pool_verification_requirements(
+    ctx: Context<SetPoolVerificationRequirements>,
+    required_verifications: u8,
+) -> Result<()> {
+    require!(
+        required_verifications < 1 << 3,
+        MicroLendingError::InvalidPoolConfiguration
+    ); // One bit per VerificationType
+
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    lending_pool.borrower_policy.required_verifications = required_verifications;
+
+    msg!(
+        "Pool {} now requires verifications: {:#05b}",
+        lending_pool.name,
+        required_verifications
+    );
+    emit!(BorrowerPolicyUpdated {
+        pool: lending_pool.key(),
+        policy: lending_pool.borrower_policy.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPoolVerificationRequirements<'info> {
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    pub authority: Signer<'info>,
+}
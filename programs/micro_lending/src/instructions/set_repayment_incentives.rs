@@ -0,0 +1,48 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+pub fn set_repayment_incentives(
+    ctx: Context<SetRepaymentIncentives>,
+    incentives: RepaymentIncentives,
+) -> Result<()> {
+    require!(
+        incentives.rate_step_down_bps <= 1000,
+        MicroLendingError::InvalidPoolConfiguration
+    ); // Max 10% per step
+    require!(
+        incentives.min_interest_rate <= 5000,
+        MicroLendingError::InvalidPoolConfiguration
+    );
+    require!(
+        incentives.early_payoff_rebate_bps <= 10000,
+        MicroLendingError::InvalidPoolConfiguration
+    );
+
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    lending_pool.repayment_incentives = incentives;
+
+    msg!(
+        "Repayment incentives for pool {} set, streak length: {}",
+        lending_pool.name,
+        incentives.streak_length
+    );
+    emit!(RepaymentIncentivesUpdated {
+        pool: lending_pool.key(),
+        incentives,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRepaymentIncentives<'info> {
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    pub authority: Signer<'info>,
+}
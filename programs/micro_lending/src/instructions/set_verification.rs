@@ -0,0 +1,101 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::{SEEDS_PLATFORM, SEEDS_USER, SEEDS_VERIFICATION};
+use anchor_lang::prelude::*;
+
+pub fn set_verification(
+    ctx: Context<SetVerification>,
+    verification_type: VerificationType,
+    verified: bool,
+    expires_at: Option<i64>,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    let record = &mut ctx.accounts.verification_record;
+    let current = Clock::get()?.unix_timestamp;
+
+    if let Some(expires_at) = expires_at {
+        require!(expires_at > current, MicroLendingError::InvalidExpiry);
+    }
+
+    // Record who verified what and when
+    record.user = ctx.accounts.user.key();
+    record.verification_type = verification_type;
+    record.verifier = ctx.accounts.verifier.key();
+    record.verified = verified;
+    record.verified_at = current;
+    record.expires_at = expires_at;
+    record.evidence_hash = evidence_hash;
+
+    // Mirror the result on the user's profile
+    match verification_type {
+        VerificationType::Kyc => {
+            user_profile.kyc_verified = verified;
+            user_profile.kyc_expires_at = expires_at;
+            user_profile.kyc_verifier = record.verifier;
+        }
+        VerificationType::Phone => {
+            user_profile.phone_verified = verified;
+            user_profile.phone_expires_at = expires_at;
+            user_profile.phone_verifier = record.verifier;
+        }
+        VerificationType::Email => {
+            user_profile.email_verified = verified;
+            user_profile.email_expires_at = expires_at;
+            user_profile.email_verifier = record.verifier;
+        }
+    }
+    user_profile.last_updated = current;
+
+    msg!(
+        "Verification set for user: {} by verifier: {}, verified: {}",
+        record.user,
+        record.verifier,
+        verified
+    );
+    emit!(VerificationUpdated {
+        user: record.user,
+        verifier: record.verifier,
+        verification_type,
+        verified,
+        expires_at,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(verification_type: VerificationType)]
+pub struct SetVerification<'info> {
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+
+    #[account(
+        seeds = [SEEDS_PLATFORM],
+        bump,
+        constraint = platform.verifiers.contains(&verifier.key()) @ MicroLendingError::VerifierNotRegistered
+    )]
+    pub platform: Account<'info, Platform>,
+
+    /// CHECK: The user account being verified.
+    pub user: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEEDS_USER, user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = 8 + VerificationRecord::INIT_SPACE,
+        seeds = [SEEDS_VERIFICATION, user.key().as_ref(), &[verification_type as u8]],
+        bump
+    )]
+    pub verification_record: Account<'info, VerificationRecord>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,73 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::*;
+use crate::SEEDS_ATTESTER;
+use anchor_lang::prelude::*;
+
+pub fn update_attestation(
+    ctx: Context<UpdateAttestation>,
+    score: u16,
+    metadata: String,
+) -> Result<()> {
+    let attestation = &mut ctx.accounts.social_attestation;
+    let current = Clock::get()?.unix_timestamp;
+
+    require!(score <= 1000, MicroLendingError::InvalidAttestation);
+    require!(metadata.len() <= 500, MicroLendingError::InvalidAttestation);
+
+    // Expired attestations must be renewed before they can be changed
+    require!(
+        is_attestation_valid(attestation, current),
+        MicroLendingError::AttestationExpired
+    );
+
+    // A changed score is only verified while the attester is still authorized to verify it
+    attestation.verified =
+        is_still_verified(attestation, ctx.accounts.attester_registry.as_deref());
+    attestation.score = score;
+    attestation.metadata = metadata;
+    attestation.updated_at = current;
+
+    msg!(
+        "Attestation by {} updated for user: {}",
+        attestation.attester,
+        attestation.user
+    );
+    emit!(AttestationChanged {
+        attestation: attestation.key(),
+        user: attestation.user,
+        attester: attestation.attester,
+        action: AttestationAction::Updated,
+        attestation_type: attestation.attestation_type,
+        score: attestation.score,
+        verified: attestation.verified,
+        is_active: attestation.is_active,
+        expires_at: attestation.expires_at,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateAttestation<'info> {
+    pub attester: Signer<'info>,
+
+    /// CHECK: The user whose attestation is being updated.
+    pub user: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = attester,
+        has_one = user,
+        seeds = [b"social_attestation", user.key().as_ref(), attester.key().as_ref()],
+        bump
+    )]
+    pub social_attestation: Account<'info, SocialAttestation>,
+
+    #[account(
+        seeds = [SEEDS_ATTESTER, attester.key().as_ref()],
+        bump
+    )]
+    pub attester_registry: Option<Account<'info, Attester>>,
+}
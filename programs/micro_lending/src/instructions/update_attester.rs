@@ -0,0 +1,58 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::{SEEDS_ATTESTER, SEEDS_PLATFORM};
+use anchor_lang::prelude::*;
+
+pub fn update_attester(
+    ctx: Context<UpdateAttester>,
+    allowed_types: u8,
+    is_active: bool,
+) -> Result<()> {
+    require!(
+        allowed_types < 1 << 6,
+        MicroLendingError::InvalidAttestationType
+    ); // One bit per AttestationType
+
+    let attester_registry = &mut ctx.accounts.attester_registry;
+    let current = Clock::get()?.unix_timestamp;
+
+    attester_registry.allowed_types = allowed_types;
+    attester_registry.is_active = is_active;
+    attester_registry.updated_at = current;
+
+    msg!(
+        "Attester {} updated, active: {}",
+        attester_registry.attester,
+        is_active
+    );
+    emit!(AttesterChanged {
+        attester_registry: attester_registry.key(),
+        attester: attester_registry.attester,
+        action: RegistryAction::Updated,
+        name: attester_registry.name.clone(),
+        allowed_types,
+        is_active,
+        timestamp: current,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateAttester<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [SEEDS_PLATFORM],
+        bump,
+        has_one = authority
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        mut,
+        seeds = [SEEDS_ATTESTER, attester_registry.attester.as_ref()],
+        bump
+    )]
+    pub attester_registry: Account<'info, Attester>,
+}
@@ -1,25 +1,64 @@
+use crate::events::*;
 use crate::states::*;
 use crate::utils::*;
 use crate::{SEEDS_PLATFORM, SEEDS_USER};
 use anchor_lang::prelude::*;
 
-pub fn update_credit_score(ctx: Context<UpdateCreditScore>) -> Result<()> {
+pub fn update_credit_score<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateCreditScore<'info>>,
+) -> Result<()> {
+    let platform = &ctx.accounts.platform;
     let user_profile = &mut ctx.accounts.user_profile;
     let clock = Clock::get()?;
+    let old_score = user_profile.credit_score;
 
     let total_loans = user_profile
         .successful_loans
         .checked_add(user_profile.defaulted_loans)
         .unwrap();
 
-    let new_score = calculate_credit_score_from_history(
+    let mut new_score = calculate_credit_score_from_history(
         user_profile.successful_loans,
         total_loans,
         user_profile.defaulted_loans,
         user_profile.total_borrowed,
         user_profile.total_repaid,
+        &user_profile.recent_outcomes,
+        platform.score_half_life_days,
+        clock.unix_timestamp,
     )?;
 
+    // Users without loan history keep the new-user base score
+    if total_loans > 0 {
+        new_score = apply_inactivity_decay(
+            new_score,
+            user_profile.last_activity_at,
+            clock.unix_timestamp,
+            platform.score_half_life_days,
+            platform.inactivity_threshold_days,
+        );
+    }
+
+    // Restructured loans weigh on the score even when eventually repaid
+    new_score = new_score
+        .saturating_sub(calculate_restructure_penalty(
+            user_profile.restructured_loans,
+        ))
+        .max(300);
+
+    // Only currently-valid, verified attestations, passed as remaining accounts, add to the score
+    let attestation_scores: Vec<u16> = load_verified_attestations(
+        ctx.remaining_accounts,
+        &ctx.accounts.user.key(),
+        clock.unix_timestamp,
+    )?
+    .iter()
+    .map(|attestation| attestation.score)
+    .collect();
+    new_score = new_score
+        .saturating_add(calculate_attestation_bonus(&attestation_scores))
+        .min(850);
+
     user_profile.credit_score = new_score;
     user_profile.last_updated = clock.unix_timestamp;
 
@@ -29,6 +68,14 @@ pub fn update_credit_score(ctx: Context<UpdateCreditScore>) -> Result<()> {
         new_score
     );
 
+    emit!(CreditScoreUpdated {
+        user: user_profile.owner,
+        old_score,
+        new_score,
+        successful_loans: user_profile.successful_loans,
+        defaulted_loans: user_profile.defaulted_loans,
+        timestamp: clock.unix_timestamp,
+    });
     Ok(())
 }
 
@@ -0,0 +1,50 @@
+use crate::events::*;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+pub fn update_loan_committee(
+    ctx: Context<UpdateLoanCommittee>,
+    members: Vec<Pubkey>,
+    quorum: u8,
+    voting_window: i64,
+) -> Result<()> {
+    validate_committee(&members, quorum, voting_window)?;
+
+    let committee = &mut ctx.accounts.loan_committee;
+    committee.members = members;
+    committee.quorum = quorum;
+    committee.voting_window = voting_window;
+
+    msg!(
+        "Loan committee for pool {} updated, quorum {} of {}",
+        ctx.accounts.lending_pool.name,
+        quorum,
+        committee.members.len()
+    );
+    emit!(LoanCommitteeChanged {
+        committee: committee.key(),
+        pool: ctx.accounts.lending_pool.key(),
+        action: RegistryAction::Updated,
+        members: committee.members.clone(),
+        quorum,
+        voting_window,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateLoanCommittee<'info> {
+    #[account(
+        mut,
+        seeds = [b"loan_committee", lending_pool.key().as_ref()],
+        bump
+    )]
+    pub loan_committee: Account<'info, LoanCommittee>,
+
+    #[account(has_one = authority)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    pub authority: Signer<'info>,
+}
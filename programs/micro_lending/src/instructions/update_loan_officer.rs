@@ -0,0 +1,50 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+pub fn update_loan_officer(
+    ctx: Context<UpdateLoanOfficer>,
+    commission_bps: u16,
+    is_active: bool,
+) -> Result<()> {
+    require!(
+        commission_bps <= 5000,
+        MicroLendingError::InvalidPoolConfiguration
+    ); // Max half of the interest
+
+    let loan_officer = &mut ctx.accounts.loan_officer;
+    loan_officer.commission_bps = commission_bps;
+    loan_officer.is_active = is_active;
+
+    msg!(
+        "Loan officer {} updated, active: {}",
+        loan_officer.officer,
+        is_active
+    );
+    emit!(LoanOfficerChanged {
+        loan_officer: loan_officer.key(),
+        pool: loan_officer.pool,
+        officer: loan_officer.officer,
+        action: RegistryAction::Updated,
+        commission_bps,
+        is_active,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateLoanOfficer<'info> {
+    #[account(
+        mut,
+        seeds = [b"loan_officer", lending_pool.key().as_ref(), loan_officer.officer.as_ref()],
+        bump
+    )]
+    pub loan_officer: Account<'info, LoanOfficer>,
+
+    #[account(has_one = authority)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    pub authority: Signer<'info>,
+}
@@ -1,5 +1,13 @@
 use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::{
+    PayoffQuote, LOAN_CONSENT_DOMAIN, MAX_COMMITTEE_MEMBERS, MAX_RECENT_OUTCOMES,
+    MIN_ATTESTER_REPUTATION, NEUTRAL_CREDIT_SCORE, RESTRUCTURE_SCORE_PENALTY,
+};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
 
 // Calculate interest rate based on credit score and other factors
 pub fn calculate_interest_rate(
@@ -85,12 +93,16 @@ pub fn calculate_loan_payment(
 }
 
 // Calculate credit score based on payment history
+#[allow(clippy::too_many_arguments)]
 pub fn calculate_credit_score_from_history(
     successful_payments: u16,
     total_payments: u16,
     defaults: u16,
     total_borrowed: u64,
     total_repaid: u64,
+    recent_outcomes: &[LoanOutcomeRecord],
+    half_life_days: u16,
+    current_time: i64,
 ) -> Result<u16> {
     if total_payments == 0 {
         return Ok(300); // Base score for new users
@@ -111,8 +123,28 @@ pub fn calculate_credit_score_from_history(
     // Repayment ratio weight (30%)
     score += (repayment_ratio * 300.0) as u16;
 
-    // Penalty for defaults (20%)
-    score = score.saturating_sub(defaults * 50);
+    // Penalty for defaults (20%), weighted by how recently each one happened
+    let mut penalty = 0.0;
+    let mut recent_defaults = 0u16;
+    for record in recent_outcomes {
+        let weight = recency_weight(record.timestamp, current_time, half_life_days);
+        match record.outcome {
+            LoanOutcome::Defaulted => {
+                recent_defaults += 1;
+                penalty += 50.0 * weight;
+            }
+            LoanOutcome::RepaidLate => penalty += 15.0 * weight,
+            LoanOutcome::RepaidOnTime => {}
+        }
+    }
+
+    // Defaults that fell out of the ring buffer are at least as old as its oldest entry
+    let older_weight = recent_outcomes
+        .first()
+        .map(|record| recency_weight(record.timestamp, current_time, half_life_days))
+        .unwrap_or(1.0);
+    penalty += defaults.saturating_sub(recent_defaults) as f64 * 50.0 * older_weight;
+    score = score.saturating_sub(penalty as u16);
 
     // Experience bonus (10%)
     if total_payments > 10 {
@@ -123,11 +155,52 @@ pub fn calculate_credit_score_from_history(
     }
 
     // Cap score between 300 and 850
-    score = score.max(300).min(850);
+    score = score.clamp(300, 850);
 
     Ok(score)
 }
 
+// Weight of an event that happened at `event_time`, halving every `half_life_days`
+pub fn recency_weight(event_time: i64, current_time: i64, half_life_days: u16) -> f64 {
+    if half_life_days == 0 {
+        return 1.0;
+    }
+    let age_days = (current_time - event_time).max(0) as f64 / 86400.0;
+    0.5f64.powf(age_days / half_life_days as f64)
+}
+
+// Decay score toward the neutral baseline once a user has been inactive past the threshold
+pub fn apply_inactivity_decay(
+    score: u16,
+    last_activity_at: i64,
+    current_time: i64,
+    half_life_days: u16,
+    inactivity_threshold_days: u16,
+) -> u16 {
+    let inactive_days = days_between(last_activity_at, current_time);
+    if half_life_days == 0 || inactive_days <= inactivity_threshold_days as u32 {
+        return score;
+    }
+
+    let decay_days = inactive_days - inactivity_threshold_days as u32;
+    let retained = 0.5f64.powf(decay_days as f64 / half_life_days as f64);
+    let baseline = NEUTRAL_CREDIT_SCORE as f64;
+    let decayed = baseline + (score as f64 - baseline) * retained;
+
+    decayed.round() as u16
+}
+
+// Append a loan outcome to the user's ring buffer, evicting the oldest when full
+pub fn record_loan_outcome(user_profile: &mut UserProfile, outcome: LoanOutcome, timestamp: i64) {
+    if user_profile.recent_outcomes.len() >= MAX_RECENT_OUTCOMES {
+        user_profile.recent_outcomes.remove(0);
+    }
+    user_profile
+        .recent_outcomes
+        .push(LoanOutcomeRecord { outcome, timestamp });
+    user_profile.last_activity_at = timestamp;
+}
+
 // Validate social attestation
 pub fn validate_social_attestation(
     attester: &Pubkey,
@@ -152,6 +225,656 @@ pub fn validate_social_attestation(
     }
 }
 
+// Check whether an attestation currently counts towards a user's reputation
+pub fn is_attestation_valid(attestation: &SocialAttestation, current_time: i64) -> bool {
+    let expired = matches!(attestation.expires_at, Some(expires_at) if current_time >= expires_at);
+    attestation.is_active && !expired
+}
+
+// Check whether a verified attestation is still backed by its attester's registry entry, so
+// attesters deactivated or fallen below the minimum reputation no longer verify anything
+pub fn is_still_verified(attestation: &SocialAttestation, registry: Option<&Attester>) -> bool {
+    attestation.verified
+        && registry.is_some_and(|registry| {
+            registry.attester == attestation.attester
+                && is_attester_authorized(registry, attestation.attestation_type)
+        })
+}
+
+// Split remaining accounts into the leading social attestations and the accounts after them
+pub fn split_attestation_accounts<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> (&'info [AccountInfo<'info>], &'info [AccountInfo<'info>]) {
+    let attestation_count = remaining_accounts
+        .iter()
+        .take_while(|account_info| {
+            account_info
+                .try_borrow_data()
+                .is_ok_and(|data| data.starts_with(SocialAttestation::DISCRIMINATOR))
+        })
+        .count();
+    remaining_accounts.split_at(attestation_count)
+}
+
+// Load the currently-valid, verified attestations for a user passed in as remaining
+// accounts. The attestations come first, with their attesters' registry entries among the
+// accounts after them; an attestation whose attester is no longer authorized doesn't count.
+pub fn load_verified_attestations<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    user: &Pubkey,
+    current_time: i64,
+) -> Result<Vec<Account<'info, SocialAttestation>>> {
+    let (attestation_accounts, other_accounts) = split_attestation_accounts(remaining_accounts);
+    let registries: Vec<Account<'info, Attester>> = other_accounts
+        .iter()
+        .filter_map(|account_info| Account::<Attester>::try_from(account_info).ok())
+        .collect();
+
+    let mut verified: Vec<Account<'info, SocialAttestation>> = Vec::new();
+    for account_info in attestation_accounts {
+        let attestation = Account::<SocialAttestation>::try_from(account_info)?;
+        require!(
+            attestation.user == *user,
+            MicroLendingError::InvalidAttestation
+        );
+        if verified.iter().any(|a| a.key() == attestation.key()) {
+            continue;
+        }
+        let registry = registries
+            .iter()
+            .find(|registry| registry.attester == attestation.attester);
+        if is_attestation_valid(&attestation, current_time)
+            && is_still_verified(&attestation, registry.map(|registry| &**registry))
+        {
+            verified.push(attestation);
+        }
+    }
+    Ok(verified)
+}
+
+// Credit score bonus from valid social attestations, capped at 100 points
+pub fn calculate_attestation_bonus(attestation_scores: &[u16]) -> u16 {
+    let bonus: u32 = attestation_scores
+        .iter()
+        .map(|score| *score as u32 * 25 / 1000)
+        .sum();
+    bonus.min(100) as u16
+}
+
+// Bit for an attestation type in an attester's allowed_types mask
+pub fn attestation_type_mask(attestation_type: AttestationType) -> u8 {
+    1 << (attestation_type as u8)
+}
+
+// Check whether a registered attester may issue verified attestations of this type
+pub fn is_attester_authorized(attester: &Attester, attestation_type: AttestationType) -> bool {
+    attester.is_active
+        && attester.reputation_score >= MIN_ATTESTER_REPUTATION
+        && attester.allowed_types & attestation_type_mask(attestation_type) != 0
+}
+
+// Move an attester's reputation with the outcome of a loan they vouched for
+pub fn apply_attester_outcome(attester: &mut Attester, repaid: bool) {
+    if repaid {
+        attester.successful_outcomes = attester.successful_outcomes.saturating_add(1);
+        attester.reputation_score = attester.reputation_score.saturating_add(10).min(1000);
+    } else {
+        attester.defaulted_outcomes = attester.defaulted_outcomes.saturating_add(1);
+        attester.reputation_score = attester.reputation_score.saturating_sub(50);
+    }
+}
+
+// Settle reputation for every registered attester that backed a loan.
+// Their Attester accounts must be passed, writable, as remaining accounts.
+pub fn settle_backing_attesters<'info>(
+    backing_attesters: &[Pubkey],
+    remaining_accounts: &'info [AccountInfo<'info>],
+    repaid: bool,
+) -> Result<()> {
+    let mut settled: Vec<Pubkey> = Vec::new();
+    for account_info in remaining_accounts {
+        let Ok(mut attester) = Account::<Attester>::try_from(account_info) else {
+            continue;
+        };
+        if !backing_attesters.contains(&attester.attester) || settled.contains(&attester.attester)
+        {
+            continue;
+        }
+        apply_attester_outcome(&mut attester, repaid);
+        attester.exit(&crate::ID)?;
+        settled.push(attester.attester);
+    }
+
+    require!(
+        settled.len() == backing_attesters.len(),
+        MicroLendingError::MissingAttesterAccount
+    );
+    Ok(())
+}
+
+// Bit for a verification type in a pool's required_verifications mask
+pub fn verification_type_mask(verification_type: VerificationType) -> u8 {
+    1 << (verification_type as u8)
+}
+
+// Bitmask of the user's verifications that are set, not yet expired, and made by a
+// verifier that is still registered
+pub fn active_verifications(
+    user_profile: &UserProfile,
+    verifiers: &[Pubkey],
+    current_time: i64,
+) -> u8 {
+    let checks = [
+        (
+            VerificationType::Kyc,
+            user_profile.kyc_verified,
+            user_profile.kyc_expires_at,
+            user_profile.kyc_verifier,
+        ),
+        (
+            VerificationType::Phone,
+            user_profile.phone_verified,
+            user_profile.phone_expires_at,
+            user_profile.phone_verifier,
+        ),
+        (
+            VerificationType::Email,
+            user_profile.email_verified,
+            user_profile.email_expires_at,
+            user_profile.email_verifier,
+        ),
+    ];
+
+    checks
+        .iter()
+        .filter(|(_, verified, expires_at, verifier)| {
+            *verified
+                && verifiers.contains(verifier)
+                && !matches!(expires_at, Some(expires_at) if current_time >= *expires_at)
+        })
+        .fold(0, |mask, (verification_type, _, _, _)| {
+            mask | verification_type_mask(*verification_type)
+        })
+}
+
+// Bit for a collateral type in a pool's allowed_collateral_types mask
+pub fn collateral_type_mask(collateral_type: CollateralType) -> u8 {
+    1 << (collateral_type as u8)
+}
+
+pub fn collateral_type_from_u8(collateral_type: u8) -> Option<CollateralType> {
+    match collateral_type {
+        0 => Some(CollateralType::None),
+        1 => Some(CollateralType::Social),
+        2 => Some(CollateralType::Asset),
+        3 => Some(CollateralType::Income),
+        4 => Some(CollateralType::Group),
+        _ => None,
+    }
+}
+
+// First criterion of a pool's borrower policy a loan request fails, if any
+#[allow(clippy::too_many_arguments)]
+pub fn borrower_policy_rejection(
+    policy: &BorrowerPolicy,
+    user_profile: &UserProfile,
+    verifiers: &[Pubkey],
+    amount: u64,
+    collateral_type: CollateralType,
+    purpose: &str,
+    valid_attestations: usize,
+    current_time: i64,
+) -> Option<MicroLendingError> {
+    let verifications = active_verifications(user_profile, verifiers, current_time);
+    if user_profile.credit_score < policy.min_credit_score {
+        Some(MicroLendingError::CreditScoreBelowPoolMinimum)
+    } else if verifications & policy.required_verifications != policy.required_verifications {
+        Some(MicroLendingError::MissingRequiredVerification)
+    } else if valid_attestations < policy.min_attestations as usize {
+        Some(MicroLendingError::PoolAttestationMinimumNotMet)
+    } else if policy.allowed_collateral_types & collateral_type_mask(collateral_type) == 0 {
+        Some(MicroLendingError::CollateralTypeNotAllowed)
+    } else if !policy.allowed_purposes.is_empty()
+        && !policy
+            .allowed_purposes
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(purpose))
+    {
+        Some(MicroLendingError::LoanPurposeNotAllowed)
+    } else if policy.max_loan_per_borrower != 0 && amount > policy.max_loan_per_borrower {
+        Some(MicroLendingError::LoanExceedsPoolBorrowerLimit)
+    } else {
+        None
+    }
+}
+
+// Check a loan request against a pool's borrower policy, one error per criterion
+#[allow(clippy::too_many_arguments)]
+pub fn check_borrower_policy(
+    policy: &BorrowerPolicy,
+    user_profile: &UserProfile,
+    verifiers: &[Pubkey],
+    amount: u64,
+    collateral_type: CollateralType,
+    purpose: &str,
+    valid_attestations: usize,
+    current_time: i64,
+) -> Result<()> {
+    match borrower_policy_rejection(
+        policy,
+        user_profile,
+        verifiers,
+        amount,
+        collateral_type,
+        purpose,
+        valid_attestations,
+        current_time,
+    ) {
+        Some(rejection) => Err(rejection.into()),
+        None => Ok(()),
+    }
+}
+
+// Reserve pool liquidity for an approved loan
+pub fn reserve_loan_liquidity(lending_pool: &mut LendingPool, amount: u64) -> Result<()> {
+    lending_pool.available_liquidity = lending_pool
+        .available_liquidity
+        .checked_sub(amount)
+        .ok_or(MicroLendingError::InsufficientLiquidity)?;
+    lending_pool.total_reserved = lending_pool.total_reserved.checked_add(amount).unwrap();
+    lending_pool.active_loans = lending_pool.active_loans.checked_add(1).unwrap();
+    Ok(())
+}
+
+// Return liquidity reserved for an approved loan that will not be disbursed
+pub fn release_loan_reservation(lending_pool: &mut LendingPool, amount: u64) {
+    lending_pool.available_liquidity = lending_pool
+        .available_liquidity
+        .checked_add(amount)
+        .unwrap();
+    lending_pool.total_reserved = lending_pool.total_reserved.saturating_sub(amount);
+    lending_pool.active_loans = lending_pool.active_loans.saturating_sub(1);
+}
+
+// Share of pool assets lent out or reserved, in basis points, after lending `amount` more
+pub fn pool_utilization_after(lending_pool: &LendingPool, amount: u64) -> u16 {
+    let committed = lending_pool.total_borrowed as u128 + lending_pool.total_reserved as u128;
+    let total_assets = committed + lending_pool.available_liquidity as u128;
+    if total_assets == 0 {
+        return 10000;
+    }
+    ((committed + amount as u128) * 10000 / total_assets).min(10000) as u16
+}
+
+// Pool assets backing lender shares. Interest already credited through interest_per_share
+// belongs to the lenders it was credited to, so it does not count toward the share price.
+pub fn pool_share_assets(lending_pool: &LendingPool) -> u64 {
+    let interest_owed = lending_pool
+        .total_interest_earned
+        .saturating_sub(lending_pool.total_interest_distributed);
+    lending_pool
+        .available_liquidity
+        .saturating_add(lending_pool.total_reserved)
+        .saturating_add(lending_pool.total_borrowed)
+        .saturating_sub(interest_owed)
+}
+
+// Shares minted for a deposit, priced at the pool's share assets before the deposit
+pub fn calculate_shares_to_mint(lending_pool: &LendingPool, amount: u64) -> Result<u64> {
+    if lending_pool.total_shares == 0 {
+        return Ok(amount);
+    }
+    let share_assets = pool_share_assets(lending_pool);
+    require!(share_assets > 0, MicroLendingError::PoolInsolvent);
+    let shares = amount as u128 * lending_pool.total_shares as u128 / share_assets as u128;
+    u64::try_from(shares).map_err(|_| MicroLendingError::PoolInsolvent.into())
+}
+
+// Tokens backing `shares`, not counting their unclaimed interest
+pub fn calculate_share_value(lending_pool: &LendingPool, shares: u64) -> u64 {
+    (shares as u128 * pool_share_assets(lending_pool) as u128)
+        .checked_div(lending_pool.total_shares as u128)
+        .unwrap_or(0) as u64
+}
+
+// Check whether a loan request falls inside the pool's auto-approval rules
+pub fn qualifies_for_auto_approval(
+    lending_pool: &LendingPool,
+    credit_score: u16,
+    amount: u64,
+) -> bool {
+    let rules = &lending_pool.auto_approval;
+    // Committee pools leave every decision to the committee
+    if !rules.enabled
+        || lending_pool.committee.is_some()
+        || amount > lending_pool.available_liquidity
+    {
+        return false;
+    }
+
+    // The highest band the borrower's score reaches sets the amount limit
+    let band = rules
+        .score_bands
+        .iter()
+        .filter(|band| credit_score >= band.min_credit_score)
+        .max_by_key(|band| band.min_credit_score);
+
+    match band {
+        Some(band) => {
+            amount <= band.max_amount
+                && pool_utilization_after(lending_pool, amount) <= rules.max_utilization_bps
+        }
+        None => false,
+    }
+}
+
+// Check whether a requested or approved loan has outlived the pool's TTL
+pub fn is_loan_stale(loan: &Loan, lending_pool: &LendingPool, current_time: i64) -> bool {
+    let (started_at, ttl) = match loan.status {
+        LoanStatus::Requested => (loan.created_at, lending_pool.request_ttl),
+        LoanStatus::Approved => (loan.approved_at, lending_pool.approval_ttl),
+        _ => return false,
+    };
+    ttl > 0 && current_time > started_at.saturating_add(ttl)
+}
+
+// Validate a committee's member set, quorum and voting window
+pub fn validate_committee(members: &[Pubkey], quorum: u8, voting_window: i64) -> Result<()> {
+    require!(
+        !members.is_empty() && members.len() <= MAX_COMMITTEE_MEMBERS,
+        MicroLendingError::InvalidCommitteeConfiguration
+    );
+    require!(
+        members
+            .iter()
+            .enumerate()
+            .all(|(i, member)| !members[..i].contains(member)),
+        MicroLendingError::InvalidCommitteeConfiguration
+    );
+    require!(
+        quorum > 0 && quorum as usize <= members.len(),
+        MicroLendingError::InvalidCommitteeConfiguration
+    );
+    require!(
+        voting_window > 0,
+        MicroLendingError::InvalidCommitteeConfiguration
+    );
+    Ok(())
+}
+
+// Approve and reject votes on a loan from the committee's current members
+pub fn committee_tally(loan: &Loan, committee: &LoanCommittee) -> (u8, u8) {
+    loan.committee_votes
+        .iter()
+        .filter(|vote| committee.members.contains(&vote.member))
+        .fold((0, 0), |(approve, reject), vote| {
+            if vote.approve {
+                (approve + 1, reject)
+            } else {
+                (approve, reject + 1)
+            }
+        })
+}
+
+// Once enough current members reject a loan, the quorum can no longer be reached
+pub fn committee_rejects(committee: &LoanCommittee, reject_votes: u8) -> bool {
+    reject_votes as usize
+        > committee
+            .members
+            .len()
+            .saturating_sub(committee.quorum as usize)
+}
+
+// Resolve the loan officer account a loan was originated by, if any
+pub fn loan_officer_for<'a, 'info>(
+    loan: &Loan,
+    loan_officer: &'a mut Option<Account<'info, LoanOfficer>>,
+) -> Result<Option<&'a mut Account<'info, LoanOfficer>>> {
+    let Some(officer_key) = loan.loan_officer else {
+        return Ok(None);
+    };
+    let loan_officer = loan_officer
+        .as_mut()
+        .filter(|officer| officer.officer == officer_key && officer.pool == loan.lender_pool)
+        .ok_or(MicroLendingError::LoanOfficerAccountRequired)?;
+    Ok(Some(loan_officer))
+}
+
+// Recompute the share of an officer's disbursed principal that was written off. This is
+// not portfolio-at-risk, which depends on how overdue open loans are at a point in time
+// and is reported by the indexer.
+pub fn update_officer_write_off_rate(loan_officer: &mut LoanOfficer) {
+    loan_officer.write_off_rate_bps = if loan_officer.total_disbursed == 0 {
+        0
+    } else {
+        (loan_officer.defaulted_principal as u128 * 10000 / loan_officer.total_disbursed as u128)
+            .min(10000) as u16
+    };
+}
+
+// Message a borrower signs to consent to a loan officer requesting `consent` for them
+pub fn loan_consent_message(consent: &LoanConsent) -> Vec<u8> {
+    let mut message = LOAN_CONSENT_DOMAIN.to_vec();
+    consent.serialize(&mut message).unwrap();
+    message
+}
+
+// Check that the instruction before this one verified the borrower's signature over a
+// consent matching `expected`, apart from its expiry, which must still be ahead
+pub fn verify_loan_consent(
+    instructions: &AccountInfo,
+    borrower: &Pubkey,
+    expected: &LoanConsent,
+    current_time: i64,
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions)?;
+    require!(current_index > 0, MicroLendingError::InvalidBorrowerConsent);
+    let ed25519 = load_instruction_at_checked(current_index as usize - 1, instructions)?;
+    require!(
+        ed25519.program_id == ed25519_program::ID,
+        MicroLendingError::InvalidBorrowerConsent
+    );
+
+    // A single signature whose key, signature and message all sit in that instruction
+    let data = &ed25519.data;
+    require!(
+        data.len() >= 16 && data[0] == 1,
+        MicroLendingError::InvalidBorrowerConsent
+    );
+    let offset = |field: usize| u16::from_le_bytes([data[2 + 2 * field], data[3 + 2 * field]]);
+    require!(
+        [1, 3, 6].iter().all(|&field| offset(field) == u16::MAX),
+        MicroLendingError::InvalidBorrowerConsent
+    );
+    let field_bytes = |start: u16, len: usize| data.get(start as usize..start as usize + len);
+    let signer = field_bytes(offset(2), 32).ok_or(MicroLendingError::InvalidBorrowerConsent)?;
+    require!(
+        signer == borrower.as_ref(),
+        MicroLendingError::InvalidBorrowerConsent
+    );
+    let message = field_bytes(offset(4), offset(5) as usize)
+        .and_then(|message| message.strip_prefix(LOAN_CONSENT_DOMAIN))
+        .ok_or(MicroLendingError::InvalidBorrowerConsent)?;
+
+    let consent = LoanConsent::try_from_slice(message)
+        .map_err(|_| MicroLendingError::InvalidBorrowerConsent)?;
+    require!(
+        consent.expires_at > current_time,
+        MicroLendingError::InvalidBorrowerConsent
+    );
+    require!(
+        consent
+            == LoanConsent {
+                expires_at: consent.expires_at,
+                ..expected.clone()
+            },
+        MicroLendingError::InvalidBorrowerConsent
+    );
+    Ok(())
+}
+
+// Platform fee within a payment. The fee is charged on top of the amount applied to the
+// loan, so paying `amount + amount * platform_fee / 10000` applies exactly `amount`.
+pub fn platform_fee_within(payment_amount: u64, platform_fee: u16) -> u64 {
+    (payment_amount as u128 * platform_fee as u128 / (10000 + platform_fee as u128)) as u64
+}
+
+// How make_payment splits a payment
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PaymentSettlement {
+    pub platform_fee: u64,
+    pub interest_paid: u64,
+    pub early_payoff_rebate: u64,
+    pub late_fee: u64,
+    pub principal_paid: u64,
+}
+
+// Split `payment_amount` against the loan's payoff quote at the time of payment. Only a
+// payment clearing the loan gets the early payoff rebate, and any payment must at least
+// cover the interest and late fee.
+pub fn settle_payment(
+    quote: &PayoffQuote,
+    platform_fee: u16,
+    payment_amount: u64,
+) -> Result<PaymentSettlement> {
+    require!(
+        payment_amount > 0 && payment_amount <= quote.total_due + quote.platform_fee,
+        MicroLendingError::InvalidPaymentAmount
+    );
+    let fee = platform_fee_within(payment_amount, platform_fee);
+    let applied = payment_amount - fee;
+    let (interest_paid, early_payoff_rebate) = if applied >= quote.total_due {
+        (quote.interest_accrued, quote.early_payoff_rebate)
+    } else {
+        (quote.interest_accrued + quote.early_payoff_rebate, 0)
+    };
+    let lender_charges = interest_paid + quote.late_fee;
+    require!(
+        applied >= lender_charges,
+        MicroLendingError::InvalidPaymentAmount
+    );
+    Ok(PaymentSettlement {
+        platform_fee: fee,
+        interest_paid,
+        early_payoff_rebate,
+        late_fee: quote.late_fee,
+        principal_paid: applied - lender_charges,
+    })
+}
+
+// Score penalty for loans that needed rescheduling, capped at five loans
+pub fn calculate_restructure_penalty(restructured_loans: u16) -> u16 {
+    restructured_loans.min(5) * RESTRUCTURE_SCORE_PENALTY
+}
+
+// Suspend payments on a loan for a number of days, shifting its due date by the same amount
+pub fn apply_forbearance(loan: &mut Loan, days: u16, current_time: i64) {
+    let start = loan.forbearance_until.max(current_time);
+    let end = start + (days as i64 * 86400);
+    loan.due_date += end - start;
+    loan.forbearance_until = end;
+    loan.forbearance_count = loan.forbearance_count.saturating_add(1);
+}
+
+// Extend a loan that was outstanding when a disaster was declared on its pool
+pub fn sync_disaster_forbearance(loan: &mut Loan, lending_pool: &LendingPool) -> bool {
+    let declared_at = lending_pool.disaster_declared_at;
+    if declared_at == 0
+        || loan.disbursed_at == 0
+        || loan.disbursed_at >= declared_at
+        || loan.forbearance_until >= lending_pool.disaster_forbearance_until
+    {
+        return false;
+    }
+    let start = loan.forbearance_until.max(declared_at);
+    loan.due_date += lending_pool.disaster_forbearance_until - start;
+    loan.forbearance_until = lending_pool.disaster_forbearance_until;
+    loan.forbearance_count = loan.forbearance_count.saturating_add(1);
+    true
+}
+
+// Apply a pending disaster forbearance to a loan account, recording it for indexers,
+// which otherwise could not tell when the loan's due date moved
+pub fn apply_disaster_forbearance(
+    loan: &mut Account<Loan>,
+    lending_pool: &LendingPool,
+    current_time: i64,
+) {
+    let old_due_date = loan.due_date;
+    if sync_disaster_forbearance(loan, lending_pool) {
+        emit!(DisasterForbearanceApplied {
+            loan: loan.key(),
+            borrower: loan.borrower,
+            pool: loan.lender_pool,
+            forbearance_until: loan.forbearance_until,
+            old_due_date,
+            new_due_date: loan.due_date,
+            timestamp: current_time,
+        });
+    }
+}
+
+pub fn is_in_forbearance(loan: &Loan, current_time: i64) -> bool {
+    current_time < loan.forbearance_until
+}
+
+// Current per-borrower loan limit under the platform's lending ladder
+pub fn borrower_loan_limit(user_profile: &UserProfile, lending_ladder: &LendingLadder) -> u64 {
+    if lending_ladder.first_loan_cap == 0 {
+        u64::MAX
+    } else if user_profile.borrowing_limit == 0 {
+        lending_ladder.first_loan_cap
+    } else {
+        user_profile.borrowing_limit
+    }
+}
+
+// Move the borrower up the ladder after an on-time repayment, down otherwise
+pub fn step_borrowing_limit(
+    user_profile: &mut UserProfile,
+    platform: &Platform,
+    outcome: LoanOutcome,
+) {
+    let lending_ladder = &platform.lending_ladder;
+    if lending_ladder.first_loan_cap == 0 {
+        return;
+    }
+    let step_bps = if outcome == LoanOutcome::RepaidOnTime {
+        lending_ladder.step_up_bps
+    } else {
+        lending_ladder.step_down_bps
+    };
+    let current_limit = borrower_loan_limit(user_profile, lending_ladder);
+    let new_limit = (current_limit as u128 * step_bps as u128 / 10000) as u64;
+    user_profile.borrowing_limit =
+        new_limit.clamp(platform.min_loan_amount, platform.max_loan_amount);
+}
+
+// Installment size that counts toward an on-time streak: the schedule spread over weekly
+// periods, the last of which may be short
+pub fn expected_installment(loan: &Loan) -> u64 {
+    let periods = loan.duration_days.div_ceil(7).max(1) as u64;
+    loan.scheduled_repayment / periods
+}
+
+// Apply a rate step-down once the loan's streak reaches a multiple of the pool's streak length
+pub fn step_down_interest_rate(loan: &mut Loan, incentives: &RepaymentIncentives) -> Option<u16> {
+    if loan.on_time_streak.checked_rem(incentives.streak_length) != Some(0) {
+        return None;
+    }
+    let new_rate = loan
+        .interest_rate
+        .saturating_sub(incentives.rate_step_down_bps)
+        .max(incentives.min_interest_rate);
+    if new_rate >= loan.interest_rate {
+        return None;
+    }
+    let old_rate = loan.interest_rate;
+    loan.interest_rate = new_rate;
+    loan.rate_step_downs = loan.rate_step_downs.saturating_add(1);
+    Some(old_rate)
+}
+
 // Calculate days between timestamps
 pub fn days_between(start: i64, end: i64) -> u32 {
     let diff = end - start;
@@ -0,0 +1,109 @@
+use crate::error::*;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+pub fn vote_on_loan(ctx: Context<VoteOnLoan>, approve: bool) -> Result<()> {
+    let loan = &mut ctx.accounts.loan;
+    let committee = &ctx.accounts.loan_committee;
+    let vote = &mut ctx.accounts.loan_vote;
+    let current = Clock::get()?.unix_timestamp;
+
+    require!(
+        loan.status == LoanStatus::Requested,
+        MicroLendingError::InvalidLoanState
+    );
+    require!(
+        current <= loan.created_at.saturating_add(committee.voting_window),
+        MicroLendingError::VotingWindowClosed
+    );
+
+    // The vote account stays on-chain as the audit record
+    vote.loan = loan.key();
+    vote.member = ctx.accounts.member.key();
+    vote.approve = approve;
+    vote.voted_at = current;
+
+    // Votes from members since removed no longer count
+    loan.committee_votes
+        .retain(|vote| committee.members.contains(&vote.member));
+    loan.committee_votes.push(CommitteeVote {
+        member: vote.member,
+        approve,
+    });
+    let (approve_votes, reject_votes) = committee_tally(loan, committee);
+
+    msg!(
+        "Committee member {} voted {} on loan for borrower: {}",
+        vote.member,
+        if approve { "approve" } else { "reject" },
+        loan.borrower
+    );
+    emit!(LoanVoteCast {
+        loan: loan.key(),
+        member: vote.member,
+        approve,
+        approve_votes,
+        reject_votes,
+        timestamp: current,
+    });
+
+    // Too many rejections to reach the quorum turn the request down, as reject_loan does
+    if committee_rejects(committee, reject_votes) {
+        msg!("Loan rejected by committee for borrower: {}", loan.borrower);
+        emit!(LoanClosedBeforeDisbursement {
+            loan: loan.key(),
+            borrower: loan.borrower,
+            pool: ctx.accounts.lending_pool.key(),
+            amount: loan.amount,
+            previous_status: LoanStatus::Requested,
+            reason: LoanClosureReason::Rejected,
+            rejection_code: None,
+            timestamp: current,
+        });
+        loan.close(ctx.accounts.borrower.to_account_info())?;
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VoteOnLoan<'info> {
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    #[account(
+        seeds = [b"loan_committee", lending_pool.key().as_ref()],
+        bump,
+        constraint = loan_committee.members.contains(&member.key()) @ MicroLendingError::NotCommitteeMember
+    )]
+    pub loan_committee: Account<'info, LoanCommittee>,
+
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"loan", loan.borrower.as_ref(), lending_pool.key().as_ref()],
+        bump
+    )]
+    pub loan: Account<'info, Loan>,
+
+    /// CHECK: Receives the loan account rent if the committee rejects; must match loan.borrower.
+    #[account(
+        mut,
+        constraint = borrower.key() == loan.borrower @ MicroLendingError::InvalidBorrowerAccount
+    )]
+    pub borrower: AccountInfo<'info>,
+
+    // Seeded by the request time so votes on a later loan at the same PDA start fresh
+    #[account(
+        init,
+        payer = member,
+        space = 8 + LoanVote::INIT_SPACE,
+        seeds = [b"loan_vote", loan.key().as_ref(), &loan.created_at.to_le_bytes(), member.key().as_ref()],
+        bump
+    )]
+    pub loan_vote: Account<'info, LoanVote>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,5 +1,8 @@
 use crate::error::*;
+use crate::events::*;
+use crate::get_unclaimed_interest;
 use crate::states::*;
+use crate::utils::*;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 
@@ -17,19 +20,24 @@ pub fn withdraw_from_pool(ctx: Context<WithdrawFromPool>, shares_to_withdraw: u6
         MicroLendingError::InsufficientShares
     );
 
-    // First, claim any outstanding interest to ensure balances are up-to-date
-    let unclaimed_interest =
-        (lender_deposit.shares as u128 * lending_pool.interest_per_share as u128 / 1_000_000_000)
-            .saturating_sub(lender_deposit.interest_debt as u128) as u64;
+    let lender_shares_before = lender_deposit.shares;
+    let pool_liquidity_before = lending_pool.available_liquidity;
 
-    msg!("Uncalimed Interest : {}", unclaimed_interest);
+    // Price the shares first: the lender's unclaimed interest is owed to them on top of the
+    // share value, so it must still be excluded from the pool's share assets
+    let withdraw_amount = calculate_share_value(lending_pool, shares_to_withdraw);
+    msg!("Share value : {}", withdraw_amount);
+
+    // Claim any outstanding interest along with the withdrawal
+    let unclaimed_interest = get_unclaimed_interest(lender_deposit, lending_pool);
+    msg!("Unclaimed Interest : {}", unclaimed_interest);
     if unclaimed_interest > 0 {
         lender_deposit.interest_claimed = lender_deposit
             .interest_claimed
             .checked_add(unclaimed_interest)
             .unwrap();
-        lender_deposit.interest_debt = lender_deposit
-            .interest_debt
+        lender_deposit.interest_earned = lender_deposit
+            .interest_earned
             .checked_add(unclaimed_interest)
             .unwrap();
         lending_pool.total_interest_distributed = lending_pool
@@ -38,19 +46,6 @@ pub fn withdraw_from_pool(ctx: Context<WithdrawFromPool>, shares_to_withdraw: u6
             .unwrap();
     }
 
-    // Calculate the value of shares to withdraw
-    let total_assets = lending_pool
-        .available_liquidity
-        .checked_add(lending_pool.total_borrowed)
-        .unwrap();
-
-    msg!("Total assets in pool : {}", total_assets);
-    let withdraw_amount = (shares_to_withdraw as u128)
-        .checked_mul(total_assets as u128)
-        .unwrap()
-        .checked_div(lending_pool.total_shares as u128)
-        .unwrap() as u64;
-
     let total_withdraw_amount = withdraw_amount.checked_add(unclaimed_interest).unwrap();
     msg!("Total withdraw amount : {}", total_withdraw_amount);
     require!(
@@ -81,19 +76,20 @@ pub fn withdraw_from_pool(ctx: Context<WithdrawFromPool>, shares_to_withdraw: u6
         .shares
         .checked_sub(shares_to_withdraw)
         .unwrap();
+    // Interest accrues on the remaining shares from here on
+    lender_deposit.interest_debt = (lender_deposit.shares as u128
+        * lending_pool.interest_per_share as u128
+        / 1_000_000_000) as u64;
+    // Share value can drift above the amount deposited by rounding
     lender_deposit.amount_deposited = lender_deposit
         .amount_deposited
-        .checked_sub(total_withdraw_amount)
-        .unwrap();
+        .saturating_sub(withdraw_amount);
 
     lending_pool.total_shares = lending_pool
         .total_shares
         .checked_sub(shares_to_withdraw)
         .unwrap();
-    lending_pool.total_deposited = lending_pool
-        .total_deposited
-        .checked_sub(total_withdraw_amount)
-        .unwrap();
+    lending_pool.total_deposited = lending_pool.total_deposited.saturating_sub(withdraw_amount);
     lending_pool.available_liquidity = lending_pool
         .available_liquidity
         .checked_sub(total_withdraw_amount)
@@ -105,6 +101,19 @@ pub fn withdraw_from_pool(ctx: Context<WithdrawFromPool>, shares_to_withdraw: u6
         shares_to_withdraw,
         ctx.accounts.lender.key()
     );
+    emit!(Withdrawn {
+        pool: lending_pool.key(),
+        lender: ctx.accounts.lender.key(),
+        shares_burned: shares_to_withdraw,
+        principal_amount: withdraw_amount,
+        interest_amount: unclaimed_interest,
+        total_amount: total_withdraw_amount,
+        lender_shares_before,
+        lender_shares_after: lender_deposit.shares,
+        pool_liquidity_before,
+        pool_liquidity_after: lending_pool.available_liquidity,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     Ok(())
 }
 
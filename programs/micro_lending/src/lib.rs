@@ -1,138 +1,418 @@
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 use anchor_lang::prelude::*;
 
 pub use constants::*;
+pub use events::*;
+// Anchor's #[program] module re-exports each handler under its instruction's name
+#[allow(ambiguous_glob_reexports)]
 pub use instructions::*;
 pub use state::*;
 
 declare_id!("2qH5FVMCSDgQsDJ7ZwvKZGfchazPsEv168wNTuoZuYKu");
 
-#[program]
-pub mod micro_lending {
+// #[program] generates Anchor's IDL account handlers, which call the deprecated
+// AccountInfo::realloc, beside the program module where no item attribute reaches them.
+// Wrapping both in a module scopes the allow to the generated code.
+#[allow(deprecated)]
+mod dispatch {
     use super::*;
 
-    // Initialize the lending platform
-    pub fn initialize_platform(
-        ctx: Context<InitializePlatform>,
-        platform_authority: Pubkey,
-        treasury_bump: u8,
-        platform_fee: u16, // basis points (e.g., 100 = 1%)
-        max_loan_amount: u64,
-        min_loan_amount: u64,
-    ) -> Result<()> {
-        instructions::initialize_platform(
-            ctx,
-            platform_authority,
-            treasury_bump,
-            platform_fee,
-            max_loan_amount,
-            min_loan_amount,
-        )?;
-        Ok(())
-    }
+    #[program]
+    pub mod micro_lending {
+        use super::*;
 
-    pub fn initialize_user(ctx: Context<InitializeUser>) -> Result<()> {
-        instructions::initialize_user(ctx)?;
-        Ok(())
-    }
+        // Initialize the lending platform
+        #[allow(clippy::too_many_arguments)]
+        pub fn initialize_platform(
+            ctx: Context<InitializePlatform>,
+            platform_authority: Pubkey,
+            treasury_bump: u8,
+            platform_fee: u16, // basis points (e.g., 100 = 1%)
+            max_loan_amount: u64,
+            min_loan_amount: u64,
+            score_half_life_days: u16,
+            inactivity_threshold_days: u16,
+        ) -> Result<()> {
+            instructions::initialize_platform(
+                ctx,
+                platform_authority,
+                treasury_bump,
+                platform_fee,
+                max_loan_amount,
+                min_loan_amount,
+                score_half_life_days,
+                inactivity_threshold_days,
+            )?;
+            Ok(())
+        }
 
-    pub fn create_lending_pool(
-        ctx: Context<CreateLendingPool>,
-        pool_name: String,
-        base_interest_rate: u16,
-        max_loan_duration: i64,
-    ) -> Result<()> {
-        instructions::create_lending_pool(ctx, pool_name, base_interest_rate, max_loan_duration)?;
-        Ok(())
-    }
+        pub fn initialize_user(ctx: Context<InitializeUser>) -> Result<()> {
+            instructions::initialize_user(ctx)?;
+            Ok(())
+        }
 
-    pub fn request_loan(
-        ctx: Context<RequestLoan>,
-        amount: u64,
-        duration_days: u32,
-        purpose: String,
-        collateral_type: u8,
-    ) -> Result<()> {
-        instructions::request_loan(ctx, amount, duration_days, purpose, collateral_type)?;
-        Ok(())
-    }
+        pub fn create_lending_pool(
+            ctx: Context<CreateLendingPool>,
+            pool_name: String,
+            base_interest_rate: u16,
+            max_loan_duration: i64,
+        ) -> Result<()> {
+            instructions::create_lending_pool(
+                ctx,
+                pool_name,
+                base_interest_rate,
+                max_loan_duration,
+            )?;
+            Ok(())
+        }
 
-    pub fn approve_loan(ctx: Context<ApproveLoan>) -> Result<()> {
-        instructions::approve_loan(ctx)?;
-        Ok(())
-    }
+        pub fn set_borrower_policy(
+            ctx: Context<SetBorrowerPolicy>,
+            policy: BorrowerPolicy,
+        ) -> Result<()> {
+            instructions::set_borrower_policy(ctx, policy)?;
+            Ok(())
+        }
 
-    pub fn disburse_loan(ctx: Context<DisburseLoan>) -> Result<()> {
-        instructions::disburse_loan(ctx)?;
-        Ok(())
-    }
+        pub fn set_pool_verification_requirements(
+            ctx: Context<SetPoolVerificationRequirements>,
+            required_verifications: u8,
+        ) -> Result<()> {
+            instructions::set_pool_verification_requirements(ctx, required_verifications)?;
+            Ok(())
+        }
 
-    pub fn repay_loan(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()> {
-        instructions::make_payment(ctx, payment_amount)?;
-        Ok(())
-    }
+        pub fn set_auto_approval_rules(
+            ctx: Context<SetAutoApprovalRules>,
+            rules: AutoApprovalRules,
+        ) -> Result<()> {
+            instructions::set_auto_approval_rules(ctx, rules)?;
+            Ok(())
+        }
 
-    pub fn deposit_to_pool(ctx: Context<DepositToPool>, amount: u64) -> Result<()> {
-        instructions::deposit_to_pool(ctx, amount)?;
-        Ok(())
-    }
+        pub fn set_repayment_incentives(
+            ctx: Context<SetRepaymentIncentives>,
+            incentives: RepaymentIncentives,
+        ) -> Result<()> {
+            instructions::set_repayment_incentives(ctx, incentives)?;
+            Ok(())
+        }
 
-    pub fn claim_interest(ctx: Context<ClaimInterest>) -> Result<()> {
-        instructions::claim_interest(ctx)?;
-        Ok(())
-    }
+        pub fn set_loan_ttls(
+            ctx: Context<SetLoanTtls>,
+            request_ttl: i64,
+            approval_ttl: i64,
+        ) -> Result<()> {
+            instructions::set_loan_ttls(ctx, request_ttl, approval_ttl)?;
+            Ok(())
+        }
 
-    pub fn withdraw_from_pool(
-        ctx: Context<WithdrawFromPool>,
-        shares_to_withdraw: u64,
-    ) -> Result<()> {
-        instructions::withdraw_from_pool(ctx, shares_to_withdraw)?;
-        Ok(())
-    }
+        pub fn register_loan_officer(
+            ctx: Context<RegisterLoanOfficer>,
+            commission_bps: u16,
+        ) -> Result<()> {
+            instructions::register_loan_officer(ctx, commission_bps)?;
+            Ok(())
+        }
 
-    pub fn add_attestation(
-        ctx: Context<AddAttestation>,
-        attestation_type: u8,
-        score: u16,
-        metadata: String,
-        expires_at: Option<i64>,
-    ) -> Result<()> {
-        instructions::add_attestation(ctx, attestation_type, score, metadata, expires_at)?;
-        Ok(())
-    }
+        pub fn update_loan_officer(
+            ctx: Context<UpdateLoanOfficer>,
+            commission_bps: u16,
+            is_active: bool,
+        ) -> Result<()> {
+            instructions::update_loan_officer(ctx, commission_bps, is_active)?;
+            Ok(())
+        }
 
-    pub fn liquidate_loan(ctx: Context<LiquidateLoan>) -> Result<()> {
-        instructions::liquidate_loan(ctx)?;
-        Ok(())
-    }
+        pub fn request_loan<'info>(
+            ctx: Context<'_, '_, 'info, 'info, RequestLoan<'info>>,
+            amount: u64,
+            duration_days: u32,
+            purpose: String,
+            collateral_type: u8,
+        ) -> Result<()> {
+            instructions::request_loan(ctx, amount, duration_days, purpose, collateral_type)?;
+            Ok(())
+        }
 
-    pub fn update_credit_score(ctx: Context<UpdateCreditScore>) -> Result<()> {
-        instructions::update_credit_score(ctx)?;
-        Ok(())
-    }
+        pub fn create_loan_committee(
+            ctx: Context<CreateLoanCommittee>,
+            members: Vec<Pubkey>,
+            quorum: u8,
+            voting_window: i64,
+        ) -> Result<()> {
+            instructions::create_loan_committee(ctx, members, quorum, voting_window)?;
+            Ok(())
+        }
+
+        pub fn update_loan_committee(
+            ctx: Context<UpdateLoanCommittee>,
+            members: Vec<Pubkey>,
+            quorum: u8,
+            voting_window: i64,
+        ) -> Result<()> {
+            instructions::update_loan_committee(ctx, members, quorum, voting_window)?;
+            Ok(())
+        }
+
+        pub fn vote_on_loan(ctx: Context<VoteOnLoan>, approve: bool) -> Result<()> {
+            instructions::vote_on_loan(ctx, approve)?;
+            Ok(())
+        }
+
+        pub fn approve_loan(ctx: Context<ApproveLoan>) -> Result<()> {
+            instructions::approve_loan(ctx)?;
+            Ok(())
+        }
+
+        pub fn reject_loan(ctx: Context<RejectLoan>, reason_code: u8) -> Result<()> {
+            instructions::reject_loan(ctx, reason_code)?;
+            Ok(())
+        }
+
+        pub fn cancel_loan_request(ctx: Context<CancelLoanRequest>) -> Result<()> {
+            instructions::cancel_loan_request(ctx)?;
+            Ok(())
+        }
+
+        pub fn expire_loan(ctx: Context<ExpireLoan>) -> Result<()> {
+            instructions::expire_loan(ctx)?;
+            Ok(())
+        }
+
+        pub fn disburse_loan(ctx: Context<DisburseLoan>) -> Result<()> {
+            instructions::disburse_loan(ctx)?;
+            Ok(())
+        }
+
+        pub fn repay_loan<'info>(
+            ctx: Context<'_, '_, 'info, 'info, MakePayment<'info>>,
+            payment_amount: u64,
+        ) -> Result<()> {
+            instructions::make_payment(ctx, payment_amount)?;
+            Ok(())
+        }
+
+        pub fn deposit_to_pool(ctx: Context<DepositToPool>, amount: u64) -> Result<()> {
+            instructions::deposit_to_pool(ctx, amount)?;
+            Ok(())
+        }
+
+        pub fn claim_interest(ctx: Context<ClaimInterest>) -> Result<()> {
+            instructions::claim_interest(ctx)?;
+            Ok(())
+        }
+
+        pub fn withdraw_from_pool(
+            ctx: Context<WithdrawFromPool>,
+            shares_to_withdraw: u64,
+        ) -> Result<()> {
+            instructions::withdraw_from_pool(ctx, shares_to_withdraw)?;
+            Ok(())
+        }
 
-    pub fn add_transaction_history(
-        ctx: Context<AddTransactionHistory>,
-        transaction_type: TransactionType,
-        amount: u64,
-        counterparty: Option<Pubkey>,
-        timestamp: i64,
-        frequency_score: u16,
-        consistency_score: u16,
-    ) -> Result<()> {
-        instructions::add_transaction_history(
-            ctx,
-            transaction_type,
-            amount,
-            counterparty,
-            timestamp,
-            frequency_score,
-            consistency_score,
-        )?;
-        Ok(())
+        // Read-only quotes, returned as return data for clients to simulate
+        pub fn quote_loan<'info>(
+            ctx: Context<'_, '_, 'info, 'info, QuoteLoan<'info>>,
+            amount: u64,
+            duration_days: u32,
+            purpose: String,
+            collateral_type: u8,
+        ) -> Result<LoanQuote> {
+            instructions::quote_loan(ctx, amount, duration_days, purpose, collateral_type)
+        }
+
+        pub fn quote_payoff(ctx: Context<QuotePayoff>) -> Result<PayoffQuote> {
+            instructions::quote_payoff(ctx)
+        }
+
+        pub fn quote_withdrawal(
+            ctx: Context<QuoteLenderPosition>,
+            shares_to_withdraw: u64,
+        ) -> Result<WithdrawalQuote> {
+            instructions::quote_withdrawal(ctx, shares_to_withdraw)
+        }
+
+        pub fn quote_pending_interest(ctx: Context<QuoteLenderPosition>) -> Result<u64> {
+            instructions::quote_pending_interest(ctx)
+        }
+
+        pub fn add_attestation(
+            ctx: Context<AddAttestation>,
+            attestation_type: u8,
+            score: u16,
+            metadata: String,
+            expires_at: Option<i64>,
+        ) -> Result<()> {
+            instructions::add_attestation(ctx, attestation_type, score, metadata, expires_at)?;
+            Ok(())
+        }
+
+        pub fn register_attester(
+            ctx: Context<RegisterAttester>,
+            name: String,
+            allowed_types: u8,
+        ) -> Result<()> {
+            instructions::register_attester(ctx, name, allowed_types)?;
+            Ok(())
+        }
+
+        pub fn update_attester(
+            ctx: Context<UpdateAttester>,
+            allowed_types: u8,
+            is_active: bool,
+        ) -> Result<()> {
+            instructions::update_attester(ctx, allowed_types, is_active)?;
+            Ok(())
+        }
+
+        pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+            instructions::revoke_attestation(ctx)?;
+            Ok(())
+        }
+
+        pub fn renew_attestation(
+            ctx: Context<RenewAttestation>,
+            expires_at: Option<i64>,
+        ) -> Result<()> {
+            instructions::renew_attestation(ctx, expires_at)?;
+            Ok(())
+        }
+
+        pub fn update_attestation(
+            ctx: Context<UpdateAttestation>,
+            score: u16,
+            metadata: String,
+        ) -> Result<()> {
+            instructions::update_attestation(ctx, score, metadata)?;
+            Ok(())
+        }
+
+        pub fn expire_attestation(ctx: Context<ExpireAttestation>) -> Result<()> {
+            instructions::expire_attestation(ctx)?;
+            Ok(())
+        }
+
+        pub fn restructure_loan(
+            ctx: Context<RestructureLoan>,
+            new_duration_days: u32,
+            new_interest_rate: u16,
+            capitalize_arrears: bool,
+        ) -> Result<()> {
+            instructions::restructure_loan(
+                ctx,
+                new_duration_days,
+                new_interest_rate,
+                capitalize_arrears,
+            )?;
+            Ok(())
+        }
+
+        pub fn refinance_loan<'info>(
+            ctx: Context<'_, '_, 'info, 'info, RefinanceLoan<'info>>,
+            amount: u64,
+            duration_days: u32,
+        ) -> Result<()> {
+            instructions::refinance_loan(ctx, amount, duration_days)?;
+            Ok(())
+        }
+
+        pub fn grant_forbearance(ctx: Context<GrantForbearance>, days: u16) -> Result<()> {
+            instructions::grant_forbearance(ctx, days)?;
+            Ok(())
+        }
+
+        pub fn declare_disaster(
+            ctx: Context<DeclareDisaster>,
+            forbearance_days: u16,
+        ) -> Result<()> {
+            instructions::declare_disaster(ctx, forbearance_days)?;
+            Ok(())
+        }
+
+        pub fn liquidate_loan<'info>(
+            ctx: Context<'_, '_, 'info, 'info, LiquidateLoan<'info>>,
+        ) -> Result<()> {
+            instructions::liquidate_loan(ctx)?;
+            Ok(())
+        }
+
+        pub fn update_credit_score<'info>(
+            ctx: Context<'_, '_, 'info, 'info, UpdateCreditScore<'info>>,
+        ) -> Result<()> {
+            instructions::update_credit_score(ctx)?;
+            Ok(())
+        }
+
+        pub fn add_verifier(ctx: Context<AddVerifier>, verifier: Pubkey) -> Result<()> {
+            instructions::add_verifier(ctx, verifier)?;
+            Ok(())
+        }
+
+        pub fn remove_verifier(ctx: Context<RemoveVerifier>, verifier: Pubkey) -> Result<()> {
+            instructions::remove_verifier(ctx, verifier)?;
+            Ok(())
+        }
+
+        pub fn set_lending_ladder(
+            ctx: Context<SetLendingLadder>,
+            first_loan_cap: u64,
+            step_up_bps: u16,
+            step_down_bps: u16,
+        ) -> Result<()> {
+            instructions::set_lending_ladder(ctx, first_loan_cap, step_up_bps, step_down_bps)?;
+            Ok(())
+        }
+
+        pub fn set_verification(
+            ctx: Context<SetVerification>,
+            verification_type: VerificationType,
+            verified: bool,
+            expires_at: Option<i64>,
+            evidence_hash: [u8; 32],
+        ) -> Result<()> {
+            instructions::set_verification(
+                ctx,
+                verification_type,
+                verified,
+                expires_at,
+                evidence_hash,
+            )?;
+            Ok(())
+        }
+
+        pub fn add_transaction_history(
+            ctx: Context<AddTransactionHistory>,
+            transaction_type: TransactionType,
+            amount: u64,
+            counterparty: Option<Pubkey>,
+            timestamp: i64,
+            frequency_score: u16,
+            consistency_score: u16,
+        ) -> Result<()> {
+            instructions::add_transaction_history(
+                ctx,
+                transaction_type,
+                amount,
+                counterparty,
+                timestamp,
+                frequency_score,
+                consistency_score,
+            )?;
+            Ok(())
+        }
+
+        // Rewrite an account created under its first layout in the current one
+        pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+            instructions::migrate_account(ctx)?;
+            Ok(())
+        }
     }
 }
+
+pub use dispatch::*;
@@ -0,0 +1,97 @@
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+// Account layouts as first deployed. Accounts created then are still sized for these, which
+// is how migrate_account tells them apart from accounts in the current layout.
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PlatformV1 {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub treasury_bump: u8,
+    pub platform_fee: u16,
+    pub max_loan_amount: u64,
+    pub min_loan_amount: u64,
+    pub total_loans_issued: u64,
+    pub total_volume: u64,
+    pub total_defaults: u64,
+    pub is_active: bool,
+    pub created_at: i64,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UserProfileV1 {
+    pub owner: Pubkey,
+    pub credit_score: u16,
+    pub total_borrowed: u64,
+    pub total_repaid: u64,
+    pub active_loans: u8,
+    pub successful_loans: u16,
+    pub defaulted_loans: u16,
+    pub reputation_score: u16,
+    pub created_at: i64,
+    pub last_updated: i64,
+    pub kyc_verified: bool,
+    pub phone_verified: bool,
+    pub email_verified: bool,
+    pub transaction_history_count: u16,
+    pub social_attestations_count: u8,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LendingPoolV1 {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    #[max_len(500)]
+    pub name: String,
+    pub base_interest_rate: u16,
+    pub max_loan_duration: i64,
+    pub total_deposited: u64,
+    pub total_borrowed: u64,
+    pub total_shares: u64,
+    pub available_liquidity: u64,
+    pub active_loans: u32,
+    pub total_interest_earned: u64,
+    pub total_interest_distributed: u64,
+    pub interest_per_share: u64,
+    pub is_active: bool,
+    pub created_at: i64,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LoanV1 {
+    pub borrower: Pubkey,
+    pub lender_pool: Pubkey,
+    pub amount: u64,
+    pub interest_rate: u16,
+    pub duration_days: u32,
+    pub disbursed_at: i64,
+    pub due_date: i64,
+    pub amount_repaid: u64,
+    pub interest_accrued: u64,
+    pub status: LoanStatus,
+    #[max_len(50)]
+    pub purpose: String,
+    pub collateral_type: CollateralType,
+    pub collateral_value: u64,
+    pub payment_count: u16,
+    pub last_payment_date: i64,
+    pub grace_period_days: u8,
+    pub late_fee_rate: u16,
+    pub created_at: i64,
+    pub liquidated_at: Option<i64>,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SocialAttestationV1 {
+    pub user: Pubkey,
+    pub attester: Pubkey,
+    pub attestation_type: AttestationType,
+    pub score: u16,
+    #[max_len(500)]
+    pub metadata: String,
+    pub verified: bool,
+    pub created_at: i64,
+    pub expires_at: Option<i64>,
+}
@@ -1,2 +1,4 @@
+pub use legacy::*;
 pub use states::*;
+pub mod legacy;
 pub mod states;
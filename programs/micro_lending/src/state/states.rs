@@ -14,6 +14,18 @@ pub struct Platform {
     pub total_defaults: u64,
     pub is_active: bool,
     pub created_at: i64,
+    pub score_half_life_days: u16, // 0 disables recency weighting and decay
+    pub inactivity_threshold_days: u16,
+    #[max_len(10)]
+    pub verifiers: Vec<Pubkey>,
+    pub lending_ladder: LendingLadder,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct LendingLadder {
+    pub first_loan_cap: u64, // 0 disables per-borrower limits
+    pub step_up_bps: u16,    // limit multiplier after an on-time repayment
+    pub step_down_bps: u16,  // limit multiplier after a late repayment or default
 }
 
 #[account]
@@ -33,8 +45,34 @@ pub struct UserProfile {
     pub kyc_verified: bool,
     pub phone_verified: bool,
     pub email_verified: bool,
+    pub kyc_expires_at: Option<i64>,
+    pub phone_expires_at: Option<i64>,
+    pub email_expires_at: Option<i64>,
     pub transaction_history_count: u16,
     pub social_attestations_count: u8,
+    pub last_activity_at: i64,
+    #[max_len(8)]
+    pub recent_outcomes: Vec<LoanOutcomeRecord>, // ring buffer, oldest first
+    pub restructured_loans: u16,
+    pub borrowing_limit: u64, // 0 until the first loan closes; see LendingLadder
+    pub on_time_streak: u16,  // consecutive on-time installments across loans
+    // Verifier behind each verification; it only counts while they stay registered
+    pub kyc_verifier: Pubkey,
+    pub phone_verifier: Pubkey,
+    pub email_verifier: Pubkey,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct LoanOutcomeRecord {
+    pub outcome: LoanOutcome,
+    pub timestamp: i64,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LoanOutcome {
+    RepaidOnTime,
+    RepaidLate,
+    Defaulted,
 }
 
 #[account]
@@ -58,6 +96,48 @@ pub struct LendingPool {
     pub interest_per_share: u64,
     pub is_active: bool,
     pub created_at: i64,
+    pub borrower_policy: BorrowerPolicy,
+    pub total_reserved: u64, // approved but not yet disbursed
+    pub auto_approval: AutoApprovalRules,
+    pub request_ttl: i64,  // seconds, 0 = requests never expire
+    pub approval_ttl: i64, // seconds, 0 = approvals never expire
+    pub committee: Option<Pubkey>,
+    pub disaster_declared_at: i64, // 0 = no disaster declared
+    pub disaster_forbearance_until: i64,
+    pub repayment_incentives: RepaymentIncentives,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct RepaymentIncentives {
+    pub streak_length: u8, // on-time installments per rate step-down, 0 = disabled
+    pub rate_step_down_bps: u16, // taken off Loan.interest_rate at each step
+    pub min_interest_rate: u16, // step-downs never go below this
+    pub early_payoff_rebate_bps: u16, // share of accrued interest waived on early payoff
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct BorrowerPolicy {
+    pub min_credit_score: u16,
+    pub required_verifications: u8, // bitmask indexed by VerificationType
+    pub min_attestations: u8,       // currently-valid, verified attestations
+    pub allowed_collateral_types: u8, // bitmask indexed by CollateralType
+    pub max_loan_per_borrower: u64, // 0 = no pool-specific cap
+    #[max_len(5, 32)]
+    pub allowed_purposes: Vec<String>, // empty = any purpose
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct AutoApprovalRules {
+    pub enabled: bool,
+    pub max_utilization_bps: u16, // pool utilization after the loan
+    #[max_len(5)]
+    pub score_bands: Vec<ScoreBand>,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreBand {
+    pub min_credit_score: u16,
+    pub max_amount: u64,
 }
 
 #[account]
@@ -84,6 +164,80 @@ pub struct Loan {
     pub late_fee_rate: u16, // basis points
     pub created_at: i64,
     pub liquidated_at: Option<i64>,
+    #[max_len(5)]
+    pub backing_attesters: Vec<Pubkey>, // registered attesters vouching at request time
+    pub approved_at: i64,
+    #[max_len(10)]
+    pub committee_votes: Vec<CommitteeVote>, // only current members' votes count
+    pub loan_officer: Option<Pubkey>,
+    pub accrual_start: i64, // interest accrues on outstanding principal from here
+    pub scheduled_repayment: u64, // total due at due_date under the current schedule
+    pub restructure_count: u8,
+    pub restructured_at: Option<i64>,
+    pub forbearance_until: i64, // no payments due or late fees before this
+    pub forbearance_count: u8,
+    pub refinanced: bool,                // paid off by a follow-on loan
+    pub refinanced_from: Option<Pubkey>, // archived record of the loan this one paid off
+    pub on_time_streak: u8,              // consecutive on-time installments on this loan
+    pub rate_step_downs: u8,
+    pub carried_interest: u64, // accrued at an earlier rate up to accrual_start, still owed
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct LoanCommittee {
+    pub pool: Pubkey,
+    #[max_len(10)]
+    pub members: Vec<Pubkey>,
+    pub quorum: u8,         // approve votes needed
+    pub voting_window: i64, // seconds after the loan request
+    pub created_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct LoanOfficer {
+    pub pool: Pubkey,
+    pub officer: Pubkey,
+    pub commission_bps: u16, // share of interest paid to the officer
+    pub is_active: bool,
+    pub loans_originated: u32,
+    pub late_repayments: u32,
+    pub defaulted_loans: u32,
+    pub total_disbursed: u64,
+    pub outstanding_principal: u64,
+    pub defaulted_principal: u64,
+    pub write_off_rate_bps: u16, // defaulted principal over principal disbursed
+    pub commission_earned: u64,
+    pub registered_at: i64,
+}
+
+// Loan terms a borrower signs off-chain so a loan officer can submit the request and pay
+// for the loan account. Verified through an ed25519 instruction preceding request_loan.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct LoanConsent {
+    pub officer: Pubkey,
+    pub lending_pool: Pubkey,
+    pub amount: u64,
+    pub duration_days: u32,
+    pub purpose: String,
+    pub collateral_type: u8,
+    pub expires_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct LoanVote {
+    pub loan: Pubkey,
+    pub member: Pubkey,
+    pub approve: bool,
+    pub voted_at: i64,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CommitteeVote {
+    pub member: Pubkey,
+    pub approve: bool,
 }
 
 #[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -118,6 +272,24 @@ pub struct SocialAttestation {
     pub verified: bool,
     pub created_at: i64,
     pub expires_at: Option<i64>,
+    pub is_active: bool, // counted in UserProfile.social_attestations_count
+    pub updated_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Attester {
+    pub attester: Pubkey,
+    #[max_len(50)]
+    pub name: String,
+    pub allowed_types: u8, // bitmask indexed by AttestationType
+    pub reputation_score: u16,
+    pub attestations_issued: u32,
+    pub successful_outcomes: u32,
+    pub defaulted_outcomes: u32,
+    pub is_active: bool,
+    pub registered_at: i64,
+    pub updated_at: i64,
 }
 
 #[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -130,6 +302,25 @@ pub enum AttestationType {
     Reference,
 }
 
+#[account]
+#[derive(InitSpace)]
+pub struct VerificationRecord {
+    pub user: Pubkey,
+    pub verification_type: VerificationType,
+    pub verifier: Pubkey,
+    pub verified: bool,
+    pub verified_at: i64,
+    pub expires_at: Option<i64>,
+    pub evidence_hash: [u8; 32], // hash of the off-chain evidence
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationType {
+    Kyc,
+    Phone,
+    Email,
+}
+
 #[account]
 #[derive(InitSpace)]
 
@@ -184,3 +375,12 @@ pub struct RepaymentSchedule {
     pub total_late_fees: u64,
     pub auto_debit_enabled: bool,
 }
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MigratedAccountType {
+    Platform,
+    UserProfile,
+    LendingPool,
+    Loan,
+    SocialAttestation,
+}
@@ -0,0 +1,192 @@
+//! Pool and loan accounting checked directly against the program's pure helpers, without
+//! loading the program, so these run under a plain `cargo test`.
+
+use anchor_lang::prelude::Pubkey;
+use micro_lending::error::MicroLendingError;
+use micro_lending::{
+    calculate_share_value, calculate_shares_to_mint, pool_share_assets, settle_payment,
+    AutoApprovalRules, BorrowerPolicy, LendingPool, PayoffQuote, RepaymentIncentives,
+    ALL_COLLATERAL_TYPES,
+};
+use proptest::prelude::*;
+
+const SHARE_PRECISION: u128 = 1_000_000_000;
+
+fn pool() -> LendingPool {
+    LendingPool {
+        authority: Pubkey::default(),
+        mint: Pubkey::default(),
+        token_account: Pubkey::default(),
+        name: "accounting".to_string(),
+        base_interest_rate: 1000,
+        max_loan_duration: 365,
+        total_deposited: 0,
+        total_borrowed: 0,
+        total_shares: 0,
+        available_liquidity: 0,
+        active_loans: 0,
+        total_interest_earned: 0,
+        total_interest_distributed: 0,
+        interest_per_share: 0,
+        is_active: true,
+        created_at: 0,
+        borrower_policy: BorrowerPolicy {
+            min_credit_score: 0,
+            required_verifications: 0,
+            min_attestations: 0,
+            allowed_collateral_types: ALL_COLLATERAL_TYPES,
+            max_loan_per_borrower: 0,
+            allowed_purposes: Vec::new(),
+        },
+        total_reserved: 0,
+        auto_approval: AutoApprovalRules {
+            enabled: false,
+            max_utilization_bps: 0,
+            score_bands: Vec::new(),
+        },
+        request_ttl: 0,
+        approval_ttl: 0,
+        committee: None,
+        disaster_declared_at: 0,
+        disaster_forbearance_until: 0,
+        repayment_incentives: RepaymentIncentives {
+            streak_length: 0,
+            rate_step_down_bps: 0,
+            min_interest_rate: 0,
+            early_payoff_rebate_bps: 0,
+        },
+    }
+}
+
+// Pool holding `deposited`, of which `borrowed` is lent out, plus `interest` paid in and
+// credited to the existing shares but not yet claimed
+fn funded_pool(deposited: u64, borrowed: u64, interest: u64) -> LendingPool {
+    let mut pool = pool();
+    pool.total_deposited = deposited;
+    pool.total_shares = deposited;
+    pool.total_borrowed = borrowed;
+    pool.available_liquidity = deposited - borrowed + interest;
+    pool.total_interest_earned = interest;
+    pool.interest_per_share = (interest as u128 * SHARE_PRECISION / deposited as u128) as u64;
+    pool
+}
+
+#[test]
+fn first_deposit_mints_one_share_per_token() {
+    assert_eq!(calculate_shares_to_mint(&pool(), 1_000).unwrap(), 1_000);
+}
+
+#[test]
+fn unclaimed_interest_does_not_count_toward_share_assets() {
+    let pool = funded_pool(1_000_000, 400_000, 50_000);
+    assert_eq!(pool_share_assets(&pool), 1_000_000);
+    // A newcomer pays the same price per share as the original lenders
+    assert_eq!(calculate_shares_to_mint(&pool, 10_000).unwrap(), 10_000);
+    assert_eq!(calculate_share_value(&pool, 10_000), 10_000);
+}
+
+#[test]
+fn reserved_liquidity_counts_toward_share_assets() {
+    let mut pool = funded_pool(1_000_000, 0, 0);
+    pool.available_liquidity -= 300_000;
+    pool.total_reserved = 300_000;
+    assert_eq!(pool_share_assets(&pool), 1_000_000);
+}
+
+#[test]
+fn deposit_into_a_wiped_out_pool_is_rejected() {
+    let mut pool = funded_pool(1_000_000, 1_000_000, 0);
+    // The only loan was liquidated for nothing
+    pool.total_borrowed = 0;
+    assert_eq!(
+        calculate_shares_to_mint(&pool, 1_000).unwrap_err(),
+        MicroLendingError::PoolInsolvent.into()
+    );
+}
+
+// Payoff quote for a loan with `outstanding` principal and `interest` accrued, of which
+// `rebate` is waived on payoff, under a platform fee of `platform_fee` bps
+fn payoff_quote(outstanding: u64, interest: u64, rebate: u64, platform_fee: u16) -> PayoffQuote {
+    let total_due = outstanding + interest - rebate;
+    PayoffQuote {
+        outstanding_principal: outstanding,
+        interest_accrued: interest - rebate,
+        early_payoff_rebate: rebate,
+        late_fee: 0,
+        total_due,
+        platform_fee: (total_due as u128 * platform_fee as u128 / 10000) as u64,
+        in_forbearance: false,
+    }
+}
+
+#[test]
+fn payoff_with_platform_fee_clears_the_principal() {
+    let quote = payoff_quote(1_000_000, 20_000, 0, 100);
+    let settlement = settle_payment(&quote, 100, quote.total_due + quote.platform_fee).unwrap();
+    assert_eq!(settlement.platform_fee, quote.platform_fee);
+    assert_eq!(settlement.interest_paid, 20_000);
+    assert_eq!(settlement.principal_paid, 1_000_000);
+
+    // Paying only the total leaves the fee short of a payoff
+    let settlement = settle_payment(&quote, 100, quote.total_due).unwrap();
+    assert!(settlement.principal_paid < 1_000_000);
+
+    assert_eq!(
+        settle_payment(&quote, 100, quote.total_due + quote.platform_fee + 1).unwrap_err(),
+        MicroLendingError::InvalidPaymentAmount.into()
+    );
+}
+
+#[test]
+fn only_a_payoff_gets_the_early_payoff_rebate() {
+    let quote = payoff_quote(1_000_000, 20_000, 5_000, 0);
+    let settlement = settle_payment(&quote, 0, quote.total_due).unwrap();
+    assert_eq!(settlement.early_payoff_rebate, 5_000);
+    assert_eq!(settlement.interest_paid, 15_000);
+    assert_eq!(settlement.principal_paid, 1_000_000);
+
+    let settlement = settle_payment(&quote, 0, 500_000).unwrap();
+    assert_eq!(settlement.early_payoff_rebate, 0);
+    assert_eq!(settlement.interest_paid, 20_000);
+    assert_eq!(settlement.principal_paid, 480_000);
+
+    // A payment must at least cover the interest
+    assert_eq!(
+        settle_payment(&quote, 0, 19_999).unwrap_err(),
+        MicroLendingError::InvalidPaymentAmount.into()
+    );
+}
+
+proptest! {
+    // Paying a payoff quote's total and platform fee always clears the principal, paying
+    // exactly the quoted fee
+    #[test]
+    fn payoff_quote_always_clears_the_loan(
+        outstanding in 1u64..1_000_000_000_000,
+        interest in 0u64..1_000_000_000,
+        platform_fee in 0u16..=1000,
+    ) {
+        let quote = payoff_quote(outstanding, interest, 0, platform_fee);
+        let payment = quote.total_due + quote.platform_fee;
+        let settlement = settle_payment(&quote, platform_fee, payment).unwrap();
+        prop_assert_eq!(settlement.principal_paid, outstanding);
+        prop_assert_eq!(settlement.platform_fee, quote.platform_fee);
+    }
+
+    // Depositing and immediately redeeming the new shares never returns more than was put in
+    #[test]
+    fn deposit_round_trip_never_gains(
+        deposited in 1u64..1_000_000_000_000,
+        borrowed_bps in 0u64..=10_000,
+        interest in 0u64..1_000_000_000,
+        amount in 1u64..1_000_000_000_000,
+    ) {
+        let borrowed = (deposited as u128 * borrowed_bps as u128 / 10_000) as u64;
+        let mut pool = funded_pool(deposited, borrowed, interest);
+        let shares = calculate_shares_to_mint(&pool, amount).unwrap();
+        pool.total_shares += shares;
+        pool.total_deposited += amount;
+        pool.available_liquidity += amount;
+        prop_assert!(calculate_share_value(&pool, shares) <= amount);
+    }
+}
@@ -0,0 +1,429 @@
+//! In-process test harness: runs the built program in LiteSVM with a platform, a mint and
+//! a lending pool already set up, and lets tests move the clock.
+//!
+//! The program is loaded from `target/deploy/micro_lending.so` (`anchor build`), or from
+//! the path in `MICRO_LENDING_SO`. Tests that need it are `#[ignore]`d, so run them with
+//! `cargo test -p micro_lending -- --ignored` after building it.
+
+#![allow(dead_code)]
+// Helpers hand back LiteSVM's TransactionResult, whose error carries the failed transaction's
+// metadata by value
+#![allow(clippy::result_large_err)]
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{AccountDeserialize, AccountSerialize, AnchorDeserialize};
+use anchor_spl::token::TokenAccount;
+use litesvm::types::TransactionResult;
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo};
+use micro_lending::{
+    calculate_payoff_quote, BorrowerPolicy, LenderDeposit, LendingPool, Loan, PayoffQuote,
+    Platform, UserProfile, ALL_COLLATERAL_TYPES,
+};
+use micro_lending_client::{instructions, pda};
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use std::path::PathBuf;
+
+pub const DAY: i64 = 86_400;
+// Mid-2025; loans disbursed at timestamp 0 would look undisbursed to the program
+pub const START_TIME: i64 = 1_750_000_000;
+
+pub const DECIMALS: u8 = 6;
+pub const MAX_LOAN: u64 = 1_000_000_000;
+pub const MIN_LOAN: u64 = 1_000_000;
+pub const BASE_INTEREST_RATE: u16 = 1000;
+pub const MAX_LOAN_DURATION: i64 = 365;
+pub const USER_TOKENS: u64 = 1_000_000_000;
+pub const POOL_DEPOSIT: u64 = 500_000_000;
+// 5% of MAX_LOAN, within reach of a new user's credit score
+pub const LOAN_AMOUNT: u64 = 50_000_000;
+pub const LOAN_DAYS: u32 = 30;
+
+pub fn program_path() -> PathBuf {
+    std::env::var_os("MICRO_LENDING_SO")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy/micro_lending.so")
+        })
+}
+
+pub fn token_program() -> Pubkey {
+    anchor_spl::token::ID
+}
+
+// Policy a new pool starts with: no requirements beyond the platform's
+pub fn open_policy() -> BorrowerPolicy {
+    BorrowerPolicy {
+        min_credit_score: 0,
+        required_verifications: 0,
+        min_attestations: 0,
+        allowed_collateral_types: ALL_COLLATERAL_TYPES,
+        max_loan_per_borrower: 0,
+        allowed_purposes: Vec::new(),
+    }
+}
+
+pub struct TestEnv {
+    pub svm: LiteSVM,
+    // Platform authority, mint authority and authority of `pool`
+    pub authority: Keypair,
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+}
+
+impl TestEnv {
+    // Platform without a fee, so a payment of the quoted total clears a loan
+    pub fn new() -> Self {
+        Self::with_platform_fee(0)
+    }
+
+    pub fn with_platform_fee(platform_fee: u16) -> Self {
+        let path = program_path();
+        assert!(
+            path.exists(),
+            "{} not found; run `anchor build` or set MICRO_LENDING_SO",
+            path.display()
+        );
+
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(micro_lending::ID, &path)
+            .expect("loading micro_lending program");
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = START_TIME;
+        svm.set_sysvar(&clock);
+
+        let authority = Keypair::new();
+        svm.airdrop(&authority.pubkey(), 100 * LAMPORTS_PER_SOL)
+            .expect("airdrop");
+        let mint = CreateMint::new(&mut svm, &authority)
+            .decimals(DECIMALS)
+            .send()
+            .expect("creating mint");
+
+        let mut env = TestEnv {
+            svm,
+            authority,
+            mint,
+            pool: Pubkey::default(),
+        };
+        let authority_key = env.authority.pubkey();
+        env.send_as_authority(&[instructions::initialize_platform(
+            &authority_key,
+            authority_key,
+            platform_fee,
+            MAX_LOAN,
+            MIN_LOAN,
+            365,
+            180,
+        )])
+        .expect("initializing platform");
+        let authority = env.authority();
+        env.pool = env.create_pool(&authority);
+        env
+    }
+
+    pub fn authority(&self) -> Keypair {
+        self.authority.insecure_clone()
+    }
+
+    // The first signer pays the fee
+    pub fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> TransactionResult {
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(transaction);
+        // Repeating an instruction later must not be mistaken for a duplicate transaction
+        self.svm.expire_blockhash();
+        result
+    }
+
+    pub fn send_as_authority(&mut self, instructions: &[Instruction]) -> TransactionResult {
+        let authority = self.authority();
+        self.send(instructions, &[&authority])
+    }
+
+    // Runs a quote instruction and decodes its return data
+    pub fn simulate_quote<T: AnchorDeserialize>(
+        &mut self,
+        instruction: Instruction,
+        payer: &Keypair,
+    ) -> T {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer],
+            self.svm.latest_blockhash(),
+        );
+        let simulated = self
+            .svm
+            .simulate_transaction(transaction)
+            .unwrap_or_else(|failed| panic!("quote failed: {:#?}", failed.meta.logs));
+        T::try_from_slice(&simulated.meta.return_data.data).expect("decoding quote")
+    }
+
+    // -----------------------------------------------------------------------
+    // Clock
+    // -----------------------------------------------------------------------
+
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    pub fn warp(&mut self, seconds: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp += seconds;
+        self.svm.set_sysvar(&clock);
+        self.svm.expire_blockhash();
+    }
+
+    pub fn warp_days(&mut self, days: i64) {
+        self.warp(days * DAY);
+    }
+
+    // -----------------------------------------------------------------------
+    // Participants
+    // -----------------------------------------------------------------------
+
+    pub fn funded_keypair(&mut self) -> Keypair {
+        let keypair = Keypair::new();
+        self.svm
+            .airdrop(&keypair.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .expect("airdrop");
+        keypair
+    }
+
+    // A keypair with SOL, a token account holding USER_TOKENS and a user profile
+    pub fn new_user(&mut self) -> Keypair {
+        let user = self.funded_keypair();
+        let authority = self.authority();
+        let token_account =
+            CreateAssociatedTokenAccount::new(&mut self.svm, &authority, &self.mint)
+                .owner(&user.pubkey())
+                .send()
+                .expect("creating token account");
+        MintTo::new(
+            &mut self.svm,
+            &authority,
+            &self.mint,
+            &token_account,
+            USER_TOKENS,
+        )
+        .send()
+        .expect("minting");
+        self.send(&[instructions::initialize_user(&user.pubkey())], &[&user])
+            .expect("initializing user");
+        user
+    }
+
+    pub fn new_lender(&mut self, deposit: u64) -> Keypair {
+        let lender = self.new_user();
+        self.deposit(&lender, deposit).expect("depositing");
+        lender
+    }
+
+    // Creates another pool over the same mint, owned by `authority`
+    pub fn create_pool(&mut self, authority: &Keypair) -> Pubkey {
+        self.send(
+            &[instructions::create_lending_pool(
+                &authority.pubkey(),
+                &self.mint,
+                &token_program(),
+                "Market traders".to_string(),
+                BASE_INTEREST_RATE,
+                MAX_LOAN_DURATION,
+            )],
+            &[authority],
+        )
+        .expect("creating lending pool");
+        pda::lending_pool(&authority.pubkey(), &self.mint).0
+    }
+
+    // -----------------------------------------------------------------------
+    // Lenders and loans on `pool`
+    // -----------------------------------------------------------------------
+
+    pub fn deposit(&mut self, lender: &Keypair, amount: u64) -> TransactionResult {
+        let instruction = instructions::deposit_to_pool(
+            &lender.pubkey(),
+            &self.pool,
+            &self.mint,
+            &token_program(),
+            amount,
+        );
+        self.send(&[instruction], &[lender])
+    }
+
+    pub fn request_loan(
+        &mut self,
+        borrower: &Keypair,
+        amount: u64,
+        duration_days: u32,
+    ) -> TransactionResult {
+        let instruction = instructions::request_loan(
+            &borrower.pubkey(),
+            &self.pool,
+            None,
+            &[],
+            amount,
+            duration_days,
+            "inventory".to_string(),
+            0,
+        );
+        self.send(&[instruction], &[borrower])
+    }
+
+    pub fn approve_loan(&mut self, borrower: &Keypair) -> TransactionResult {
+        let instruction = instructions::approve_loan(
+            &self.authority.pubkey(),
+            &self.pool,
+            &borrower.pubkey(),
+            false,
+        );
+        self.send_as_authority(&[instruction])
+    }
+
+    pub fn disburse_loan(&mut self, borrower: &Keypair) -> TransactionResult {
+        let instruction = instructions::disburse_loan(
+            &self.authority.pubkey(),
+            &self.mint,
+            &token_program(),
+            &borrower.pubkey(),
+            None,
+        );
+        self.send_as_authority(&[instruction])
+    }
+
+    // Requests, approves and disburses a loan without an officer or attestations
+    pub fn open_loan(&mut self, borrower: &Keypair, amount: u64, duration_days: u32) {
+        self.request_loan(borrower, amount, duration_days)
+            .expect("requesting loan");
+        self.approve_loan(borrower).expect("approving loan");
+        self.disburse_loan(borrower).expect("disbursing loan");
+    }
+
+    pub fn repay(&mut self, borrower: &Keypair, amount: u64) -> TransactionResult {
+        let instruction = instructions::repay_loan(
+            &borrower.pubkey(),
+            &self.pool,
+            &self.mint,
+            &token_program(),
+            None,
+            &[],
+            amount,
+        );
+        self.send(&[instruction], &[borrower])
+    }
+
+    pub fn liquidate(&mut self, borrower: &Keypair) -> TransactionResult {
+        let keeper = self.funded_keypair();
+        let instruction = instructions::liquidate_loan(
+            &keeper.pubkey(),
+            &self.pool,
+            &borrower.pubkey(),
+            None,
+            &[],
+        );
+        self.send(&[instruction], &[&keeper])
+    }
+
+    // -----------------------------------------------------------------------
+    // State
+    // -----------------------------------------------------------------------
+
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self
+            .svm
+            .get_account(address)
+            .unwrap_or_else(|| panic!("account {address} not found"));
+        T::try_deserialize(&mut account.data.as_slice()).expect("decoding account")
+    }
+
+    // Rewrites a program account in place, for states no instruction can reach
+    pub fn update_account<T: AccountDeserialize + AccountSerialize>(
+        &mut self,
+        address: &Pubkey,
+        update: impl FnOnce(&mut T),
+    ) {
+        let mut account = self.svm.get_account(address).expect("account not found");
+        let mut state = T::try_deserialize(&mut account.data.as_slice()).expect("decoding account");
+        update(&mut state);
+        let mut writer: &mut [u8] = &mut account.data;
+        state.try_serialize(&mut writer).expect("encoding account");
+        self.svm
+            .set_account(*address, account)
+            .expect("writing account");
+    }
+
+    pub fn platform(&self) -> Platform {
+        self.account(&pda::platform().0)
+    }
+
+    pub fn lending_pool(&self) -> LendingPool {
+        self.account(&self.pool)
+    }
+
+    pub fn loan(&self, borrower: &Keypair) -> Loan {
+        self.account(&pda::loan(&borrower.pubkey(), &self.pool).0)
+    }
+
+    pub fn user_profile(&self, user: &Keypair) -> UserProfile {
+        self.account(&pda::user_profile(&user.pubkey()).0)
+    }
+
+    pub fn lender_deposit(&self, lender: &Keypair) -> LenderDeposit {
+        self.account(&pda::lender_deposit(&lender.pubkey(), &self.pool).0)
+    }
+
+    pub fn token_balance(&self, owner: &Pubkey) -> u64 {
+        let address = pda::token_account(owner, &self.mint, &token_program());
+        self.account::<TokenAccount>(&address).amount
+    }
+
+    pub fn pool_token_balance(&self) -> u64 {
+        let address = pda::pool_token_account(&self.pool).0;
+        self.account::<TokenAccount>(&address).amount
+    }
+
+    // What a single repay_loan call would need to clear the borrower's loan right now
+    pub fn payoff_quote(&self, borrower: &Keypair) -> PayoffQuote {
+        calculate_payoff_quote(
+            &self.platform(),
+            &self.lending_pool(),
+            &self.loan(borrower),
+            self.now(),
+        )
+        .expect("quoting payoff")
+    }
+}
+
+// Asserts the transaction failed with this custom error code: a MicroLendingError, or an
+// Anchor ErrorCode cast to u32
+pub fn assert_error(result: TransactionResult, expected: impl Into<u32>) {
+    let expected = expected.into();
+    match result {
+        Ok(_) => panic!("expected error {expected}, transaction succeeded"),
+        Err(failed) => match failed.err {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => assert_eq!(
+                code, expected,
+                "expected error {expected}, got {code}; logs: {:#?}",
+                failed.meta.logs
+            ),
+            other => panic!(
+                "expected error {expected}, got {other:?}; logs: {:#?}",
+                failed.meta.logs
+            ),
+        },
+    }
+}
@@ -0,0 +1,166 @@
+// Helpers return LiteSVM's TransactionResult, whose error carries transaction metadata by value
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::*;
+use micro_lending::error::MicroLendingError;
+use micro_lending::{get_unclaimed_interest, LendingPool};
+use micro_lending_client::instructions;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn claim(env: &mut TestEnv, lender: &Keypair) -> litesvm::types::TransactionResult {
+    let instruction =
+        instructions::claim_interest(&lender.pubkey(), &env.pool, &env.mint, &token_program());
+    env.send(&[instruction], &[lender])
+}
+
+fn withdraw(env: &mut TestEnv, lender: &Keypair, shares: u64) -> litesvm::types::TransactionResult {
+    let instruction = instructions::withdraw_from_pool(
+        &lender.pubkey(),
+        &env.pool,
+        &env.mint,
+        &token_program(),
+        shares,
+    );
+    env.send(&[instruction], &[lender])
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn lender_claims_interest_and_withdraws_deposit() {
+    let mut env = TestEnv::new();
+    let lender = env.new_lender(POOL_DEPOSIT);
+    let deposit = env.lender_deposit(&lender);
+    assert_eq!(deposit.shares, POOL_DEPOSIT);
+    assert_eq!(env.lending_pool().available_liquidity, POOL_DEPOSIT);
+
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    env.warp_days(15);
+    let quote = env.payoff_quote(&borrower);
+    assert!(quote.interest_accrued > 0);
+    env.repay(&borrower, quote.total_due).unwrap();
+
+    let pending = get_unclaimed_interest(&env.lender_deposit(&lender), &env.lending_pool());
+    assert_eq!(pending, quote.interest_accrued);
+    let balance = env.token_balance(&lender.pubkey());
+    claim(&mut env, &lender).unwrap();
+    assert_eq!(env.token_balance(&lender.pubkey()), balance + pending);
+    assert_eq!(env.lender_deposit(&lender).interest_claimed, pending);
+
+    withdraw(&mut env, &lender, deposit.shares).unwrap();
+    assert_eq!(
+        env.token_balance(&lender.pubkey()),
+        balance + pending + POOL_DEPOSIT
+    );
+    let pool = env.lending_pool();
+    assert_eq!(pool.total_shares, 0);
+    assert_eq!(pool.available_liquidity, 0);
+    assert_eq!(env.pool_token_balance(), 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn withdrawal_is_limited_to_available_liquidity() {
+    let mut env = TestEnv::new();
+    let lender = env.new_lender(LOAN_AMOUNT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    let result = withdraw(&mut env, &lender, LOAN_AMOUNT);
+    assert_error(result, MicroLendingError::InsufficientLiquidity);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn deposit_of_zero_is_rejected() {
+    let mut env = TestEnv::new();
+    let lender = env.new_user();
+    let result = env.deposit(&lender, 0);
+    assert_error(result, MicroLendingError::InvalidPaymentAmount);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn claim_without_interest_is_rejected() {
+    let mut env = TestEnv::new();
+    let lender = env.new_lender(POOL_DEPOSIT);
+    let result = claim(&mut env, &lender);
+    assert_error(result, MicroLendingError::NoInterestToClaim);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn withdrawal_of_zero_shares_is_rejected() {
+    let mut env = TestEnv::new();
+    let lender = env.new_lender(POOL_DEPOSIT);
+    let result = withdraw(&mut env, &lender, 0);
+    assert_error(result, MicroLendingError::InvalidAmount);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn withdrawal_beyond_shares_is_rejected() {
+    let mut env = TestEnv::new();
+    let lender = env.new_lender(POOL_DEPOSIT);
+    let result = withdraw(&mut env, &lender, POOL_DEPOSIT + 1);
+    assert_error(result, MicroLendingError::InsufficientShares);
+}
+
+// No instruction deactivates a pool, so the flag is flipped directly
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn inactive_pool_rejects_deposits() {
+    let mut env = TestEnv::new();
+    let pool = env.pool;
+    env.update_account::<LendingPool>(&pool, |pool| pool.is_active = false);
+
+    let lender = env.new_user();
+    let result = env.deposit(&lender, POOL_DEPOSIT);
+    assert_error(result, MicroLendingError::PoolNotActive);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn later_depositors_get_shares_at_the_current_price() {
+    let mut env = TestEnv::new();
+    let first = env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    env.warp_days(15);
+    let quote = env.payoff_quote(&borrower);
+    env.repay(&borrower, quote.total_due).unwrap();
+
+    // Interest already credited to the first lender does not raise the share price
+    let second = env.new_lender(POOL_DEPOSIT);
+    assert_eq!(env.lender_deposit(&second).shares, POOL_DEPOSIT);
+
+    // Withdrawing everything pays the unclaimed interest once, on top of the deposit
+    let balance = env.token_balance(&first.pubkey());
+    withdraw(&mut env, &first, POOL_DEPOSIT).unwrap();
+    assert_eq!(
+        env.token_balance(&first.pubkey()),
+        balance + POOL_DEPOSIT + quote.interest_accrued
+    );
+    let balance = env.token_balance(&second.pubkey());
+    withdraw(&mut env, &second, POOL_DEPOSIT).unwrap();
+    assert_eq!(env.token_balance(&second.pubkey()), balance + POOL_DEPOSIT);
+    assert_eq!(env.pool_token_balance(), 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn deposit_into_a_wiped_out_pool_is_rejected() {
+    let mut env = TestEnv::new();
+    env.new_lender(LOAN_AMOUNT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    env.warp_days(LOAN_DAYS as i64 + 8);
+    env.liquidate(&borrower).unwrap();
+
+    // Every share is backed by nothing, so new shares cannot be priced
+    let lender = env.new_user();
+    let result = env.deposit(&lender, POOL_DEPOSIT);
+    assert_error(result, MicroLendingError::PoolInsolvent);
+}
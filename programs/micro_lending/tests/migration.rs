@@ -0,0 +1,364 @@
+// Helpers return LiteSVM's TransactionResult, whose error carries transaction metadata by value
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use anchor_lang::prelude::AccountInfo;
+use anchor_lang::{
+    AccountDeserialize, AccountSerialize, AnchorDeserialize, AnchorSerialize, Discriminator, Space,
+};
+use common::*;
+use micro_lending::error::MicroLendingError;
+use micro_lending::{
+    calculate_loan_payment, migrate_lending_pool, migrate_loan, migrate_user_profile,
+    pool_share_assets, reserved_for_approvals, CollateralType, LendingPool, LendingPoolV1, Loan,
+    LoanStatus, LoanV1, UserProfile, UserProfileV1,
+};
+use micro_lending_client::{instructions, pda};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn legacy_user_profile(owner: Pubkey) -> UserProfileV1 {
+    UserProfileV1 {
+        owner,
+        credit_score: 640,
+        total_borrowed: 3 * LOAN_AMOUNT,
+        total_repaid: 2 * LOAN_AMOUNT,
+        active_loans: 1,
+        successful_loans: 2,
+        defaulted_loans: 0,
+        reputation_score: 500,
+        created_at: START_TIME - 90 * DAY,
+        last_updated: START_TIME - 10 * DAY,
+        kyc_verified: false,
+        phone_verified: false,
+        email_verified: false,
+        transaction_history_count: 7,
+        social_attestations_count: 3,
+    }
+}
+
+// An Active loan with one payment made, disbursed 20 days before START_TIME
+fn legacy_loan(status: LoanStatus) -> LoanV1 {
+    LoanV1 {
+        borrower: Pubkey::new_unique(),
+        lender_pool: Pubkey::new_unique(),
+        amount: LOAN_AMOUNT,
+        interest_rate: 1200,
+        duration_days: LOAN_DAYS,
+        disbursed_at: START_TIME - 20 * DAY,
+        due_date: START_TIME + 10 * DAY,
+        amount_repaid: LOAN_AMOUNT / 4,
+        interest_accrued: 164_383,
+        status,
+        purpose: "stock".to_string(),
+        collateral_type: CollateralType::Social,
+        collateral_value: 0,
+        payment_count: 1,
+        last_payment_date: START_TIME - 5 * DAY,
+        grace_period_days: 7,
+        late_fee_rate: 500,
+        created_at: START_TIME - 21 * DAY,
+        liquidated_at: None,
+    }
+}
+
+// A pool holding POOL_DEPOSIT, of which LOAN_AMOUNT was taken out of available liquidity
+// by approving `legacy_loan(LoanStatus::Approved)`
+fn legacy_lending_pool(authority: Pubkey, mint: Pubkey) -> LendingPoolV1 {
+    LendingPoolV1 {
+        authority,
+        mint,
+        token_account: Pubkey::new_unique(),
+        name: "Market traders".to_string(),
+        base_interest_rate: BASE_INTEREST_RATE,
+        max_loan_duration: MAX_LOAN_DURATION,
+        total_deposited: POOL_DEPOSIT,
+        total_borrowed: 0,
+        total_shares: POOL_DEPOSIT,
+        available_liquidity: POOL_DEPOSIT - LOAN_AMOUNT,
+        active_loans: 1,
+        total_interest_earned: 0,
+        total_interest_distributed: 0,
+        interest_per_share: 0,
+        is_active: true,
+        created_at: START_TIME - 60 * DAY,
+    }
+}
+
+fn approved_loan_for(pool: Pubkey) -> LoanV1 {
+    LoanV1 {
+        lender_pool: pool,
+        disbursed_at: 0,
+        due_date: 0,
+        amount_repaid: 0,
+        interest_accrued: 0,
+        payment_count: 0,
+        last_payment_date: 0,
+        ..legacy_loan(LoanStatus::Approved)
+    }
+}
+
+// Account data as the first layout stored it: discriminator, fields, zero padding
+fn legacy_data(discriminator: &[u8], fields: &impl AnchorSerialize, space: usize) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    fields.serialize(&mut data).unwrap();
+    data.resize(8 + space, 0);
+    data
+}
+
+// Plant `fields` at `address` as a program account in the first layout
+fn plant_legacy(
+    env: &mut TestEnv,
+    address: Pubkey,
+    discriminator: &[u8],
+    fields: &impl AnchorSerialize,
+    space: usize,
+) {
+    let data = legacy_data(discriminator, fields, space);
+    let lamports = env.svm.minimum_balance_for_rent_exemption(data.len());
+    env.svm
+        .set_account(
+            address,
+            Account {
+                lamports,
+                data,
+                owner: micro_lending::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn user_profile_fields_after_the_inserted_ones_carry_over() {
+    let owner = Pubkey::new_unique();
+    let data = legacy_data(
+        UserProfile::DISCRIMINATOR,
+        &legacy_user_profile(owner),
+        UserProfileV1::INIT_SPACE,
+    );
+    let legacy = UserProfileV1::deserialize(&mut &data[8..]).unwrap();
+    let mut migrated = Vec::new();
+    migrate_user_profile(legacy)
+        .try_serialize(&mut migrated)
+        .unwrap();
+    assert!(migrated.len() <= 8 + UserProfile::INIT_SPACE);
+    let profile = UserProfile::try_deserialize(&mut migrated.as_slice()).unwrap();
+
+    assert_eq!(profile.owner, owner);
+    assert_eq!(profile.credit_score, 640);
+    assert_eq!(profile.transaction_history_count, 7);
+    assert_eq!(profile.social_attestations_count, 3);
+    assert_eq!(profile.last_activity_at, START_TIME - 10 * DAY);
+    assert_eq!(profile.kyc_expires_at, None);
+    assert!(profile.recent_outcomes.is_empty());
+    assert_eq!(profile.borrowing_limit, 0);
+}
+
+#[test]
+fn migrated_loan_accrues_from_its_last_payment_on_its_original_schedule() {
+    let loan = migrate_loan(legacy_loan(LoanStatus::Active), START_TIME).unwrap();
+    assert!(loan.status == LoanStatus::Active);
+    assert_eq!(loan.amount_repaid, LOAN_AMOUNT / 4);
+    assert_eq!(loan.accrual_start, START_TIME - 5 * DAY);
+    assert_eq!(
+        loan.scheduled_repayment,
+        calculate_loan_payment(LOAN_AMOUNT, 1200, LOAN_DAYS).unwrap()
+    );
+    assert_eq!(loan.approved_at, 0);
+    assert_eq!(loan.purpose, "stock");
+    assert!(loan.collateral_type == CollateralType::Social);
+
+    let unpaid = migrate_loan(
+        LoanV1 {
+            payment_count: 0,
+            last_payment_date: 0,
+            ..legacy_loan(LoanStatus::Disbursed)
+        },
+        START_TIME,
+    )
+    .unwrap();
+    assert_eq!(unpaid.accrual_start, START_TIME - 20 * DAY);
+}
+
+#[test]
+fn pending_approval_starts_its_ttl_at_migration() {
+    let loan = migrate_loan(
+        LoanV1 {
+            disbursed_at: 0,
+            payment_count: 0,
+            ..legacy_loan(LoanStatus::Approved)
+        },
+        START_TIME,
+    )
+    .unwrap();
+    assert_eq!(loan.approved_at, START_TIME);
+    assert_eq!(loan.scheduled_repayment, 0);
+}
+
+#[test]
+fn approved_loans_stay_reserved_in_a_migrated_pool() {
+    let pool = Pubkey::new_unique();
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = legacy_data(
+        Loan::DISCRIMINATOR,
+        &approved_loan_for(pool),
+        LoanV1::INIT_SPACE,
+    );
+    let loan = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &micro_lending::ID,
+        false,
+        0,
+    );
+    // A loan listed twice is reserved once
+    let total_reserved = reserved_for_approvals(&pool, &[loan.clone(), loan]).unwrap();
+    assert_eq!(total_reserved, LOAN_AMOUNT);
+
+    let migrated = migrate_lending_pool(
+        legacy_lending_pool(Pubkey::new_unique(), Pubkey::new_unique()),
+        total_reserved,
+    );
+    assert_eq!(migrated.total_reserved, LOAN_AMOUNT);
+    assert_eq!(migrated.available_liquidity, POOL_DEPOSIT - LOAN_AMOUNT);
+    assert_eq!(pool_share_assets(&migrated), POOL_DEPOSIT);
+}
+
+#[test]
+fn only_the_pools_approved_loans_are_reserved() {
+    let pool = Pubkey::new_unique();
+    let refused = [
+        (approved_loan_for(Pubkey::new_unique()), micro_lending::ID),
+        (
+            LoanV1 {
+                lender_pool: pool,
+                ..legacy_loan(LoanStatus::Active)
+            },
+            micro_lending::ID,
+        ),
+        (approved_loan_for(pool), Pubkey::new_unique()),
+    ];
+    for (legacy, owner) in refused {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = legacy_data(Loan::DISCRIMINATOR, &legacy, LoanV1::INIT_SPACE);
+        let loan = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(
+            reserved_for_approvals(&pool, &[loan]).unwrap_err(),
+            MicroLendingError::InvalidLoanState.into()
+        );
+    }
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn legacy_user_profile_is_grown_and_rewritten_once() {
+    let mut env = TestEnv::new();
+    let user = Keypair::new();
+    let address = pda::user_profile(&user.pubkey()).0;
+    plant_legacy(
+        &mut env,
+        address,
+        UserProfile::DISCRIMINATOR,
+        &legacy_user_profile(user.pubkey()),
+        UserProfileV1::INIT_SPACE,
+    );
+
+    let payer = env.funded_keypair();
+    let instruction = instructions::migrate_account(&payer.pubkey(), &address, &[]);
+    env.send(std::slice::from_ref(&instruction), &[&payer])
+        .unwrap();
+
+    let account = env.svm.get_account(&address).unwrap();
+    let new_space = 8 + UserProfile::INIT_SPACE;
+    assert_eq!(account.data.len(), new_space);
+    assert_eq!(
+        account.lamports,
+        env.svm.minimum_balance_for_rent_exemption(new_space)
+    );
+    let profile = env.user_profile(&user);
+    assert_eq!(profile.transaction_history_count, 7);
+    assert_eq!(profile.social_attestations_count, 3);
+
+    let result = env.send(&[instruction], &[&payer]);
+    assert_error(result, MicroLendingError::AccountNotMigratable);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn accounts_in_the_current_layout_are_refused() {
+    let mut env = TestEnv::new();
+    let payer = env.funded_keypair();
+    let instruction = instructions::migrate_account(&payer.pubkey(), &env.pool, &[]);
+    let result = env.send(&[instruction], &[&payer]);
+    assert_error(result, MicroLendingError::AccountNotMigratable);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn legacy_pool_keeps_its_pending_approval_reserved() {
+    let mut env = TestEnv::new();
+    let pool = env.pool;
+    let loan = Pubkey::new_unique();
+    let legacy_pool = legacy_lending_pool(env.authority.pubkey(), env.mint);
+    plant_legacy(
+        &mut env,
+        pool,
+        LendingPool::DISCRIMINATOR,
+        &legacy_pool,
+        LendingPoolV1::INIT_SPACE,
+    );
+    plant_legacy(
+        &mut env,
+        loan,
+        Loan::DISCRIMINATOR,
+        &approved_loan_for(pool),
+        LoanV1::INIT_SPACE,
+    );
+
+    let authority = env.authority.pubkey();
+    env.send_as_authority(&[instructions::migrate_account(&authority, &pool, &[loan])])
+        .unwrap();
+
+    let migrated = env.lending_pool();
+    assert_eq!(migrated.total_reserved, LOAN_AMOUNT);
+    assert_eq!(migrated.available_liquidity, POOL_DEPOSIT - LOAN_AMOUNT);
+    assert_eq!(pool_share_assets(&migrated), POOL_DEPOSIT);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn only_the_pool_authority_migrates_a_pool() {
+    let mut env = TestEnv::new();
+    let pool = env.pool;
+    let legacy_pool = legacy_lending_pool(env.authority.pubkey(), env.mint);
+    plant_legacy(
+        &mut env,
+        pool,
+        LendingPool::DISCRIMINATOR,
+        &legacy_pool,
+        LendingPoolV1::INIT_SPACE,
+    );
+
+    let payer = env.funded_keypair();
+    let instruction = instructions::migrate_account(&payer.pubkey(), &pool, &[]);
+    let result = env.send(&[instruction], &[&payer]);
+    assert_error(result, MicroLendingError::Unauthorized);
+}
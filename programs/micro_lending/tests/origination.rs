@@ -0,0 +1,908 @@
+// Helpers return LiteSVM's TransactionResult, whose error carries transaction metadata by value
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::*;
+use litesvm::types::TransactionResult;
+use micro_lending::error::MicroLendingError;
+use micro_lending::{
+    loan_consent_message, AutoApprovalRules, BorrowerPolicy, LoanConsent, LoanOfficer, LoanQuote,
+    LoanStatus, ScoreBand, VerificationType,
+};
+use micro_lending_client::{instructions, pda};
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn request(
+    env: &mut TestEnv,
+    borrower: &Keypair,
+    amount: u64,
+    purpose: &str,
+    collateral_type: u8,
+) -> TransactionResult {
+    let instruction = instructions::request_loan(
+        &borrower.pubkey(),
+        &env.pool,
+        None,
+        &[],
+        amount,
+        LOAN_DAYS,
+        purpose.to_string(),
+        collateral_type,
+    );
+    env.send(&[instruction], &[borrower])
+}
+
+fn set_policy(env: &mut TestEnv, policy: BorrowerPolicy) {
+    let instruction = instructions::set_borrower_policy(&env.authority.pubkey(), &env.pool, policy);
+    env.send_as_authority(&[instruction]).unwrap();
+}
+
+fn create_committee(env: &mut TestEnv, members: Vec<Pubkey>, quorum: u8) -> TransactionResult {
+    let instruction = instructions::create_loan_committee(
+        &env.authority.pubkey(),
+        &env.pool,
+        members,
+        quorum,
+        3600,
+    );
+    env.send_as_authority(&[instruction])
+}
+
+fn vote(
+    env: &mut TestEnv,
+    member: &Keypair,
+    borrower: &Keypair,
+    approve: bool,
+) -> TransactionResult {
+    let created_at = env.loan(borrower).created_at;
+    let instruction = instructions::vote_on_loan(
+        &member.pubkey(),
+        &env.pool,
+        &borrower.pubkey(),
+        created_at,
+        approve,
+    );
+    env.send(&[instruction], &[member])
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn loan_is_requested_approved_and_disbursed() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+    let loan = env.loan(&borrower);
+    assert!(loan.status == LoanStatus::Requested);
+    // New users score 300, which adds 5% to the pool's base rate
+    assert_eq!(loan.interest_rate, BASE_INTEREST_RATE + 500);
+
+    env.approve_loan(&borrower).unwrap();
+    assert!(env.loan(&borrower).status == LoanStatus::Approved);
+    let pool = env.lending_pool();
+    assert_eq!(pool.available_liquidity, POOL_DEPOSIT - LOAN_AMOUNT);
+    assert_eq!(pool.total_reserved, LOAN_AMOUNT);
+
+    env.disburse_loan(&borrower).unwrap();
+    let loan = env.loan(&borrower);
+    assert!(loan.status == LoanStatus::Disbursed);
+    assert_eq!(loan.due_date, env.now() + LOAN_DAYS as i64 * DAY);
+    assert_eq!(
+        env.token_balance(&borrower.pubkey()),
+        USER_TOKENS + LOAN_AMOUNT
+    );
+    let pool = env.lending_pool();
+    assert_eq!(pool.total_borrowed, LOAN_AMOUNT);
+    assert_eq!(pool.total_reserved, 0);
+    assert_eq!(env.user_profile(&borrower).active_loans, 1);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn request_inside_auto_approval_rules_skips_manual_approval() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let rules = AutoApprovalRules {
+        enabled: true,
+        max_utilization_bps: 5000,
+        score_bands: vec![ScoreBand {
+            min_credit_score: 300,
+            max_amount: LOAN_AMOUNT,
+        }],
+    };
+    let instruction =
+        instructions::set_auto_approval_rules(&env.authority.pubkey(), &env.pool, rules);
+    env.send_as_authority(&[instruction]).unwrap();
+
+    let borrower = env.new_user();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+    assert!(env.loan(&borrower).status == LoanStatus::Approved);
+
+    let larger = env.new_user();
+    env.request_loan(&larger, LOAN_AMOUNT + 1, LOAN_DAYS)
+        .unwrap();
+    assert!(env.loan(&larger).status == LoanStatus::Requested);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn invalid_pool_configuration_is_rejected() {
+    let mut env = TestEnv::new();
+    let authority = env.funded_keypair();
+    let instruction = instructions::create_lending_pool(
+        &authority.pubkey(),
+        &env.mint,
+        &token_program(),
+        "Too expensive".to_string(),
+        5001,
+        MAX_LOAN_DURATION,
+    );
+    let result = env.send(&[instruction], &[&authority]);
+    assert_error(result, MicroLendingError::InvalidPoolConfiguration);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn request_below_platform_minimum_is_rejected() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    let result = env.request_loan(&borrower, MIN_LOAN - 1, LOAN_DAYS);
+    assert_error(result, MicroLendingError::LoanAmountTooLow);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn request_above_platform_maximum_is_rejected() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    let result = env.request_loan(&borrower, MAX_LOAN + 1, LOAN_DAYS);
+    assert_error(result, MicroLendingError::LoanAmountTooHigh);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn request_beyond_pool_duration_is_rejected() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    let result = env.request_loan(&borrower, LOAN_AMOUNT, MAX_LOAN_DURATION as u32 + 1);
+    assert_error(result, MicroLendingError::LoanDurationTooLong);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn borrower_with_an_active_loan_cannot_borrow_elsewhere() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    let other_authority = env.funded_keypair();
+    env.pool = env.create_pool(&other_authority);
+    let result = env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    assert_error(result, MicroLendingError::BorrowerHasActiveLoan);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn request_beyond_pool_liquidity_is_rejected() {
+    let mut env = TestEnv::new();
+    env.new_lender(LOAN_AMOUNT - 1);
+    let borrower = env.new_user();
+    let result = env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    assert_error(result, MicroLendingError::InsufficientLiquidity);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn request_beyond_credit_score_is_rejected() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    // 20% of the platform maximum needs a score of 450
+    let result = env.request_loan(&borrower, MAX_LOAN / 5, LOAN_DAYS);
+    assert_error(result, MicroLendingError::InsufficientCreditScore);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn request_beyond_lending_ladder_limit_is_rejected() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let instruction =
+        instructions::set_lending_ladder(&env.authority.pubkey(), LOAN_AMOUNT / 2, 15000, 5000);
+    env.send_as_authority(&[instruction]).unwrap();
+
+    let borrower = env.new_user();
+    let result = env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    assert_error(result, MicroLendingError::LoanExceedsBorrowerLimit);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn unknown_collateral_type_is_rejected() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    let result = request(&mut env, &borrower, LOAN_AMOUNT, "inventory", 5);
+    assert_error(result, MicroLendingError::InvalidCollateralType);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn social_collateral_needs_a_verified_attestation() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    let result = request(&mut env, &borrower, LOAN_AMOUNT, "inventory", 1);
+    assert_error(result, MicroLendingError::InsufficientAttestations);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn overlong_purpose_is_rejected() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    let result = request(&mut env, &borrower, LOAN_AMOUNT, &"x".repeat(101), 0);
+    assert_error(result, MicroLendingError::InvalidPoolConfiguration);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn pool_minimum_credit_score_is_enforced() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    set_policy(
+        &mut env,
+        BorrowerPolicy {
+            min_credit_score: 600,
+            ..open_policy()
+        },
+    );
+    let borrower = env.new_user();
+    let result = env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    assert_error(result, MicroLendingError::CreditScoreBelowPoolMinimum);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn pool_required_verification_is_enforced() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    set_policy(
+        &mut env,
+        BorrowerPolicy {
+            required_verifications: 1 << VerificationType::Kyc as u8,
+            ..open_policy()
+        },
+    );
+    let borrower = env.new_user();
+    let result = env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    assert_error(result, MicroLendingError::MissingRequiredVerification);
+
+    // Once a registered verifier records the KYC check the request goes through
+    let verifier = env.funded_keypair();
+    let instruction = instructions::add_verifier(&env.authority.pubkey(), verifier.pubkey());
+    env.send_as_authority(&[instruction]).unwrap();
+    let instruction = instructions::set_verification(
+        &verifier.pubkey(),
+        &borrower.pubkey(),
+        VerificationType::Kyc,
+        true,
+        None,
+        [7; 32],
+    );
+    env.send(&[instruction], &[&verifier]).unwrap();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn verification_requirements_update_only_that_part_of_the_policy() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    set_policy(
+        &mut env,
+        BorrowerPolicy {
+            min_credit_score: 300,
+            ..open_policy()
+        },
+    );
+    let instruction = instructions::set_pool_verification_requirements(
+        &env.authority.pubkey(),
+        &env.pool,
+        1 << VerificationType::Phone as u8,
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+    let policy = env.lending_pool().borrower_policy;
+    assert_eq!(
+        policy.required_verifications,
+        1 << VerificationType::Phone as u8
+    );
+    assert_eq!(policy.min_credit_score, 300);
+
+    let borrower = env.new_user();
+    let result = env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    assert_error(result, MicroLendingError::MissingRequiredVerification);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn verification_lapses_when_its_verifier_is_removed() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    set_policy(
+        &mut env,
+        BorrowerPolicy {
+            required_verifications: 1 << VerificationType::Kyc as u8,
+            ..open_policy()
+        },
+    );
+    let borrower = env.new_user();
+    let verifier = env.funded_keypair();
+    let instruction = instructions::add_verifier(&env.authority.pubkey(), verifier.pubkey());
+    env.send_as_authority(&[instruction]).unwrap();
+    let instruction = instructions::set_verification(
+        &verifier.pubkey(),
+        &borrower.pubkey(),
+        VerificationType::Kyc,
+        true,
+        None,
+        [7; 32],
+    );
+    env.send(&[instruction], &[&verifier]).unwrap();
+
+    let instruction = instructions::remove_verifier(&env.authority.pubkey(), verifier.pubkey());
+    env.send_as_authority(&[instruction]).unwrap();
+    let result = env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    assert_error(result, MicroLendingError::MissingRequiredVerification);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn pool_attestation_minimum_is_enforced() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    set_policy(
+        &mut env,
+        BorrowerPolicy {
+            min_attestations: 1,
+            ..open_policy()
+        },
+    );
+    let borrower = env.new_user();
+    let result = env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    assert_error(result, MicroLendingError::PoolAttestationMinimumNotMet);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn pool_collateral_types_are_enforced() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    set_policy(
+        &mut env,
+        BorrowerPolicy {
+            // Asset collateral only
+            allowed_collateral_types: 1 << 2,
+            ..open_policy()
+        },
+    );
+    let borrower = env.new_user();
+    let result = request(&mut env, &borrower, LOAN_AMOUNT, "inventory", 0);
+    assert_error(result, MicroLendingError::CollateralTypeNotAllowed);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn pool_purposes_are_enforced() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    set_policy(
+        &mut env,
+        BorrowerPolicy {
+            allowed_purposes: vec!["farming".to_string()],
+            ..open_policy()
+        },
+    );
+    let borrower = env.new_user();
+    let result = request(&mut env, &borrower, LOAN_AMOUNT, "school fees", 0);
+    assert_error(result, MicroLendingError::LoanPurposeNotAllowed);
+
+    // Purposes match case-insensitively
+    request(&mut env, &borrower, LOAN_AMOUNT, "Farming", 0).unwrap();
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn pool_per_borrower_limit_is_enforced() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    set_policy(
+        &mut env,
+        BorrowerPolicy {
+            max_loan_per_borrower: LOAN_AMOUNT - 1,
+            ..open_policy()
+        },
+    );
+    let borrower = env.new_user();
+    let result = env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    assert_error(result, MicroLendingError::LoanExceedsPoolBorrowerLimit);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn approved_loan_cannot_be_approved_again() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+    env.approve_loan(&borrower).unwrap();
+
+    let result = env.approve_loan(&borrower);
+    assert_error(result, MicroLendingError::InvalidLoanState);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn rejection_refunds_rent_only_to_the_borrower() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+
+    let impostor = env.funded_keypair();
+    let mut instruction =
+        instructions::reject_loan(&env.authority.pubkey(), &env.pool, &borrower.pubkey(), 1);
+    for meta in &mut instruction.accounts {
+        if meta.pubkey == borrower.pubkey() {
+            meta.pubkey = impostor.pubkey();
+        }
+    }
+    let result = env.send_as_authority(&[instruction]);
+    assert_error(result, MicroLendingError::InvalidBorrowerAccount);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn stale_request_expires_only_after_its_ttl() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let instruction = instructions::set_loan_ttls(&env.authority.pubkey(), &env.pool, 3600, 0);
+    env.send_as_authority(&[instruction]).unwrap();
+    let borrower = env.new_user();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+
+    let keeper = env.funded_keypair();
+    let instruction = instructions::expire_loan(&keeper.pubkey(), &env.pool, &borrower.pubkey());
+    let result = env.send(std::slice::from_ref(&instruction), &[&keeper]);
+    assert_error(result, MicroLendingError::LoanNotExpired);
+
+    env.warp(3601);
+    env.send(&[instruction], &[&keeper]).unwrap();
+    let loan = pda::loan(&borrower.pubkey(), &env.pool).0;
+    let lamports = env
+        .svm
+        .get_account(&loan)
+        .map_or(0, |account| account.lamports);
+    assert_eq!(lamports, 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn stale_request_cannot_be_approved() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let instruction = instructions::set_loan_ttls(&env.authority.pubkey(), &env.pool, 3600, 0);
+    env.send_as_authority(&[instruction]).unwrap();
+    let borrower = env.new_user();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+
+    env.warp(3601);
+    let result = env.approve_loan(&borrower);
+    assert_error(result, MicroLendingError::LoanExpired);
+    assert!(env.loan(&borrower).status == LoanStatus::Requested);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn stale_approval_cannot_be_disbursed() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let instruction = instructions::set_loan_ttls(&env.authority.pubkey(), &env.pool, 0, 3600);
+    env.send_as_authority(&[instruction]).unwrap();
+    let borrower = env.new_user();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+    env.approve_loan(&borrower).unwrap();
+
+    env.warp(3601);
+    let result = env.disburse_loan(&borrower);
+    assert_error(result, MicroLendingError::LoanExpired);
+    assert!(env.loan(&borrower).status == LoanStatus::Approved);
+    assert_eq!(env.lending_pool().total_reserved, LOAN_AMOUNT);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn committee_must_be_valid() {
+    let mut env = TestEnv::new();
+    let members = vec![env.funded_keypair().pubkey(), env.funded_keypair().pubkey()];
+    let result = create_committee(&mut env, members, 3);
+    assert_error(result, MicroLendingError::InvalidCommitteeConfiguration);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn committee_pool_needs_quorum_to_approve() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let member = env.funded_keypair();
+    create_committee(&mut env, vec![member.pubkey()], 1).unwrap();
+    let borrower = env.new_user();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+
+    // Approving without the committee account is refused outright
+    let result = env.approve_loan(&borrower);
+    assert_error(result, MicroLendingError::CommitteeRequired);
+
+    let approve =
+        instructions::approve_loan(&env.authority.pubkey(), &env.pool, &borrower.pubkey(), true);
+    let result = env.send_as_authority(std::slice::from_ref(&approve));
+    assert_error(result, MicroLendingError::QuorumNotReached);
+
+    vote(&mut env, &member, &borrower, true).unwrap();
+    env.send_as_authority(&[approve]).unwrap();
+    assert!(env.loan(&borrower).status == LoanStatus::Approved);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn committee_pool_skips_auto_approval() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let rules = AutoApprovalRules {
+        enabled: true,
+        max_utilization_bps: 10000,
+        score_bands: vec![ScoreBand {
+            min_credit_score: 0,
+            max_amount: MAX_LOAN,
+        }],
+    };
+    let instruction =
+        instructions::set_auto_approval_rules(&env.authority.pubkey(), &env.pool, rules);
+    env.send_as_authority(&[instruction]).unwrap();
+    let member = env.funded_keypair();
+    create_committee(&mut env, vec![member.pubkey()], 1).unwrap();
+
+    let borrower = env.new_user();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+    assert!(env.loan(&borrower).status == LoanStatus::Requested);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn removed_members_votes_do_not_count() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let (first, second) = (env.funded_keypair(), env.funded_keypair());
+    create_committee(&mut env, vec![first.pubkey(), second.pubkey()], 1).unwrap();
+    let borrower = env.new_user();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+    vote(&mut env, &first, &borrower, true).unwrap();
+
+    let instruction = instructions::update_loan_committee(
+        &env.authority.pubkey(),
+        &env.pool,
+        vec![second.pubkey()],
+        1,
+        3600,
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+    let approve =
+        instructions::approve_loan(&env.authority.pubkey(), &env.pool, &borrower.pubkey(), true);
+    let result = env.send_as_authority(std::slice::from_ref(&approve));
+    assert_error(result, MicroLendingError::QuorumNotReached);
+
+    vote(&mut env, &second, &borrower, true).unwrap();
+    env.send_as_authority(&[approve]).unwrap();
+    assert!(env.loan(&borrower).status == LoanStatus::Approved);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn committee_rejects_once_quorum_is_out_of_reach() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let members = [
+        env.funded_keypair(),
+        env.funded_keypair(),
+        env.funded_keypair(),
+    ];
+    let keys = members.iter().map(Keypair::pubkey).collect();
+    create_committee(&mut env, keys, 2).unwrap();
+    let borrower = env.new_user();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+
+    // One rejection of three still leaves two approvals possible
+    vote(&mut env, &members[0], &borrower, false).unwrap();
+    assert!(env.loan(&borrower).status == LoanStatus::Requested);
+
+    vote(&mut env, &members[1], &borrower, false).unwrap();
+    let loan = pda::loan(&borrower.pubkey(), &env.pool).0;
+    let lamports = env
+        .svm
+        .get_account(&loan)
+        .map_or(0, |account| account.lamports);
+    assert_eq!(lamports, 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn only_committee_members_vote() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let member = env.funded_keypair();
+    create_committee(&mut env, vec![member.pubkey()], 1).unwrap();
+    let borrower = env.new_user();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+
+    let outsider = env.funded_keypair();
+    let result = vote(&mut env, &outsider, &borrower, true);
+    assert_error(result, MicroLendingError::NotCommitteeMember);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn votes_close_with_the_voting_window() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let member = env.funded_keypair();
+    create_committee(&mut env, vec![member.pubkey()], 1).unwrap();
+    let borrower = env.new_user();
+    env.request_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS).unwrap();
+
+    env.warp(3601);
+    let result = vote(&mut env, &member, &borrower, true);
+    assert_error(result, MicroLendingError::VotingWindowClosed);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn inactive_loan_officer_cannot_originate() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let officer = env.funded_keypair();
+    let authority = env.authority.pubkey();
+    env.send_as_authority(&[
+        instructions::register_loan_officer(&authority, &env.pool, &officer.pubkey(), 1000),
+        instructions::update_loan_officer(&authority, &env.pool, &officer.pubkey(), 1000, false),
+    ])
+    .unwrap();
+
+    let borrower = env.new_user();
+    let instruction = instructions::request_loan(
+        &borrower.pubkey(),
+        &env.pool,
+        Some(&officer.pubkey()),
+        &[],
+        LOAN_AMOUNT,
+        LOAN_DAYS,
+        "inventory".to_string(),
+        0,
+    );
+    let result = env.send(&[instruction], &[&borrower, &officer]);
+    assert_error(result, MicroLendingError::LoanOfficerInactive);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn officer_loan_needs_the_officer_account() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let officer = env.funded_keypair();
+    let instruction = instructions::register_loan_officer(
+        &env.authority.pubkey(),
+        &env.pool,
+        &officer.pubkey(),
+        1000,
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+
+    let borrower = env.new_user();
+    let instruction = instructions::request_loan(
+        &borrower.pubkey(),
+        &env.pool,
+        Some(&officer.pubkey()),
+        &[],
+        LOAN_AMOUNT,
+        LOAN_DAYS,
+        "inventory".to_string(),
+        0,
+    );
+    env.send(&[instruction], &[&borrower, &officer]).unwrap();
+    env.approve_loan(&borrower).unwrap();
+
+    // Disbursing without the officer's account would skip their portfolio tracking
+    let result = env.disburse_loan(&borrower);
+    assert_error(result, MicroLendingError::LoanOfficerAccountRequired);
+
+    // The loan counts as the officer's origination once it goes out
+    let loan_officer = pda::loan_officer(&env.pool, &officer.pubkey()).0;
+    assert_eq!(
+        env.account::<LoanOfficer>(&loan_officer).loans_originated,
+        0
+    );
+    let instruction = instructions::disburse_loan(
+        &env.authority.pubkey(),
+        &env.mint,
+        &token_program(),
+        &borrower.pubkey(),
+        Some(&officer.pubkey()),
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+    let loan_officer = env.account::<LoanOfficer>(&loan_officer);
+    assert_eq!(loan_officer.loans_originated, 1);
+    assert_eq!(loan_officer.total_disbursed, LOAN_AMOUNT);
+}
+
+// A request the officer submits and pays for, with consent the borrower signed off-chain
+fn request_with_consent(
+    env: &mut TestEnv,
+    officer: &Keypair,
+    borrower: &Keypair,
+    consent: LoanConsent,
+    signed: &LoanConsent,
+) -> TransactionResult {
+    let signature = borrower.sign_message(&loan_consent_message(signed));
+    let instructions = instructions::request_loan_with_consent(
+        &borrower.pubkey(),
+        &[],
+        consent,
+        signature.as_ref().try_into().unwrap(),
+    );
+    env.send(&instructions, &[officer])
+}
+
+fn officer_with_borrower(env: &mut TestEnv) -> (Keypair, Keypair, LoanConsent) {
+    env.new_lender(POOL_DEPOSIT);
+    let officer = env.funded_keypair();
+    let instruction = instructions::register_loan_officer(
+        &env.authority.pubkey(),
+        &env.pool,
+        &officer.pubkey(),
+        1000,
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+    let borrower = env.new_user();
+    let consent = LoanConsent {
+        officer: officer.pubkey(),
+        lending_pool: env.pool,
+        amount: LOAN_AMOUNT,
+        duration_days: LOAN_DAYS,
+        purpose: "inventory".to_string(),
+        collateral_type: 0,
+        expires_at: env.now() + DAY,
+    };
+    (officer, borrower, consent)
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn officer_requests_and_pays_with_borrower_consent() {
+    let mut env = TestEnv::new();
+    let (officer, borrower, consent) = officer_with_borrower(&mut env);
+    let borrower_lamports = env.svm.get_balance(&borrower.pubkey()).unwrap();
+
+    request_with_consent(&mut env, &officer, &borrower, consent.clone(), &consent).unwrap();
+
+    let loan = env.loan(&borrower);
+    assert!(loan.status == LoanStatus::Requested);
+    assert_eq!(loan.loan_officer, Some(officer.pubkey()));
+    // The officer paid the loan account's rent, not the borrower
+    assert_eq!(
+        env.svm.get_balance(&borrower.pubkey()).unwrap(),
+        borrower_lamports
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn officer_request_without_borrower_signature_or_consent_is_rejected() {
+    let mut env = TestEnv::new();
+    let (officer, borrower, _) = officer_with_borrower(&mut env);
+    let mut instruction = instructions::request_loan(
+        &borrower.pubkey(),
+        &env.pool,
+        Some(&officer.pubkey()),
+        &[],
+        LOAN_AMOUNT,
+        LOAN_DAYS,
+        "inventory".to_string(),
+        0,
+    );
+    // Neither the borrower's signature nor the consent instruction, with the officer paying
+    instruction.accounts[4] = AccountMeta::new_readonly(borrower.pubkey(), false);
+    instruction.accounts[5] = AccountMeta::new(officer.pubkey(), true);
+    let result = env.send(&[instruction], &[&officer]);
+    assert_error(result, MicroLendingError::BorrowerConsentRequired);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn expired_borrower_consent_is_rejected() {
+    let mut env = TestEnv::new();
+    let (officer, borrower, mut consent) = officer_with_borrower(&mut env);
+    consent.expires_at = env.now();
+    let result = request_with_consent(&mut env, &officer, &borrower, consent.clone(), &consent);
+    assert_error(result, MicroLendingError::InvalidBorrowerConsent);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn borrower_consent_must_match_the_request() {
+    let mut env = TestEnv::new();
+    let (officer, borrower, consent) = officer_with_borrower(&mut env);
+    let requested = LoanConsent {
+        amount: consent.amount * 2,
+        ..consent.clone()
+    };
+    let result = request_with_consent(&mut env, &officer, &borrower, requested, &consent);
+    assert_error(result, MicroLendingError::InvalidBorrowerConsent);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn loan_quote_reports_the_pool_policy_rejection() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    set_policy(
+        &mut env,
+        BorrowerPolicy {
+            allowed_purposes: vec!["farming".to_string()],
+            min_attestations: 1,
+            ..open_policy()
+        },
+    );
+    let borrower = env.new_user();
+    let quote_for = |env: &mut TestEnv, purpose: &str| -> LoanQuote {
+        let instruction = instructions::quote_loan(
+            &borrower.pubkey(),
+            &env.pool,
+            &[],
+            LOAN_AMOUNT,
+            LOAN_DAYS,
+            purpose.to_string(),
+            0,
+        );
+        env.simulate_quote(instruction, &borrower)
+    };
+
+    let quote = quote_for(&mut env, "farming");
+    assert_eq!(
+        quote.rejection_code,
+        Some(MicroLendingError::PoolAttestationMinimumNotMet.into())
+    );
+    let result = request(&mut env, &borrower, LOAN_AMOUNT, "farming", 0);
+    assert_error(result, MicroLendingError::PoolAttestationMinimumNotMet);
+
+    set_policy(
+        &mut env,
+        BorrowerPolicy {
+            allowed_purposes: vec!["farming".to_string()],
+            ..open_policy()
+        },
+    );
+    let quote = quote_for(&mut env, "school fees");
+    assert_eq!(
+        quote.rejection_code,
+        Some(MicroLendingError::LoanPurposeNotAllowed.into())
+    );
+    assert_eq!(quote_for(&mut env, "farming").rejection_code, None);
+}
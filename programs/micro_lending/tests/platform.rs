@@ -0,0 +1,279 @@
+// Helpers return LiteSVM's TransactionResult, whose error carries transaction metadata by value
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::*;
+use litesvm::types::TransactionResult;
+use micro_lending::error::MicroLendingError;
+use micro_lending::{
+    calculate_attestation_bonus, SocialAttestation, VerificationType, MAX_VERIFIERS,
+};
+use micro_lending_client::{instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn add_verifier(env: &mut TestEnv, verifier: Pubkey) -> TransactionResult {
+    let instruction = instructions::add_verifier(&env.authority.pubkey(), verifier);
+    env.send_as_authority(&[instruction])
+}
+
+fn attest(
+    env: &mut TestEnv,
+    attester: &Keypair,
+    user: &Pubkey,
+    attestation_type: u8,
+    score: u16,
+    expires_at: Option<i64>,
+) -> TransactionResult {
+    let instruction = instructions::add_attestation(
+        &attester.pubkey(),
+        user,
+        false,
+        attestation_type,
+        score,
+        "Known at the market".to_string(),
+        expires_at,
+    );
+    env.send(&[instruction], &[attester])
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn lending_ladder_steps_must_be_in_range() {
+    let mut env = TestEnv::new();
+    // A step up below 100% would shrink limits after an on-time repayment
+    let instruction =
+        instructions::set_lending_ladder(&env.authority.pubkey(), LOAN_AMOUNT, 5000, 5000);
+    let result = env.send_as_authority(&[instruction]);
+    assert_error(result, MicroLendingError::InvalidPlatformConfiguration);
+}
+
+// Authority checks use `has_one`, so a stranger gets Anchor's constraint error rather than
+// MicroLendingError::Unauthorized, which no instruction raises
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn only_the_platform_authority_configures_the_platform() {
+    let mut env = TestEnv::new();
+    let stranger = env.funded_keypair();
+    let instruction =
+        instructions::set_lending_ladder(&stranger.pubkey(), LOAN_AMOUNT, 15000, 5000);
+    let result = env.send(&[instruction], &[&stranger]);
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::ConstraintHasOne as u32,
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn verifier_registry_rejects_duplicates_and_unknown_verifiers() {
+    let mut env = TestEnv::new();
+    let verifier = Pubkey::new_unique();
+    add_verifier(&mut env, verifier).unwrap();
+    let result = add_verifier(&mut env, verifier);
+    assert_error(result, MicroLendingError::VerifierAlreadyRegistered);
+
+    let instruction = instructions::remove_verifier(&env.authority.pubkey(), Pubkey::new_unique());
+    let result = env.send_as_authority(&[instruction]);
+    assert_error(result, MicroLendingError::VerifierNotRegistered);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn verifier_registry_is_capped() {
+    let mut env = TestEnv::new();
+    for _ in 0..MAX_VERIFIERS {
+        add_verifier(&mut env, Pubkey::new_unique()).unwrap();
+    }
+    let result = add_verifier(&mut env, Pubkey::new_unique());
+    assert_error(result, MicroLendingError::VerifierRegistryFull);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn only_registered_verifiers_set_verifications() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let impostor = env.funded_keypair();
+    let instruction = instructions::set_verification(
+        &impostor.pubkey(),
+        &user.pubkey(),
+        VerificationType::Phone,
+        true,
+        None,
+        [1; 32],
+    );
+    let result = env.send(&[instruction], &[&impostor]);
+    assert_error(result, MicroLendingError::VerifierNotRegistered);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn verification_expiry_must_be_in_the_future() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let verifier = env.funded_keypair();
+    add_verifier(&mut env, verifier.pubkey()).unwrap();
+    let instruction = instructions::set_verification(
+        &verifier.pubkey(),
+        &user.pubkey(),
+        VerificationType::Email,
+        true,
+        Some(env.now()),
+        [1; 32],
+    );
+    let result = env.send(&[instruction], &[&verifier]);
+    assert_error(result, MicroLendingError::InvalidExpiry);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn attestation_score_is_capped() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let attester = env.funded_keypair();
+    let result = attest(&mut env, &attester, &user.pubkey(), 0, 1001, None);
+    assert_error(result, MicroLendingError::InvalidAttestation);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn unknown_attestation_type_is_rejected() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let attester = env.funded_keypair();
+    let result = attest(&mut env, &attester, &user.pubkey(), 6, 700, None);
+    assert_error(result, MicroLendingError::InvalidAttestationType);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn users_cannot_attest_for_themselves() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let result = attest(&mut env, &user, &user.pubkey(), 0, 700, None);
+    assert_error(result, MicroLendingError::SocialAttestationValidationFailed);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn attestation_expires_on_schedule() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let attester = env.funded_keypair();
+    let expires_at = env.now() + DAY;
+    attest(
+        &mut env,
+        &attester,
+        &user.pubkey(),
+        1,
+        700,
+        Some(expires_at),
+    )
+    .unwrap();
+    assert_eq!(env.user_profile(&user).social_attestations_count, 1);
+
+    // Expiry is permissionless; the keeper only pays the fee
+    let keeper = env.funded_keypair();
+    let expire = instructions::expire_attestation(&user.pubkey(), &attester.pubkey());
+    let result = env.send(std::slice::from_ref(&expire), &[&keeper]);
+    assert_error(result, MicroLendingError::AttestationNotExpired);
+
+    env.warp_days(2);
+    let instruction = instructions::update_attestation(
+        &attester.pubkey(),
+        &user.pubkey(),
+        false,
+        800,
+        "Still trading".to_string(),
+    );
+    let result = env.send(&[instruction], &[&attester]);
+    assert_error(result, MicroLendingError::AttestationExpired);
+
+    env.send(&[expire], &[&keeper]).unwrap();
+    assert_eq!(env.user_profile(&user).social_attestations_count, 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn verified_attestations_raise_the_credit_score() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let attester = env.funded_keypair();
+    let instruction = instructions::register_attester(
+        &env.authority.pubkey(),
+        &attester.pubkey(),
+        "Savings group".to_string(),
+        1 << 3,
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+    let instruction = instructions::add_attestation(
+        &attester.pubkey(),
+        &user.pubkey(),
+        true,
+        3,
+        800,
+        "Treasurer of the savings group".to_string(),
+        None,
+    );
+    env.send(&[instruction], &[&attester]).unwrap();
+
+    let instruction = instructions::update_credit_score(
+        &env.authority.pubkey(),
+        &user.pubkey(),
+        &[attester.pubkey()],
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+    assert_eq!(
+        env.user_profile(&user).credit_score,
+        300 + calculate_attestation_bonus(&[800])
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn deactivated_attesters_no_longer_verify_their_attestations() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let attester = env.funded_keypair();
+    let authority = env.authority.pubkey();
+    env.send_as_authority(&[instructions::register_attester(
+        &authority,
+        &attester.pubkey(),
+        "Savings group".to_string(),
+        1 << 3,
+    )])
+    .unwrap();
+    let instruction = instructions::add_attestation(
+        &attester.pubkey(),
+        &user.pubkey(),
+        true,
+        3,
+        800,
+        "Treasurer of the savings group".to_string(),
+        None,
+    );
+    env.send(&[instruction], &[&attester]).unwrap();
+    env.send_as_authority(&[instructions::update_attester(
+        &authority,
+        &attester.pubkey(),
+        1 << 3,
+        false,
+    )])
+    .unwrap();
+
+    // The attestation is still marked verified, but its attester no longer backs it
+    let address = pda::social_attestation(&user.pubkey(), &attester.pubkey()).0;
+    let instruction =
+        instructions::update_credit_score(&authority, &user.pubkey(), &[attester.pubkey()]);
+    env.send_as_authority(&[instruction]).unwrap();
+    assert_eq!(env.user_profile(&user).credit_score, 300);
+
+    // Renewing it drops the verification for good
+    let instruction =
+        instructions::renew_attestation(&attester.pubkey(), &user.pubkey(), true, None);
+    env.send(&[instruction], &[&attester]).unwrap();
+    let attestation: SocialAttestation = env.account(&address);
+    assert!(!attestation.verified);
+}
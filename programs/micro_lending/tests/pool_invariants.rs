@@ -0,0 +1,288 @@
+//! Property-based fuzzing of pool accounting. Random sequences of deposits, loans,
+//! repayments, claims, withdrawals, liquidations and clock warps run against the program in
+//! LiteSVM, and the pool's accounting invariants are checked after every step.
+//!
+//! `PROPTEST_CASES` sets the number of sequences (32 by default).
+
+// Helpers return LiteSVM's TransactionResult, whose error carries transaction metadata by value
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::*;
+use litesvm::types::TransactionResult;
+use micro_lending::{LendingPool, LoanStatus};
+use micro_lending_client::{instructions, pda};
+use proptest::prelude::*;
+use proptest::test_runner::{Config, TestCaseError, TestRunner};
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+const LENDERS: usize = 3;
+const MAX_ACTIONS: usize = 40;
+
+#[derive(Debug, Clone)]
+enum Action {
+    Deposit { lender: usize, amount: u64 },
+    // Fractions are in basis points of the lender's shares or the loan's payoff
+    Withdraw { lender: usize, fraction: u16 },
+    Claim { lender: usize },
+    // Each loan goes to a new borrower, since a borrower's loan account stays on the pool
+    Borrow { amount: u64, duration_days: u32 },
+    Repay { loan: usize, fraction: u16 },
+    Liquidate { loan: usize },
+    Warp { days: i64 },
+}
+
+fn fraction() -> impl Strategy<Value = u16> {
+    prop_oneof![Just(10_000u16), 1..10_000u16]
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        3 => (0..LENDERS, MIN_LOAN..=POOL_DEPOSIT / 2)
+            .prop_map(|(lender, amount)| Action::Deposit { lender, amount }),
+        2 => (0..LENDERS, fraction())
+            .prop_map(|(lender, fraction)| Action::Withdraw { lender, fraction }),
+        1 => (0..LENDERS).prop_map(|lender| Action::Claim { lender }),
+        // Up to 10% of the platform maximum, within reach of a new user's credit score
+        3 => (MIN_LOAN..=MAX_LOAN / 10, 1..=90u32)
+            .prop_map(|(amount, duration_days)| Action::Borrow { amount, duration_days }),
+        3 => (any::<usize>(), fraction())
+            .prop_map(|(loan, fraction)| Action::Repay { loan, fraction }),
+        1 => any::<usize>().prop_map(|loan| Action::Liquidate { loan }),
+        2 => (1..=30i64).prop_map(|days| Action::Warp { days }),
+    ]
+}
+
+// What the test knows about each lender, independently of the program's bookkeeping
+#[derive(Default)]
+struct LenderLedger {
+    deposited: u128,
+    earned: u128,
+    extracted: u128,
+}
+
+struct Harness {
+    env: TestEnv,
+    lenders: Vec<Keypair>,
+    ledgers: Vec<LenderLedger>,
+    loans: Vec<Keypair>,
+    steps: u128,
+}
+
+// Liquidity, reservations and outstanding principal: everything lenders have a claim on
+fn gross_assets(pool: &LendingPool) -> u128 {
+    pool.available_liquidity as u128 + pool.total_reserved as u128 + pool.total_borrowed as u128
+}
+
+impl Harness {
+    fn new(mut env: TestEnv) -> Self {
+        let lenders = (0..LENDERS).map(|_| env.new_user()).collect();
+        Harness {
+            env,
+            lenders,
+            ledgers: (0..LENDERS).map(|_| LenderLedger::default()).collect(),
+            loans: Vec::new(),
+            steps: 0,
+        }
+    }
+
+    fn shares(&self, lender: usize) -> u64 {
+        let address = pda::lender_deposit(&self.lenders[lender].pubkey(), &self.env.pool).0;
+        match self.env.svm.get_account(&address) {
+            Some(_) => self.env.lender_deposit(&self.lenders[lender]).shares,
+            None => 0,
+        }
+    }
+
+    fn balance(&self, lender: usize) -> u128 {
+        self.env.token_balance(&self.lenders[lender].pubkey()) as u128
+    }
+
+    fn loan(&self, index: usize) -> Option<&Keypair> {
+        (!self.loans.is_empty()).then(|| &self.loans[index % self.loans.len()])
+    }
+
+    // Runs a lender transaction and records what it paid out to them
+    fn lender_send(&mut self, lender: usize, instruction: Instruction) -> TransactionResult {
+        let before = self.balance(lender);
+        let signer = self.lenders[lender].insecure_clone();
+        let result = self.env.send(&[instruction], &[&signer]);
+        if result.is_ok() {
+            self.ledgers[lender].extracted += self.balance(lender).saturating_sub(before);
+        }
+        result
+    }
+
+    fn apply(&mut self, action: &Action) -> Result<(), TestCaseError> {
+        let mint = self.env.mint;
+        let pool = self.env.pool;
+        let result: Option<TransactionResult> = match *action {
+            Action::Deposit { lender, amount } => {
+                let signer = self.lenders[lender].insecure_clone();
+                let result = self.env.deposit(&signer, amount);
+                if result.is_ok() {
+                    self.ledgers[lender].deposited += amount as u128;
+                }
+                Some(result)
+            }
+            Action::Withdraw { lender, fraction } => {
+                let shares = self.shares(lender) as u128 * fraction as u128 / 10_000;
+                if shares == 0 {
+                    return Ok(());
+                }
+                let lender_key = self.lenders[lender].pubkey();
+                let instruction = instructions::withdraw_from_pool(
+                    &lender_key,
+                    &pool,
+                    &mint,
+                    &token_program(),
+                    shares as u64,
+                );
+                Some(self.lender_send(lender, instruction))
+            }
+            Action::Claim { lender } => {
+                let lender_key = self.lenders[lender].pubkey();
+                let instruction =
+                    instructions::claim_interest(&lender_key, &pool, &mint, &token_program());
+                Some(self.lender_send(lender, instruction))
+            }
+            Action::Borrow {
+                amount,
+                duration_days,
+            } => {
+                let borrower = self.env.new_user();
+                let opened = self
+                    .env
+                    .request_loan(&borrower, amount, duration_days)
+                    .and_then(|_| self.env.approve_loan(&borrower))
+                    .and_then(|_| self.env.disburse_loan(&borrower));
+                if opened.is_ok() {
+                    self.loans.push(borrower);
+                }
+                Some(opened)
+            }
+            Action::Repay { loan, fraction } => {
+                let Some(borrower) = self.loan(loan).map(Keypair::insecure_clone) else {
+                    return Ok(());
+                };
+                if !is_outstanding(&self.env, &borrower) {
+                    return Ok(());
+                }
+                let total_due = self.env.payoff_quote(&borrower).total_due;
+                let amount = (total_due as u128 * fraction as u128 / 10_000).max(1) as u64;
+
+                // Whatever the pool gains beyond returned principal is lender income
+                let pool_before = self.env.lending_pool();
+                let result = self.env.repay(&borrower, amount);
+                if result.is_ok() {
+                    let income = gross_assets(&self.env.lending_pool())
+                        .saturating_sub(gross_assets(&pool_before));
+                    self.credit_income(income, pool_before.total_shares);
+                }
+                Some(result)
+            }
+            Action::Liquidate { loan } => {
+                let Some(borrower) = self.loan(loan).map(Keypair::insecure_clone) else {
+                    return Ok(());
+                };
+                Some(self.env.liquidate(&borrower))
+            }
+            Action::Warp { days } => {
+                self.env.warp_days(days);
+                None
+            }
+        };
+        self.steps += 1;
+
+        if let Some(Err(failed)) = result {
+            // Rejections must come from a require!, not a panic or an arithmetic fault
+            prop_assert!(
+                matches!(
+                    failed.err,
+                    TransactionError::InstructionError(_, InstructionError::Custom(_))
+                ),
+                "{:?} failed with {:?}; logs: {:#?}",
+                action,
+                failed.err,
+                failed.meta.logs
+            );
+        }
+        self.check_invariants(action)
+    }
+
+    fn credit_income(&mut self, income: u128, total_shares: u64) {
+        if total_shares == 0 {
+            return;
+        }
+        for lender in 0..LENDERS {
+            let shares = self.shares(lender) as u128;
+            self.ledgers[lender].earned += income * shares / total_shares as u128;
+        }
+    }
+
+    fn check_invariants(&self, action: &Action) -> Result<(), TestCaseError> {
+        let pool = self.env.lending_pool();
+
+        // Reserved liquidity stays in the pool's token account until disbursed
+        let balance = self.env.pool_token_balance();
+        prop_assert!(
+            balance as u128 >= pool.available_liquidity as u128 + pool.total_reserved as u128,
+            "after {:?}: pool holds {} tokens, available {} + reserved {}",
+            action,
+            balance,
+            pool.available_liquidity,
+            pool.total_reserved
+        );
+
+        let shares: u64 = (0..LENDERS).map(|lender| self.shares(lender)).sum();
+        prop_assert_eq!(shares, pool.total_shares, "after {:?}", action);
+
+        // Share and interest math round down, but allow a unit per step either way
+        for (lender, ledger) in self.ledgers.iter().enumerate() {
+            prop_assert!(
+                ledger.extracted <= ledger.deposited + ledger.earned + self.steps,
+                "after {:?}: lender {} extracted {} of {} deposited and {} earned",
+                action,
+                lender,
+                ledger.extracted,
+                ledger.deposited,
+                ledger.earned
+            );
+        }
+        Ok(())
+    }
+}
+
+fn is_outstanding(env: &TestEnv, borrower: &Keypair) -> bool {
+    let status = env.loan(borrower).status;
+    status == LoanStatus::Disbursed || status == LoanStatus::Active
+}
+
+fn run(actions: Vec<Action>) -> Result<(), TestCaseError> {
+    let env = TestEnv::new();
+    let mut harness = Harness::new(env);
+    for action in &actions {
+        harness.apply(action)?;
+    }
+    Ok(())
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn pool_accounting_invariants_hold() {
+    let cases = std::env::var("PROPTEST_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(32);
+    let mut runner = TestRunner::new(Config {
+        cases,
+        ..Config::default()
+    });
+    let result = runner.run(&prop::collection::vec(action(), 1..=MAX_ACTIONS), run);
+    if let Err(failure) = result {
+        panic!("{failure}");
+    }
+}
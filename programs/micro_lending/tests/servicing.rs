@@ -0,0 +1,726 @@
+// Helpers return LiteSVM's TransactionResult, whose error carries transaction metadata by value
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::*;
+use litesvm::types::TransactionResult;
+use micro_lending::error::MicroLendingError;
+use micro_lending::{
+    calculate_late_fee, calculate_simple_interest, expected_installment, get_unclaimed_interest,
+    Attester, LoanOfficer, LoanOutcome, LoanStatus, PayoffQuote, RepaymentIncentives,
+};
+use micro_lending_client::{instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn last_outcome(env: &TestEnv, borrower: &Keypair) -> LoanOutcome {
+    env.user_profile(borrower)
+        .recent_outcomes
+        .last()
+        .expect("no loan outcome recorded")
+        .outcome
+}
+
+fn grant_forbearance(env: &mut TestEnv, borrower: &Keypair, days: u16) -> TransactionResult {
+    let instruction = instructions::grant_forbearance(
+        &env.authority.pubkey(),
+        &env.pool,
+        &borrower.pubkey(),
+        days,
+    );
+    env.send_as_authority(&[instruction])
+}
+
+fn restructure(
+    env: &mut TestEnv,
+    borrower: &Keypair,
+    duration_days: u32,
+    interest_rate: u16,
+) -> TransactionResult {
+    restructure_with(env, borrower, duration_days, interest_rate, true)
+}
+
+// Restructuring needs the borrower's consent as well as the pool authority's
+fn restructure_with(
+    env: &mut TestEnv,
+    borrower: &Keypair,
+    duration_days: u32,
+    interest_rate: u16,
+    capitalize_arrears: bool,
+) -> TransactionResult {
+    let instruction = instructions::restructure_loan(
+        &env.authority.pubkey(),
+        &env.pool,
+        &borrower.pubkey(),
+        None,
+        duration_days,
+        interest_rate,
+        capitalize_arrears,
+    );
+    let authority = env.authority();
+    env.send(&[instruction], &[&authority, borrower])
+}
+
+fn refinance(env: &mut TestEnv, borrower: &Keypair, amount: u64) -> TransactionResult {
+    refinance_with(env, borrower, &[], &[], &[], amount)
+}
+
+fn refinance_with(
+    env: &mut TestEnv,
+    borrower: &Keypair,
+    backing_attesters: &[Pubkey],
+    attesters: &[Pubkey],
+    members: &[&Keypair],
+    amount: u64,
+) -> TransactionResult {
+    let created_at = env.loan(borrower).created_at;
+    let member_keys: Vec<Pubkey> = members.iter().map(|member| member.pubkey()).collect();
+    let instruction = instructions::refinance_loan(
+        &env.authority.pubkey(),
+        &env.mint,
+        &token_program(),
+        &borrower.pubkey(),
+        None,
+        created_at,
+        backing_attesters,
+        attesters,
+        &member_keys,
+        amount,
+        LOAN_DAYS,
+    );
+    let authority = env.authority();
+    let mut signers = vec![&authority, borrower];
+    signers.extend_from_slice(members);
+    env.send(&[instruction], &signers)
+}
+
+// Registers an attester who adds a verified attestation of the borrower
+fn attest(env: &mut TestEnv, borrower: &Keypair) -> Keypair {
+    let attester = env.funded_keypair();
+    let instruction = instructions::register_attester(
+        &env.authority.pubkey(),
+        &attester.pubkey(),
+        "Market association".to_string(),
+        1,
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+    let instruction = instructions::add_attestation(
+        &attester.pubkey(),
+        &borrower.pubkey(),
+        true,
+        0,
+        800,
+        "Stall holder for six years".to_string(),
+        None,
+    );
+    env.send(&[instruction], &[&attester]).unwrap();
+    attester
+}
+
+// Opens a loan on social collateral, backed by a registered attester's verified attestation
+fn open_attested_loan(env: &mut TestEnv) -> (Keypair, Keypair) {
+    let borrower = env.new_user();
+    let attester = attest(env, &borrower);
+    let instruction = instructions::request_loan(
+        &borrower.pubkey(),
+        &env.pool,
+        None,
+        &[attester.pubkey()],
+        LOAN_AMOUNT,
+        LOAN_DAYS,
+        "inventory".to_string(),
+        1,
+    );
+    env.send(&[instruction], &[&borrower]).unwrap();
+    env.approve_loan(&borrower).unwrap();
+    env.disburse_loan(&borrower).unwrap();
+    (borrower, attester)
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn late_fee_accrues_after_the_grace_period() {
+    let mut env = TestEnv::new();
+    let lender = env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    // Ten days past the due date, three past the 7-day grace period
+    env.warp_days(LOAN_DAYS as i64 + 10);
+    let quote = env.payoff_quote(&borrower);
+    let expected_fee = calculate_late_fee(
+        quote.outstanding_principal + quote.interest_accrued,
+        500,
+        10,
+    )
+    .unwrap();
+    assert!(expected_fee > 0);
+    assert_eq!(quote.late_fee, expected_fee);
+
+    env.repay(&borrower, quote.total_due).unwrap();
+    assert!(env.loan(&borrower).status == LoanStatus::Repaid);
+    assert!(last_outcome(&env, &borrower) == LoanOutcome::RepaidLate);
+
+    // The fee goes to lenders with the interest and does not count as principal
+    let pool = env.lending_pool();
+    assert_eq!(pool.total_borrowed, 0);
+    assert_eq!(
+        get_unclaimed_interest(&env.lender_deposit(&lender), &pool),
+        quote.interest_accrued + quote.late_fee
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn repayment_within_the_grace_period_is_on_time() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    // Last second of the grace period
+    env.warp_days(LOAN_DAYS as i64 + 7);
+    let quote = env.payoff_quote(&borrower);
+    assert_eq!(quote.late_fee, 0);
+
+    env.repay(&borrower, quote.total_due).unwrap();
+    assert!(last_outcome(&env, &borrower) == LoanOutcome::RepaidOnTime);
+    assert_eq!(env.user_profile(&borrower).active_loans, 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn loan_is_liquidated_only_after_the_grace_period() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    env.warp_days(LOAN_DAYS as i64 + 7);
+    let result = env.liquidate(&borrower);
+    assert_error(result, MicroLendingError::LoanNotYetDueForLiquidation);
+
+    env.warp(1);
+    env.liquidate(&borrower).unwrap();
+    assert!(env.loan(&borrower).status == LoanStatus::Liquidated);
+    assert!(last_outcome(&env, &borrower) == LoanOutcome::Defaulted);
+    assert_eq!(env.user_profile(&borrower).defaulted_loans, 1);
+    assert_eq!(env.platform().total_defaults, 1);
+    assert_eq!(env.lending_pool().total_borrowed, 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn repayment_must_be_positive_and_within_the_balance() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    let result = env.repay(&borrower, 0);
+    assert_error(result, MicroLendingError::InvalidPaymentAmount);
+
+    env.warp_days(5);
+    let quote = env.payoff_quote(&borrower);
+    let result = env.repay(&borrower, quote.total_due + 1);
+    assert_error(result, MicroLendingError::InvalidPaymentAmount);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn forbearance_defers_liquidation() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    let result = grant_forbearance(&mut env, &borrower, 0);
+    assert_error(result, MicroLendingError::InvalidForbearancePeriod);
+
+    let due_date = env.loan(&borrower).due_date;
+    grant_forbearance(&mut env, &borrower, 30).unwrap();
+    assert_eq!(env.loan(&borrower).due_date, due_date + 30 * DAY);
+
+    env.warp_days(10);
+    let result = env.liquidate(&borrower);
+    assert_error(result, MicroLendingError::LoanInForbearance);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn disaster_declaration_extends_outstanding_loans() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    // Only loans disbursed before the declaration are covered
+    env.warp_days(1);
+    let instruction = instructions::declare_disaster(&env.authority.pubkey(), &env.pool, 30);
+    env.send_as_authority(&[instruction]).unwrap();
+
+    // Past the original grace period, but the due date has moved out by 30 days
+    env.warp_days(LOAN_DAYS as i64 + 9);
+    assert_eq!(env.payoff_quote(&borrower).late_fee, 0);
+    let result = env.liquidate(&borrower);
+    assert_error(result, MicroLendingError::LoanNotYetDueForLiquidation);
+
+    env.warp_days(30);
+    env.liquidate(&borrower).unwrap();
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn restructure_capitalizes_arrears_up_to_the_limit() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    let interest_rate = env.loan(&borrower).interest_rate;
+
+    env.warp_days(10);
+    let result = restructure(&mut env, &borrower, 0, 1200);
+    assert_error(result, MicroLendingError::InvalidRestructureTerms);
+
+    restructure(&mut env, &borrower, 60, 1200).unwrap();
+    let arrears = calculate_simple_interest(LOAN_AMOUNT, interest_rate, 10).unwrap();
+    let loan = env.loan(&borrower);
+    assert_eq!(loan.amount, LOAN_AMOUNT + arrears);
+    assert_eq!(loan.interest_rate, 1200);
+    assert_eq!(loan.due_date, env.now() + 60 * DAY);
+    assert_eq!(loan.accrual_start, env.now());
+    assert_eq!(env.lending_pool().total_borrowed, LOAN_AMOUNT + arrears);
+
+    restructure(&mut env, &borrower, 60, 1200).unwrap();
+    restructure(&mut env, &borrower, 60, 1200).unwrap();
+    let result = restructure(&mut env, &borrower, 60, 1200);
+    assert_error(result, MicroLendingError::RestructureLimitReached);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn restructure_in_forbearance_capitalizes_no_late_fee() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    let interest_rate = env.loan(&borrower).interest_rate;
+
+    // Still past the grace period after the extension, but in forbearance
+    env.warp_days(LOAN_DAYS as i64 + 20);
+    grant_forbearance(&mut env, &borrower, 10).unwrap();
+    assert_eq!(env.payoff_quote(&borrower).late_fee, 0);
+
+    restructure(&mut env, &borrower, 60, interest_rate).unwrap();
+    let arrears = calculate_simple_interest(LOAN_AMOUNT, interest_rate, LOAN_DAYS + 20).unwrap();
+    assert_eq!(env.loan(&borrower).amount, LOAN_AMOUNT + arrears);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn repricing_without_capitalizing_keeps_interest_to_date_at_the_old_rate() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    let interest_rate = env.loan(&borrower).interest_rate;
+
+    env.warp_days(10);
+    restructure_with(&mut env, &borrower, 60, 1200, false).unwrap();
+    let arrears = calculate_simple_interest(LOAN_AMOUNT, interest_rate, 10).unwrap();
+    let loan = env.loan(&borrower);
+    assert_eq!(loan.amount, LOAN_AMOUNT);
+    assert_eq!(loan.carried_interest, arrears);
+    assert_eq!(loan.accrual_start, env.now());
+
+    // The new rate only applies from the restructure on
+    env.warp_days(5);
+    let repriced = calculate_simple_interest(LOAN_AMOUNT, 1200, 5).unwrap();
+    assert_eq!(
+        env.payoff_quote(&borrower).interest_accrued,
+        arrears + repriced
+    );
+
+    // The next payment settles what was carried over
+    env.repay(&borrower, LOAN_AMOUNT / 2).unwrap();
+    assert_eq!(env.loan(&borrower).carried_interest, 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn refinance_pays_off_the_loan_and_disburses_the_difference() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    let original = env.loan(&borrower);
+
+    env.warp_days(10);
+    let payoff =
+        LOAN_AMOUNT + calculate_simple_interest(LOAN_AMOUNT, original.interest_rate, 10).unwrap();
+    assert_eq!(env.payoff_quote(&borrower).total_due, payoff);
+    let result = refinance(&mut env, &borrower, LOAN_AMOUNT);
+    assert_error(result, MicroLendingError::RefinanceAmountTooLow);
+
+    let balance = env.token_balance(&borrower.pubkey());
+    refinance(&mut env, &borrower, 2 * LOAN_AMOUNT).unwrap();
+    assert_eq!(
+        env.token_balance(&borrower.pubkey()),
+        balance + 2 * LOAN_AMOUNT - payoff
+    );
+
+    let loan = env.loan(&borrower);
+    let loan_address = pda::loan(&borrower.pubkey(), &env.pool).0;
+    assert_eq!(loan.amount, 2 * LOAN_AMOUNT);
+    assert_eq!(
+        loan.refinanced_from,
+        Some(pda::refinanced_loan(&loan_address, original.created_at).0)
+    );
+    assert_eq!(loan.due_date, env.now() + LOAN_DAYS as i64 * DAY);
+}
+
+// Opens a loan the officer requested, returning the officer's keypair
+fn open_officer_loan(env: &mut TestEnv, borrower: &Keypair) -> Keypair {
+    let officer = env.new_user();
+    let instruction = instructions::register_loan_officer(
+        &env.authority.pubkey(),
+        &env.pool,
+        &officer.pubkey(),
+        1000,
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+    let instruction = instructions::request_loan(
+        &borrower.pubkey(),
+        &env.pool,
+        Some(&officer.pubkey()),
+        &[],
+        LOAN_AMOUNT,
+        LOAN_DAYS,
+        "inventory".to_string(),
+        0,
+    );
+    env.send(&[instruction], &[borrower, &officer]).unwrap();
+    env.approve_loan(borrower).unwrap();
+    let instruction = instructions::disburse_loan(
+        &env.authority.pubkey(),
+        &env.mint,
+        &token_program(),
+        &borrower.pubkey(),
+        Some(&officer.pubkey()),
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+    officer
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn officer_refinance_needs_an_active_officer_and_counts_only_the_difference() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    let officer = open_officer_loan(&mut env, &borrower);
+    env.warp_days(10);
+
+    let refinance = |env: &TestEnv| {
+        instructions::refinance_loan(
+            &env.authority.pubkey(),
+            &env.mint,
+            &token_program(),
+            &borrower.pubkey(),
+            Some(&officer.pubkey()),
+            env.loan(&borrower).created_at,
+            &[],
+            &[],
+            &[],
+            2 * LOAN_AMOUNT,
+            LOAN_DAYS,
+        )
+    };
+    let set_active = |env: &TestEnv, is_active| {
+        instructions::update_loan_officer(
+            &env.authority.pubkey(),
+            &env.pool,
+            &officer.pubkey(),
+            1000,
+            is_active,
+        )
+    };
+    let authority = env.authority();
+
+    let instruction = set_active(&env, false);
+    env.send_as_authority(&[instruction]).unwrap();
+    let instruction = refinance(&env);
+    let result = env.send(&[instruction], &[&authority, &borrower]);
+    assert_error(result, MicroLendingError::LoanOfficerInactive);
+
+    let instruction = set_active(&env, true);
+    env.send_as_authority(&[instruction]).unwrap();
+    let balance = env.token_balance(&borrower.pubkey());
+    let instruction = refinance(&env);
+    env.send(&[instruction], &[&authority, &borrower]).unwrap();
+    let net_disbursement = env.token_balance(&borrower.pubkey()) - balance;
+
+    let loan_officer = pda::loan_officer(&env.pool, &officer.pubkey()).0;
+    let loan_officer = env.account::<LoanOfficer>(&loan_officer);
+    assert_eq!(loan_officer.total_disbursed, LOAN_AMOUNT + net_disbursement);
+    assert_eq!(loan_officer.outstanding_principal, 2 * LOAN_AMOUNT);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn overdue_loan_cannot_be_refinanced() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    env.warp_days(LOAN_DAYS as i64 + 8);
+    let result = refinance(&mut env, &borrower, 2 * LOAN_AMOUNT);
+    assert_error(result, MicroLendingError::RefinanceNotAllowed);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn repayment_settles_backing_attesters() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let (borrower, attester) = open_attested_loan(&mut env);
+    assert_eq!(
+        env.loan(&borrower).backing_attesters,
+        vec![attester.pubkey()]
+    );
+
+    env.warp_days(10);
+    let quote = env.payoff_quote(&borrower);
+    let result = env.repay(&borrower, quote.total_due);
+    assert_error(result, MicroLendingError::MissingAttesterAccount);
+
+    let instruction = instructions::repay_loan(
+        &borrower.pubkey(),
+        &env.pool,
+        &env.mint,
+        &token_program(),
+        None,
+        &[attester.pubkey()],
+        quote.total_due,
+    );
+    env.send(&[instruction], &[&borrower]).unwrap();
+    let registry: Attester = env.account(&pda::attester(&attester.pubkey()).0);
+    assert_eq!(registry.successful_outcomes, 1);
+    assert_eq!(registry.reputation_score, 510);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn payoff_quote_matches_the_program() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    env.warp_days(LOAN_DAYS as i64 + 12);
+    let instruction = instructions::quote_payoff(&borrower.pubkey(), &env.pool);
+    let quote: PayoffQuote = env.simulate_quote(instruction, &borrower);
+    assert_eq!(quote, env.payoff_quote(&borrower));
+    assert!(quote.late_fee > 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn platform_fee_is_paid_to_the_treasury() {
+    let mut env = TestEnv::with_platform_fee(100);
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    env.warp_days(10);
+    let pool_balance = env.pool_token_balance();
+    let payment = LOAN_AMOUNT / 5;
+    env.repay(&borrower, payment).unwrap();
+
+    // The fee is charged on top of the amount applied to the loan
+    let fee = payment * 100 / 10100;
+    assert_eq!(env.token_balance(&pda::platform().0), fee);
+    assert_eq!(env.pool_token_balance(), pool_balance + payment - fee);
+    assert!(env.loan(&borrower).status == LoanStatus::Active);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn interest_accrues_from_the_last_payment_at_the_stepped_down_rate() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let instruction = instructions::set_repayment_incentives(
+        &env.authority.pubkey(),
+        &env.pool,
+        RepaymentIncentives {
+            streak_length: 1,
+            rate_step_down_bps: 200,
+            min_interest_rate: 0,
+            early_payoff_rebate_bps: 0,
+        },
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    let disbursed = env.loan(&borrower);
+
+    // A weekly installment of a 30-day loan is a fifth of the schedule
+    env.warp_days(7);
+    env.warp(3600);
+    let installment = expected_installment(&disbursed);
+    assert_eq!(installment, disbursed.scheduled_repayment / 5);
+    env.repay(&borrower, installment).unwrap();
+
+    let loan = env.loan(&borrower);
+    assert_eq!(loan.interest_rate, disbursed.interest_rate - 200);
+    // Only whole days were charged, so the hour carries into the next period
+    assert_eq!(loan.accrual_start, disbursed.accrual_start + 7 * DAY);
+
+    env.warp_days(7);
+    let outstanding = loan.amount - loan.amount_repaid;
+    let expected = calculate_simple_interest(outstanding, loan.interest_rate, 7).unwrap();
+    assert_eq!(env.payoff_quote(&borrower).interest_accrued, expected);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn payoff_quote_clears_the_loan_in_one_payment_with_a_platform_fee() {
+    let mut env = TestEnv::with_platform_fee(100);
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    env.warp_days(LOAN_DAYS as i64 + 12);
+    let quote = env.payoff_quote(&borrower);
+    assert!(quote.platform_fee > 0 && quote.late_fee > 0);
+    let result = env.repay(&borrower, quote.total_due + quote.platform_fee + 1);
+    assert_error(result, MicroLendingError::InvalidPaymentAmount);
+
+    env.repay(&borrower, quote.total_due + quote.platform_fee)
+        .unwrap();
+    assert!(env.loan(&borrower).status == LoanStatus::Repaid);
+    assert_eq!(env.token_balance(&pda::platform().0), quote.platform_fee);
+    assert_eq!(env.lending_pool().total_borrowed, 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn refinance_pays_off_like_make_payment() {
+    let mut env = TestEnv::with_platform_fee(100);
+    env.new_lender(POOL_DEPOSIT);
+    let instruction = instructions::set_repayment_incentives(
+        &env.authority.pubkey(),
+        &env.pool,
+        RepaymentIncentives {
+            streak_length: 0,
+            rate_step_down_bps: 0,
+            min_interest_rate: 0,
+            early_payoff_rebate_bps: 5000,
+        },
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+
+    env.warp_days(10);
+    let quote = env.payoff_quote(&borrower);
+    assert!(quote.early_payoff_rebate > 0 && quote.platform_fee > 0);
+    let balance = env.token_balance(&borrower.pubkey());
+    refinance(&mut env, &borrower, 2 * LOAN_AMOUNT).unwrap();
+    assert_eq!(
+        env.token_balance(&borrower.pubkey()),
+        balance + 2 * LOAN_AMOUNT - quote.total_due - quote.platform_fee
+    );
+    assert_eq!(env.token_balance(&pda::platform().0), quote.platform_fee);
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn refinance_settles_attesters_and_needs_valid_attestations() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let (borrower, attester) = open_attested_loan(&mut env);
+
+    env.warp_days(10);
+    // Social collateral still needs a verified attestation
+    let result = refinance_with(
+        &mut env,
+        &borrower,
+        &[attester.pubkey()],
+        &[],
+        &[],
+        2 * LOAN_AMOUNT,
+    );
+    assert_error(result, MicroLendingError::InsufficientAttestations);
+    // The current loan's attester is settled even when others back the new loan
+    let other_attester = attest(&mut env, &borrower);
+    let result = refinance_with(
+        &mut env,
+        &borrower,
+        &[],
+        &[other_attester.pubkey()],
+        &[],
+        2 * LOAN_AMOUNT,
+    );
+    assert_error(result, MicroLendingError::MissingAttesterAccount);
+
+    refinance_with(
+        &mut env,
+        &borrower,
+        &[attester.pubkey()],
+        &[attester.pubkey()],
+        &[],
+        2 * LOAN_AMOUNT,
+    )
+    .unwrap();
+    let registry: Attester = env.account(&pda::attester(&attester.pubkey()).0);
+    assert_eq!(registry.successful_outcomes, 1);
+    assert_eq!(
+        env.loan(&borrower).backing_attesters,
+        vec![attester.pubkey()]
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy/micro_lending.so; run with --ignored after `anchor build`"]
+fn committee_pool_refinance_needs_the_quorum() {
+    let mut env = TestEnv::new();
+    env.new_lender(POOL_DEPOSIT);
+    let borrower = env.new_user();
+    env.open_loan(&borrower, LOAN_AMOUNT, LOAN_DAYS);
+    let members = [env.funded_keypair(), env.funded_keypair()];
+    let instruction = instructions::create_loan_committee(
+        &env.authority.pubkey(),
+        &env.pool,
+        members.iter().map(|member| member.pubkey()).collect(),
+        2,
+        3600,
+    );
+    env.send_as_authority(&[instruction]).unwrap();
+
+    env.warp_days(10);
+    let result = refinance(&mut env, &borrower, 2 * LOAN_AMOUNT);
+    assert_error(result, MicroLendingError::CommitteeRequired);
+    let result = refinance_with(
+        &mut env,
+        &borrower,
+        &[],
+        &[],
+        &[&members[0]],
+        2 * LOAN_AMOUNT,
+    );
+    assert_error(result, MicroLendingError::QuorumNotReached);
+
+    refinance_with(
+        &mut env,
+        &borrower,
+        &[],
+        &[],
+        &[&members[0], &members[1]],
+        2 * LOAN_AMOUNT,
+    )
+    .unwrap();
+    assert_eq!(env.loan(&borrower).amount, 2 * LOAN_AMOUNT);
+}
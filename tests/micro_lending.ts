@@ -183,7 +183,9 @@ describe("micro-lending", () => {
         treasuryBump,
         100, // 1% platform fee
         new BN(1000 * 1_000_000), // Max loan
-        new BN(10 * 1_000_000)   // Min loan
+        new BN(10 * 1_000_000),  // Min loan
+        365, // Credit score half-life in days
+        180  // Inactivity threshold in days
       )
       .accounts({
         payer: authority.publicKey,
@@ -196,6 +198,22 @@ describe("micro-lending", () => {
     expect(platformAccount.isActive).to.be.true;
   });
 
+  it("Sets the platform lending ladder", async () => {
+    await program.methods
+      .setLendingLadder(
+        new BN(1000 * 1_000_000), // First loan cap
+        15000, // 1.5x after an on-time repayment
+        5000   // 0.5x after a late repayment or default
+      )
+      .accounts({
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const platformAccount = await program.account.platform.fetch(platformPda);
+    expect(platformAccount.lendingLadder.stepUpBps).to.equal(15000);
+  });
+
   it("Initializes user profiles for lender and borrower", async () => {
     // Initialize lender
     await program.methods
@@ -250,6 +268,44 @@ describe("micro-lending", () => {
     expect(poolAccount.baseInterestRate).to.equal(500);
   });
 
+  it("Sets a borrower eligibility policy on the pool", async () => {
+    await program.methods
+      .setBorrowerPolicy({
+        minCreditScore: 300,
+        requiredVerifications: 0,
+        minAttestations: 0,
+        allowedCollateralTypes: 0b11111,
+        maxLoanPerBorrower: new BN(200 * 1_000_000),
+        allowedPurposes: [],
+      })
+      .accounts({
+        lendingPool: lendingPoolPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const poolAccount = await program.account.lendingPool.fetch(lendingPoolPda);
+    expect(poolAccount.borrowerPolicy.minCreditScore).to.equal(300);
+  });
+
+  it("Sets repayment incentives on the pool", async () => {
+    await program.methods
+      .setRepaymentIncentives({
+        streakLength: 3,
+        rateStepDownBps: 50,
+        minInterestRate: 500,
+        earlyPayoffRebateBps: 2000,
+      })
+      .accounts({
+        lendingPool: lendingPoolPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const poolAccount = await program.account.lendingPool.fetch(lendingPoolPda);
+    expect(poolAccount.repaymentIncentives.streakLength).to.equal(3);
+  });
+
   it("Allows a lender to deposit into the pool", async () => {
     const depositAmount = new BN(500 * 1_000_000); // 500 tokens
 
@@ -399,6 +455,49 @@ describe("micro-lending", () => {
     expect(borrowerProfile.successfulLoans).to.equal(0);
   });
 
+  it("Restructures an active loan with the borrower's consent", async () => {
+    const before = await program.account.loan.fetch(loanPda);
+
+    await program.methods
+      .restructureLoan(60, before.interestRate, true)
+      .accounts({
+        platform: platformPda,
+        loan: loanPda,
+        lendingPool: lendingPoolPda,
+        userProfile: borrowerProfilePda,
+        authority: authority.publicKey,
+        borrower: borrower.publicKey,
+      })
+      .signers([borrower])
+      .rpc();
+
+    const after = await program.account.loan.fetch(loanPda);
+    expect(after.restructureCount).to.equal(1);
+    expect(after.durationDays).to.equal(60);
+    expect(after.dueDate.toNumber()).to.be.greaterThan(before.dueDate.toNumber());
+    expect(after.amount.gte(before.amount)).to.be.true;
+
+    const borrowerProfile = await program.account.userProfile.fetch(borrowerProfilePda);
+    expect(borrowerProfile.restructuredLoans).to.equal(1);
+  });
+
+  it("Grants forbearance on an active loan", async () => {
+    const before = await program.account.loan.fetch(loanPda);
+
+    await program.methods
+      .grantForbearance(14)
+      .accounts({
+        loan: loanPda,
+        lendingPool: lendingPoolPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const after = await program.account.loan.fetch(loanPda);
+    expect(after.forbearanceCount).to.equal(1);
+    expect(after.dueDate.toNumber()).to.equal(before.dueDate.toNumber() + 14 * 24 * 60 * 60);
+  });
+
   // =================================================================================================
   // 4. INTEREST AND WITHDRAWALS 
   // =================================================================================================
@@ -450,8 +549,25 @@ describe("micro-lending", () => {
   // =================================================================================================
   // 5. REPUTATION & DATA 
   // =================================================================================================
+  it("Registers an attester for community attestations", async () => {
+    const [attesterRegistryPda] = PublicKey.findProgramAddressSync([Buffer.from("attester"), attester.publicKey.toBuffer()], program.programId);
+
+    await program.methods
+      .registerAttester("Village council", 1 << 0) // Community only
+      .accounts({
+        authority: authority.publicKey,
+        attester: attester.publicKey,
+      })
+      .rpc();
+
+    const registry = await program.account.attester.fetch(attesterRegistryPda);
+    expect(registry.reputationScore).to.equal(500);
+    expect(registry.isActive).to.be.true;
+  });
+
   it("Adds a social attestation", async () => {
     const [attestationPda] = PublicKey.findProgramAddressSync([Buffer.from("social_attestation"), borrower.publicKey.toBuffer(), attester.publicKey.toBuffer()], program.programId);
+    const [attesterRegistryPda] = PublicKey.findProgramAddressSync([Buffer.from("attester"), attester.publicKey.toBuffer()], program.programId);
 
     await program.methods
       .addAttestation({ community: {} }, 950, "Highly recommended", null)
@@ -460,6 +576,7 @@ describe("micro-lending", () => {
         user: borrower.publicKey,
         userProfile: borrowerProfilePda,
         socialAttestation: attestationPda,
+        attesterRegistry: attesterRegistryPda,
         systemProgram: SystemProgram.programId,
       })
       .signers([attester])
@@ -467,6 +584,39 @@ describe("micro-lending", () => {
 
     const attestation = await program.account.socialAttestation.fetch(attestationPda);
     expect(attestation.score).to.equal(950);
+    expect(attestation.verified).to.be.true;
+  });
+
+  it("Updates and revokes a social attestation", async () => {
+    const [attestationPda] = PublicKey.findProgramAddressSync([Buffer.from("social_attestation"), borrower.publicKey.toBuffer(), attester.publicKey.toBuffer()], program.programId);
+    const [attesterRegistryPda] = PublicKey.findProgramAddressSync([Buffer.from("attester"), attester.publicKey.toBuffer()], program.programId);
+
+    await program.methods
+      .updateAttestation(800, "Still recommended")
+      .accounts({
+        attester: attester.publicKey,
+        user: borrower.publicKey,
+        attesterRegistry: attesterRegistryPda,
+      })
+      .signers([attester])
+      .rpc();
+
+    const attestation = await program.account.socialAttestation.fetch(attestationPda);
+    expect(attestation.score).to.equal(800);
+
+    await program.methods
+      .revokeAttestation()
+      .accounts({
+        attester: attester.publicKey,
+        user: borrower.publicKey,
+      })
+      .signers([attester])
+      .rpc();
+
+    const revoked = await program.account.socialAttestation.fetchNullable(attestationPda);
+    expect(revoked).to.be.null;
+    const profile = await program.account.userProfile.fetch(borrowerProfilePda);
+    expect(profile.socialAttestationsCount).to.equal(0);
   });
 
   it("Adds transaction history", async () => {
@@ -490,6 +640,30 @@ describe("micro-lending", () => {
     expect(updatedProfile.transactionHistoryCount).to.equal(1);
   });
 
+  it("Lets a registered verifier mark a user as KYC verified", async () => {
+    await program.methods
+      .addVerifier(authority.publicKey)
+      .accounts({
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .setVerification({ kyc: {} }, true, null, Array(32).fill(7))
+      .accounts({
+        verifier: authority.publicKey,
+        user: borrower.publicKey,
+      })
+      .rpc();
+
+    const [recordPda] = PublicKey.findProgramAddressSync([Buffer.from("verification"), borrower.publicKey.toBuffer(), Buffer.from([0])], program.programId);
+    const record = await program.account.verificationRecord.fetch(recordPda);
+    expect(record.verifier.toBase58()).to.equal(authority.publicKey.toBase58());
+
+    const profile = await program.account.userProfile.fetch(borrowerProfilePda);
+    expect(profile.kycVerified).to.be.true;
+  });
+
   it("Updates a user's credit score", async () => {
     await program.methods
       .updateCreditScore()